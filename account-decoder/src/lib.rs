@@ -9,6 +9,7 @@ pub mod parse_address_lookup_table;
 pub mod parse_bpf_loader;
 pub mod parse_config;
 pub mod parse_nonce;
+pub mod parse_pyth_oracle;
 pub mod parse_stake;
 pub mod parse_sysvar;
 pub mod parse_token;
@@ -4,6 +4,7 @@ use {
         parse_bpf_loader::parse_bpf_upgradeable_loader,
         parse_config::parse_config,
         parse_nonce::parse_nonce,
+        parse_pyth_oracle::parse_pyth_oracle,
         parse_stake::parse_stake,
         parse_sysvar::parse_sysvar,
         parse_token::{parse_token, spl_token_2022_id, spl_token_id},
@@ -12,7 +13,8 @@ use {
     inflector::Inflector,
     serde_json::Value,
     solana_sdk::{
-        instruction::InstructionError, pubkey::Pubkey, stake, system_program, sysvar, vote,
+        instruction::InstructionError, pubkey::Pubkey, pyth_oracle, stake, system_program, sysvar,
+        vote,
     },
     std::collections::HashMap,
     thiserror::Error,
@@ -22,6 +24,7 @@ lazy_static! {
     static ref ADDRESS_LOOKUP_PROGRAM_ID: Pubkey = solana_address_lookup_table_program::id();
     static ref BPF_UPGRADEABLE_LOADER_PROGRAM_ID: Pubkey = solana_sdk::bpf_loader_upgradeable::id();
     static ref CONFIG_PROGRAM_ID: Pubkey = solana_config_program::id();
+    static ref PYTH_ORACLE_PROGRAM_ID: Pubkey = pyth_oracle::id();
     static ref STAKE_PROGRAM_ID: Pubkey = stake::program::id();
     static ref SYSTEM_PROGRAM_ID: Pubkey = system_program::id();
     static ref SYSVAR_PROGRAM_ID: Pubkey = sysvar::id();
@@ -38,6 +41,7 @@ lazy_static! {
         );
         m.insert(*CONFIG_PROGRAM_ID, ParsableAccount::Config);
         m.insert(*SYSTEM_PROGRAM_ID, ParsableAccount::Nonce);
+        m.insert(*PYTH_ORACLE_PROGRAM_ID, ParsableAccount::PythOracle);
         m.insert(spl_token_id(), ParsableAccount::SplToken);
         m.insert(spl_token_2022_id(), ParsableAccount::SplToken2022);
         m.insert(*STAKE_PROGRAM_ID, ParsableAccount::Stake);
@@ -80,6 +84,7 @@ pub enum ParsableAccount {
     BpfUpgradeableLoader,
     Config,
     Nonce,
+    PythOracle,
     SplToken,
     SplToken2022,
     Stake,
@@ -111,6 +116,7 @@ pub fn parse_account_data(
         }
         ParsableAccount::Config => serde_json::to_value(parse_config(data, pubkey)?)?,
         ParsableAccount::Nonce => serde_json::to_value(parse_nonce(data)?)?,
+        ParsableAccount::PythOracle => serde_json::to_value(parse_pyth_oracle(data)?)?,
         ParsableAccount::SplToken | ParsableAccount::SplToken2022 => {
             serde_json::to_value(parse_token(data, additional_data.spl_token_decimals)?)?
         }
@@ -173,5 +179,25 @@ mod test {
         .unwrap();
         assert_eq!(parsed.program, "nonce".to_string());
         assert_eq!(parsed.space, State::size() as u64);
+
+        let price_account = pyth_oracle::PriceAccount {
+            header: pyth_oracle::AccountHeader {
+                magic: pyth_oracle::MAGIC,
+                version: pyth_oracle::VERSION,
+                account_type: pyth_oracle::AccountType::Price as u32,
+                size: std::mem::size_of::<pyth_oracle::PriceAccount>() as u32,
+            },
+            ..pyth_oracle::PriceAccount::default()
+        };
+        let price_account_data = bytemuck::bytes_of(&price_account);
+        let parsed = parse_account_data(
+            &account_pubkey,
+            &pyth_oracle::id(),
+            price_account_data,
+            None,
+        )
+        .unwrap();
+        assert_eq!(parsed.program, "pyth-oracle".to_string());
+        assert_eq!(parsed.parsed["type"], "price");
     }
 }
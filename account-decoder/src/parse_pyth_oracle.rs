@@ -0,0 +1,335 @@
+use {
+    crate::parse_account_data::{ParsableAccount, ParseAccountError},
+    solana_sdk::{
+        clock::{Slot, UnixTimestamp},
+        pyth_oracle::{
+            AccountHeader, AccountType, MappingAccount, PriceAccount, PriceEma, PriceInfo,
+            PriceStatus, ProductAccount,
+        },
+    },
+    std::collections::BTreeMap,
+};
+
+pub fn parse_pyth_oracle(data: &[u8]) -> Result<PythOracleAccountType, ParseAccountError> {
+    let header = AccountHeader::from_account_data(data).ok_or(
+        ParseAccountError::AccountNotParsable(ParsableAccount::PythOracle),
+    )?;
+    let parsed_account = match AccountType::from_u32(header.account_type) {
+        Some(AccountType::Mapping) => MappingAccount::from_account_data(data).map(|mapping| {
+            PythOracleAccountType::Mapping(UiMappingAccount {
+                next_mapping_account: mapping.next_mapping_account.to_string(),
+                products: mapping
+                    .products(data)
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect(),
+            })
+        }),
+        Some(AccountType::Product) => ProductAccount::from_account_data(data).map(|product| {
+            PythOracleAccountType::Product(UiProductAccount {
+                price_account: product.price_account.to_string(),
+                attributes: product.attributes(data).into_iter().collect(),
+            })
+        }),
+        Some(AccountType::Price) => PriceAccount::from_account_data(data).map(|price| {
+            PythOracleAccountType::Price(UiPriceAccount {
+                price_type: price.price_type,
+                exponent: price.exponent,
+                num_quoters: price.num_quoters,
+                last_slot: price.last_slot,
+                valid_slot: price.valid_slot,
+                ema_price: price.ema_price.into(),
+                ema_conf: price.ema_conf.into(),
+                timestamp: price.timestamp,
+                min_publishers: price.min_publishers,
+                product_account: price.product_account.to_string(),
+                next_price_account: price.next_price_account.to_string(),
+                prev_slot: price.prev_slot,
+                prev_price: price.prev_price,
+                prev_conf: price.prev_conf,
+                prev_timestamp: price.prev_timestamp,
+                agg: price.agg.into(),
+                components: price
+                    .components(data)
+                    .into_iter()
+                    .map(|component| UiPriceComponent {
+                        publisher: component.publisher.to_string(),
+                        agg: component.agg.into(),
+                        latest: component.latest.into(),
+                    })
+                    .collect(),
+            })
+        }),
+        None => None,
+    };
+    parsed_account.ok_or(ParseAccountError::AccountNotParsable(
+        ParsableAccount::PythOracle,
+    ))
+}
+
+/// A wrapper enum for consistency across programs
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", tag = "type", content = "info")]
+pub enum PythOracleAccountType {
+    Mapping(UiMappingAccount),
+    Product(UiProductAccount),
+    Price(UiPriceAccount),
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UiMappingAccount {
+    pub next_mapping_account: String,
+    pub products: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UiProductAccount {
+    pub price_account: String,
+    pub attributes: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UiPriceAccount {
+    pub price_type: u32,
+    pub exponent: i32,
+    pub num_quoters: u32,
+    pub last_slot: Slot,
+    pub valid_slot: Slot,
+    pub ema_price: UiPriceEma,
+    pub ema_conf: UiPriceEma,
+    pub timestamp: UnixTimestamp,
+    pub min_publishers: u8,
+    pub product_account: String,
+    pub next_price_account: String,
+    pub prev_slot: Slot,
+    pub prev_price: i64,
+    pub prev_conf: u64,
+    pub prev_timestamp: UnixTimestamp,
+    pub agg: UiPriceInfo,
+    pub components: Vec<UiPriceComponent>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UiPriceInfo {
+    pub price: i64,
+    pub conf: u64,
+    pub status: UiPriceStatus,
+    pub corp_act: u32,
+    pub pub_slot: Slot,
+}
+
+impl From<PriceInfo> for UiPriceInfo {
+    fn from(info: PriceInfo) -> Self {
+        Self {
+            price: info.price,
+            conf: info.conf,
+            status: PriceStatus::from_u32(info.status).into(),
+            corp_act: info.corp_act,
+            pub_slot: info.pub_slot,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum UiPriceStatus {
+    Unknown,
+    Trading,
+    Halted,
+    Auction,
+    Ignored,
+}
+
+impl From<PriceStatus> for UiPriceStatus {
+    fn from(status: PriceStatus) -> Self {
+        match status {
+            PriceStatus::Unknown => Self::Unknown,
+            PriceStatus::Trading => Self::Trading,
+            PriceStatus::Halted => Self::Halted,
+            PriceStatus::Auction => Self::Auction,
+            PriceStatus::Ignored => Self::Ignored,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UiPriceEma {
+    pub val: i64,
+    pub numer: i64,
+    pub denom: i64,
+}
+
+impl From<PriceEma> for UiPriceEma {
+    fn from(ema: PriceEma) -> Self {
+        Self {
+            val: ema.val,
+            numer: ema.numer,
+            denom: ema.denom,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UiPriceComponent {
+    pub publisher: String,
+    pub agg: UiPriceInfo,
+    pub latest: UiPriceInfo,
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        solana_sdk::{
+            pubkey::Pubkey,
+            pyth_oracle::{PriceComponent, MAGIC, VERSION},
+        },
+        std::mem::size_of,
+    };
+
+    fn header(account_type: AccountType, size: usize) -> AccountHeader {
+        AccountHeader {
+            magic: MAGIC,
+            version: VERSION,
+            account_type: account_type as u32,
+            size: size as u32,
+        }
+    }
+
+    #[test]
+    fn test_parse_mapping() {
+        let product = Pubkey::new_unique();
+        let mapping = MappingAccount {
+            header: header(AccountType::Mapping, size_of::<MappingAccount>() + 32),
+            num_products: 1,
+            ..MappingAccount::default()
+        };
+        let mut data = bytemuck::bytes_of(&mapping).to_vec();
+        data.extend_from_slice(product.as_ref());
+        assert_eq!(
+            parse_pyth_oracle(&data).unwrap(),
+            PythOracleAccountType::Mapping(UiMappingAccount {
+                next_mapping_account: Pubkey::default().to_string(),
+                products: vec![product.to_string()],
+            }),
+        );
+    }
+
+    #[test]
+    fn test_parse_product() {
+        let price_account = Pubkey::new_unique();
+        let attributes = b"\x06symbol\x0eCrypto.BTC/USD";
+        let product = ProductAccount {
+            header: header(
+                AccountType::Product,
+                size_of::<ProductAccount>() + attributes.len(),
+            ),
+            price_account,
+        };
+        let mut data = bytemuck::bytes_of(&product).to_vec();
+        data.extend_from_slice(attributes);
+        assert_eq!(
+            parse_pyth_oracle(&data).unwrap(),
+            PythOracleAccountType::Product(UiProductAccount {
+                price_account: price_account.to_string(),
+                attributes: BTreeMap::from([("symbol".to_string(), "Crypto.BTC/USD".to_string())]),
+            }),
+        );
+    }
+
+    #[test]
+    fn test_parse_price() {
+        let publisher = Pubkey::new_unique();
+        let agg = PriceInfo {
+            price: 2_000_000,
+            conf: 300,
+            status: PriceStatus::Trading as u32,
+            corp_act: 0,
+            pub_slot: 41,
+        };
+        let price = PriceAccount {
+            header: header(
+                AccountType::Price,
+                size_of::<PriceAccount>() + size_of::<PriceComponent>(),
+            ),
+            exponent: -5,
+            num_components: 1,
+            num_quoters: 1,
+            last_slot: 41,
+            valid_slot: 40,
+            ema_price: PriceEma {
+                val: 1_999_000,
+                numer: 3_998_000,
+                denom: 2,
+            },
+            timestamp: 1_680_000_000,
+            agg,
+            ..PriceAccount::default()
+        };
+        let component = PriceComponent {
+            publisher,
+            agg,
+            latest: PriceInfo {
+                pub_slot: 42,
+                ..agg
+            },
+        };
+        let mut data = bytemuck::bytes_of(&price).to_vec();
+        data.extend_from_slice(bytemuck::bytes_of(&component));
+
+        let ui_agg = UiPriceInfo {
+            price: 2_000_000,
+            conf: 300,
+            status: UiPriceStatus::Trading,
+            corp_act: 0,
+            pub_slot: 41,
+        };
+        assert_eq!(
+            parse_pyth_oracle(&data).unwrap(),
+            PythOracleAccountType::Price(UiPriceAccount {
+                price_type: 0,
+                exponent: -5,
+                num_quoters: 1,
+                last_slot: 41,
+                valid_slot: 40,
+                ema_price: UiPriceEma {
+                    val: 1_999_000,
+                    numer: 3_998_000,
+                    denom: 2,
+                },
+                ema_conf: UiPriceEma {
+                    val: 0,
+                    numer: 0,
+                    denom: 0,
+                },
+                timestamp: 1_680_000_000,
+                min_publishers: 0,
+                product_account: Pubkey::default().to_string(),
+                next_price_account: Pubkey::default().to_string(),
+                prev_slot: 0,
+                prev_price: 0,
+                prev_conf: 0,
+                prev_timestamp: 0,
+                agg: ui_agg,
+                components: vec![UiPriceComponent {
+                    publisher: publisher.to_string(),
+                    agg: agg.into(),
+                    latest: UiPriceInfo {
+                        pub_slot: 42,
+                        ..UiPriceInfo::from(agg)
+                    },
+                }],
+            }),
+        );
+
+        let bad_data = vec![0; 4];
+        assert!(parse_pyth_oracle(&bad_data).is_err());
+        assert!(parse_pyth_oracle(&data[..size_of::<AccountHeader>()]).is_err());
+    }
+}
@@ -8,6 +8,7 @@ use {
     bincode::deserialize,
     bv::BitVec,
     solana_sdk::{
+        accumulator::{Accumulator, MessageType},
         clock::{Clock, Epoch, Slot, UnixTimestamp},
         epoch_schedule::EpochSchedule,
         pubkey::Pubkey,
@@ -17,6 +18,7 @@ use {
         stake_history::{StakeHistory, StakeHistoryEntry},
         sysvar::{self, rewards::Rewards},
     },
+    std::collections::BTreeMap,
 };
 
 pub fn parse_sysvar(data: &[u8], pubkey: &Pubkey) -> Result<SysvarAccountType, ParseAccountError> {
@@ -82,6 +84,10 @@ pub fn parse_sysvar(data: &[u8], pubkey: &Pubkey) -> Result<SysvarAccountType, P
                     .collect();
                 SysvarAccountType::StakeHistory(stake_history)
             })
+        } else if pubkey == &sysvar::accumulator::id() {
+            deserialize::<Accumulator>(data)
+                .ok()
+                .map(|accumulator| SysvarAccountType::Accumulator(accumulator.into()))
         } else {
             None
         }
@@ -105,6 +111,7 @@ pub enum SysvarAccountType {
     SlotHashes(Vec<UiSlotHashEntry>),
     SlotHistory(UiSlotHistory),
     StakeHistory(Vec<UiStakeHistoryEntry>),
+    Accumulator(UiAccumulator),
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -218,13 +225,51 @@ pub struct UiStakeHistoryEntry {
     pub stake_history: StakeHistoryEntry,
 }
 
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UiAccumulator {
+    pub slot: Slot,
+    pub root: String,
+    pub num_messages: u64,
+    pub message_types: BTreeMap<UiAccumulatorMessageType, u64>,
+}
+
+impl From<Accumulator> for UiAccumulator {
+    fn from(accumulator: Accumulator) -> Self {
+        let mut message_types = BTreeMap::new();
+        for message in &accumulator.messages {
+            let message_type = match message.first().copied().and_then(MessageType::from_u8) {
+                Some(MessageType::PriceFeed) => UiAccumulatorMessageType::PriceFeed,
+                None => UiAccumulatorMessageType::Unknown,
+            };
+            *message_types.entry(message_type).or_default() += 1;
+        }
+        Self {
+            slot: accumulator.slot,
+            root: accumulator.root.to_string(),
+            num_messages: accumulator.messages.len() as u64,
+            message_types,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum UiAccumulatorMessageType {
+    PriceFeed,
+    Unknown,
+}
+
 #[cfg(test)]
 mod test {
     #[allow(deprecated)]
     use solana_sdk::sysvar::recent_blockhashes::IterItem;
     use {
         super::*,
-        solana_sdk::{account::create_account_for_test, fee_calculator::FeeCalculator, hash::Hash},
+        solana_sdk::{
+            account::create_account_for_test, accumulator::PriceFeedMessage,
+            fee_calculator::FeeCalculator, hash::Hash,
+        },
     };
 
     #[test]
@@ -329,6 +374,29 @@ mod test {
             }]),
         );
 
+        let accumulator = Accumulator {
+            slot: 42,
+            root: hash,
+            messages: vec![
+                PriceFeedMessage::default().to_bytes(),
+                PriceFeedMessage::default().to_bytes(),
+                vec![255; 8],
+            ],
+        };
+        let accumulator_sysvar = create_account_for_test(&accumulator);
+        assert_eq!(
+            parse_sysvar(&accumulator_sysvar.data, &sysvar::accumulator::id()).unwrap(),
+            SysvarAccountType::Accumulator(UiAccumulator {
+                slot: 42,
+                root: hash.to_string(),
+                num_messages: 3,
+                message_types: BTreeMap::from([
+                    (UiAccumulatorMessageType::PriceFeed, 2),
+                    (UiAccumulatorMessageType::Unknown, 1),
+                ]),
+            }),
+        );
+
         let bad_pubkey = solana_sdk::pubkey::new_rand();
         assert!(parse_sysvar(&stake_history_sysvar.data, &bad_pubkey).is_err());
 
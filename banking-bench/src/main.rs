@@ -450,6 +450,7 @@ fn main() {
             None,
             Arc::new(connection_cache),
             bank_forks.clone(),
+            Arc::default(),
         );
         poh_recorder.write().unwrap().set_bank(&bank, false);
 
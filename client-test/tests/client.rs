@@ -157,6 +157,7 @@ fn test_account_subscription() {
         encoding: None,
         data_slice: None,
         min_context_slot: None,
+        at_slot: None,
     });
     let (mut client, receiver) = PubsubClient::account_subscribe(
         &format!("ws://0.0.0.0:{}/", pubsub_addr.port()),
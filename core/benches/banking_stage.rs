@@ -10,7 +10,7 @@ use {
     rayon::prelude::*,
     solana_client::connection_cache::ConnectionCache,
     solana_core::{
-        banking_stage::{BankingStage, BankingStageStats},
+        banking_stage::{account_affinity::AccountAffinity, BankingStage, BankingStageStats},
         banking_trace::{BankingPacketBatch, BankingTracer},
         leader_slot_banking_stage_metrics::LeaderSlotMetricsTracker,
         qos_service::QosService,
@@ -105,6 +105,7 @@ fn bench_consume_buffered(bencher: &mut Bencher) {
                 &QosService::new(1),
                 &mut LeaderSlotMetricsTracker::new(0),
                 None,
+                &AccountAffinity::new_unshared(1),
             );
         });
 
@@ -290,6 +291,7 @@ fn bench_banking(bencher: &mut Bencher, tx_type: TransactionType) {
             None,
             Arc::new(ConnectionCache::default()),
             bank_forks,
+            Arc::default(),
         );
         poh_recorder.write().unwrap().set_bank(&bank, false);
 
@@ -4,6 +4,7 @@
 
 use {
     self::{
+        account_affinity::AccountAffinity,
         committer::CommitTransactionDetails,
         decision_maker::{BufferedPacketsDecision, DecisionMaker},
         forwarder::Forwarder,
@@ -19,6 +20,7 @@ use {
             LeaderExecuteAndCommitTimings, RecordTransactionsTimings,
         },
         packet_deserializer::PacketDeserializer,
+        publisher_lane::PublisherLane,
         qos_service::QosService,
         tracer_packet_stats::TracerPacketStats,
         unprocessed_packet_batches::*,
@@ -69,6 +71,7 @@ use {
     },
 };
 
+pub mod account_affinity;
 pub mod committer;
 mod decision_maker;
 mod forwarder;
@@ -92,6 +95,8 @@ pub struct ProcessTransactionBatchOutput {
     cost_model_throttled_transactions_count: usize,
     // Amount of time spent running the cost model
     cost_model_us: u64,
+    // Writable accounts of the transactions that failed to lock an account
+    account_lock_conflicts: Vec<Pubkey>,
     execute_and_commit_transactions_output: ExecuteAndCommitTransactionsOutput,
 }
 
@@ -363,6 +368,7 @@ impl BankingStage {
         log_messages_bytes_limit: Option<usize>,
         connection_cache: Arc<ConnectionCache>,
         bank_forks: Arc<RwLock<BankForks>>,
+        publisher_lane: Arc<RwLock<Arc<PublisherLane>>>,
     ) -> Self {
         Self::new_num_threads(
             cluster_info,
@@ -376,6 +382,7 @@ impl BankingStage {
             log_messages_bytes_limit,
             connection_cache,
             bank_forks,
+            publisher_lane,
         )
     }
 
@@ -392,6 +399,7 @@ impl BankingStage {
         log_messages_bytes_limit: Option<usize>,
         connection_cache: Arc<ConnectionCache>,
         bank_forks: Arc<RwLock<BankForks>>,
+        publisher_lane: Arc<RwLock<Arc<PublisherLane>>>,
     ) -> Self {
         assert!(num_threads >= MIN_TOTAL_THREADS);
         // Single thread to generate entries from many banks.
@@ -402,6 +410,9 @@ impl BankingStage {
             TOTAL_BUFFERED_PACKETS / ((num_threads - NUM_VOTE_PROCESSING_THREADS) as usize);
         // Keeps track of extraneous vote transactions for the vote threads
         let latest_unprocessed_votes = Arc::new(LatestUnprocessedVotes::new());
+        // Lets the non-vote threads group transactions by contended writable account
+        let mut account_affinities =
+            AccountAffinity::new_for_threads(NUM_VOTE_PROCESSING_THREADS..num_threads);
         let should_split_voting_threads = bank_forks
             .read()
             .map(|bank_forks| {
@@ -461,6 +472,10 @@ impl BankingStage {
                 let data_budget = data_budget.clone();
                 let connection_cache = connection_cache.clone();
                 let bank_forks = bank_forks.clone();
+                let publisher_lane = publisher_lane.clone();
+                let account_affinity = account_affinities
+                    .remove(&i)
+                    .unwrap_or_else(|| AccountAffinity::new_unshared(i));
                 Builder::new()
                     .name(format!("solBanknStgTx{i:02}"))
                     .spawn(move || {
@@ -477,6 +492,8 @@ impl BankingStage {
                             connection_cache,
                             &bank_forks,
                             unprocessed_transaction_storage,
+                            publisher_lane,
+                            account_affinity,
                         );
                     })
                     .unwrap()
@@ -494,6 +511,7 @@ impl BankingStage {
         replay_vote_sender: &ReplayVoteSender,
         banking_stage_stats: &BankingStageStats,
         qos_service: &QosService,
+        account_affinity: &AccountAffinity,
         log_messages_bytes_limit: Option<usize>,
         consumed_buffered_packets_count: &mut usize,
         rebuffered_packet_count: &mut usize,
@@ -531,9 +549,13 @@ impl BankingStage {
         let ProcessTransactionsSummary {
             reached_max_poh_height,
             retryable_transaction_indexes,
+            account_lock_conflicts,
             ..
         } = process_transactions_summary;
 
+        // Have transactions writing the accounts this thread failed to lock sent here
+        account_affinity.claim(&account_lock_conflicts);
+
         if reached_max_poh_height || !bank_start.should_working_bank_still_be_processing_txs() {
             payload.reached_end_of_slot = true;
         }
@@ -574,6 +596,7 @@ impl BankingStage {
         qos_service: &QosService,
         slot_metrics_tracker: &mut LeaderSlotMetricsTracker,
         log_messages_bytes_limit: Option<usize>,
+        account_affinity: &AccountAffinity,
     ) {
         let mut rebuffered_packet_count = 0;
         let mut consumed_buffered_packets_count = 0;
@@ -584,6 +607,7 @@ impl BankingStage {
             bank_start.working_bank.clone(),
             banking_stage_stats,
             slot_metrics_tracker,
+            &qos_service.publisher_lane(),
             |packets_to_process, payload| {
                 Self::do_process_packets(
                     bank_start,
@@ -593,6 +617,7 @@ impl BankingStage {
                     replay_vote_sender,
                     banking_stage_stats,
                     qos_service,
+                    account_affinity,
                     log_messages_bytes_limit,
                     &mut consumed_buffered_packets_count,
                     &mut rebuffered_packet_count,
@@ -647,6 +672,7 @@ impl BankingStage {
         connection_cache: &ConnectionCache,
         tracer_packet_stats: &mut TracerPacketStats,
         bank_forks: &Arc<RwLock<BankForks>>,
+        account_affinity: &AccountAffinity,
     ) {
         if unprocessed_transaction_storage.should_not_process() {
             return;
@@ -677,7 +703,8 @@ impl BankingStage {
                         recorder,
                         qos_service,
                         slot_metrics_tracker,
-                        log_messages_bytes_limit
+                        log_messages_bytes_limit,
+                        account_affinity,
                     ),
                     "consume_buffered_packets",
                 );
@@ -745,12 +772,14 @@ impl BankingStage {
         connection_cache: Arc<ConnectionCache>,
         bank_forks: &Arc<RwLock<BankForks>>,
         mut unprocessed_transaction_storage: UnprocessedTransactionStorage,
+        publisher_lane: Arc<RwLock<Arc<PublisherLane>>>,
+        account_affinity: AccountAffinity,
     ) {
         let recorder = poh_recorder.read().unwrap().recorder();
         let socket = UdpSocket::bind("0.0.0.0:0").unwrap();
         let mut banking_stage_stats = BankingStageStats::new(id);
         let mut tracer_packet_stats = TracerPacketStats::new(id);
-        let qos_service = QosService::new(id);
+        let qos_service = QosService::new_with_publisher_lane(id, publisher_lane);
 
         let mut slot_metrics_tracker = LeaderSlotMetricsTracker::new(id);
         let mut last_metrics_update = Instant::now();
@@ -778,6 +807,7 @@ impl BankingStage {
                         &connection_cache,
                         &mut tracer_packet_stats,
                         bank_forks,
+                        &account_affinity,
                     ),
                     "process_buffered_packets",
                 );
@@ -810,6 +840,7 @@ impl BankingStage {
                     &mut banking_stage_stats,
                     &mut tracer_packet_stats,
                     &mut slot_metrics_tracker,
+                    &account_affinity,
                 ),
                 "receive_and_buffer_packets",
             );
@@ -1066,6 +1097,7 @@ impl BankingStage {
         let (batch, lock_time) = measure!(
             bank.prepare_sanitized_batch_with_results(txs, transactions_qos_results.iter())
         );
+        let account_lock_conflicts = Self::collect_account_lock_conflicts(&batch);
 
         // retryable_txs includes AccountInUse, WouldExceedMaxBlockCostLimit
         // WouldExceedMaxAccountCostLimit, WouldExceedMaxVoteCostLimit
@@ -1120,10 +1152,32 @@ impl BankingStage {
         ProcessTransactionBatchOutput {
             cost_model_throttled_transactions_count,
             cost_model_us: cost_model_time.as_us(),
+            account_lock_conflicts,
             execute_and_commit_transactions_output,
         }
     }
 
+    /// Writable accounts of the transactions in `batch` that failed to lock because an
+    /// account was already locked by another batch, once per transaction. Fee payers
+    /// are left out: a payer is written by every transaction it pays for, and is not
+    /// what the transactions contend for.
+    fn collect_account_lock_conflicts(batch: &TransactionBatch) -> Vec<Pubkey> {
+        batch
+            .sanitized_transactions()
+            .iter()
+            .zip(batch.lock_results())
+            .filter(|(_, lock_result)| matches!(lock_result, Err(TransactionError::AccountInUse)))
+            .flat_map(|(tx, _)| {
+                let fee_payer = tx.message().fee_payer();
+                tx.get_account_locks_unchecked()
+                    .writable
+                    .into_iter()
+                    .filter(move |account| *account != fee_payer)
+            })
+            .copied()
+            .collect()
+    }
+
     fn accumulate_execute_units_and_time(execute_timings: &ExecuteTimings) -> (u64, u64) {
         let (units, times): (Vec<_>, Vec<_>) = execute_timings
             .details
@@ -1169,6 +1223,7 @@ impl BankingStage {
         let mut total_cost_model_us: u64 = 0;
         let mut total_execute_and_commit_timings = LeaderExecuteAndCommitTimings::default();
         let mut total_error_counters = TransactionErrorMetrics::default();
+        let mut all_account_lock_conflicts = vec![];
         let mut reached_max_poh_height = false;
         while chunk_start != transactions.len() {
             let chunk_end = std::cmp::min(
@@ -1189,8 +1244,10 @@ impl BankingStage {
             let ProcessTransactionBatchOutput {
                 cost_model_throttled_transactions_count: new_cost_model_throttled_transactions_count,
                 cost_model_us: new_cost_model_us,
+                account_lock_conflicts: new_account_lock_conflicts,
                 execute_and_commit_transactions_output,
             } = process_transaction_batch_output;
+            all_account_lock_conflicts.extend(new_account_lock_conflicts);
             total_cost_model_throttled_transactions_count =
                 total_cost_model_throttled_transactions_count
                     .saturating_add(new_cost_model_throttled_transactions_count);
@@ -1272,6 +1329,7 @@ impl BankingStage {
             cost_model_us: total_cost_model_us,
             execute_and_commit_timings: total_execute_and_commit_timings,
             error_counters: total_error_counters,
+            account_lock_conflicts: all_account_lock_conflicts,
         }
     }
 
@@ -1502,6 +1560,7 @@ mod tests {
                 None,
                 Arc::new(ConnectionCache::default()),
                 bank_forks,
+                Arc::default(),
             );
             drop(non_vote_sender);
             drop(tpu_vote_sender);
@@ -1557,6 +1616,7 @@ mod tests {
                 None,
                 Arc::new(ConnectionCache::default()),
                 bank_forks,
+                Arc::default(),
             );
             trace!("sending bank");
             drop(non_vote_sender);
@@ -1637,6 +1697,7 @@ mod tests {
                 None,
                 Arc::new(ConnectionCache::default()),
                 bank_forks,
+                Arc::default(),
             );
 
             // fund another account so we can send 2 good transactions in a single batch.
@@ -1798,6 +1859,7 @@ mod tests {
                     None,
                     Arc::new(ConnectionCache::default()),
                     bank_forks,
+                    Arc::default(),
                 );
 
                 // wait for banking_stage to eat the packets
@@ -2406,6 +2468,10 @@ mod tests {
                 .store(true, Ordering::Relaxed);
             let _ = poh_simulator.join();
 
+            // the transactions only contend for their fee payer
+            assert!(process_transactions_batch_output
+                .account_lock_conflicts
+                .is_empty());
             let ExecuteAndCommitTransactionsOutput {
                 transactions_attempted_execution_count,
                 executed_transactions_count,
@@ -2422,6 +2488,28 @@ mod tests {
         Blockstore::destroy(ledger_path.path()).unwrap();
     }
 
+    #[test]
+    fn test_collect_account_lock_conflicts() {
+        let GenesisConfigInfo {
+            genesis_config,
+            mint_keypair,
+            ..
+        } = create_slow_genesis_config(10_000);
+        let bank = Bank::new_no_wallclock_throttle_for_tests(&genesis_config);
+        let other_payer = Keypair::new();
+        let contended = solana_sdk::pubkey::new_rand();
+
+        let transactions = sanitize_transactions(vec![
+            system_transaction::transfer(&mint_keypair, &contended, 1, genesis_config.hash()),
+            system_transaction::transfer(&other_payer, &contended, 1, genesis_config.hash()),
+        ]);
+        let batch = bank.prepare_sanitized_batch(&transactions);
+        assert_eq!(
+            BankingStage::collect_account_lock_conflicts(&batch),
+            vec![contended]
+        );
+    }
+
     #[test]
     fn test_process_transactions_returns_unprocessed_txs() {
         solana_logger::setup();
@@ -3069,6 +3157,7 @@ mod tests {
                 &QosService::new(1),
                 &mut LeaderSlotMetricsTracker::new(0),
                 None,
+                &AccountAffinity::new_unshared(1),
             );
             assert!(buffered_packet_batches.is_empty());
             poh_recorder
@@ -3127,6 +3216,7 @@ mod tests {
                 &QosService::new(1),
                 &mut LeaderSlotMetricsTracker::new(0),
                 None,
+                &AccountAffinity::new_unshared(1),
             );
             assert!(buffered_packet_batches.is_empty());
             poh_recorder
@@ -3189,6 +3279,7 @@ mod tests {
                         &QosService::new(1),
                         &mut LeaderSlotMetricsTracker::new(0),
                         None,
+                        &AccountAffinity::new_unshared(1),
                     );
 
                     // Check everything is correct. All valid packets should be processed.
@@ -3292,6 +3383,7 @@ mod tests {
                 None,
                 Arc::new(ConnectionCache::default()),
                 bank_forks,
+                Arc::default(),
             );
 
             let keypairs = (0..100).map(|_| Keypair::new()).collect_vec();
@@ -0,0 +1,353 @@
+//! Affinity of contended writable accounts to banking threads.
+//!
+//! Transactions that write the same account cannot execute concurrently. When
+//! they are spread over several banking threads, all but one of the threads
+//! fail to lock the account and rebuffer them, which is what happens to the
+//! updates of a busy price account. A thread that hits such a lock conflict
+//! claims the written accounts, and the other threads hand newly received
+//! transactions writing a claimed account over to it, so that they are batched
+//! and executed by a single thread. A claim lapses once its account has not
+//! been contended for a while.
+
+use {
+    crate::immutable_deserialized_packet::ImmutableDeserializedPacket,
+    crossbeam_channel::{unbounded, Receiver, SendError, Sender},
+    solana_sdk::pubkey::Pubkey,
+    std::{
+        collections::HashMap,
+        sync::{Arc, RwLock},
+        time::{Duration, Instant},
+    },
+};
+
+/// Maximum number of accounts claimed at once. When the limit is reached, the
+/// claim that was least recently refreshed makes room for the new one.
+pub const MAX_CLAIMED_ACCOUNTS: usize = 1024;
+
+/// How long a claim lasts after the last lock conflict on its account, so that
+/// accounts that are no longer contended do not pin their transactions to a
+/// thread.
+pub const CLAIM_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Copy, Debug)]
+struct Claim {
+    /// Id of the thread that claimed the account
+    owner: u32,
+    /// When the account last had a lock conflict
+    refreshed: Instant,
+}
+
+impl Claim {
+    fn is_expired(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.refreshed) >= CLAIM_TIMEOUT
+    }
+}
+
+struct SharedAccountAffinity {
+    /// Claimed accounts and their claims
+    owners: RwLock<HashMap<Pubkey, Claim>>,
+    /// Handoff channel of every thread, by thread id
+    senders: HashMap<u32, Sender<Vec<ImmutableDeserializedPacket>>>,
+}
+
+/// A banking thread's view of the account affinity shared with the other
+/// banking threads.
+pub struct AccountAffinity {
+    id: u32,
+    shared: Arc<SharedAccountAffinity>,
+    receiver: Receiver<Vec<ImmutableDeserializedPacket>>,
+}
+
+impl AccountAffinity {
+    /// Create connected affinities for the banking threads `ids`, by thread id.
+    pub fn new_for_threads(ids: impl IntoIterator<Item = u32>) -> HashMap<u32, Self> {
+        let (senders, receivers): (HashMap<_, _>, Vec<_>) = ids
+            .into_iter()
+            .map(|id| {
+                let (sender, receiver) = unbounded();
+                ((id, sender), (id, receiver))
+            })
+            .unzip();
+        let shared = Arc::new(SharedAccountAffinity {
+            owners: RwLock::default(),
+            senders,
+        });
+        receivers
+            .into_iter()
+            .map(|(id, receiver)| {
+                let affinity = Self {
+                    id,
+                    shared: shared.clone(),
+                    receiver,
+                };
+                (id, affinity)
+            })
+            .collect()
+    }
+
+    /// Affinity of a thread that shares its accounts with no other thread.
+    pub fn new_unshared(id: u32) -> Self {
+        Self::new_for_threads([id]).remove(&id).unwrap()
+    }
+
+    /// Claim `accounts` for this thread, unless another thread already has.
+    /// Either way the claim on each account is refreshed, since the account is
+    /// still contended.
+    pub fn claim<'a>(&self, accounts: impl IntoIterator<Item = &'a Pubkey>) {
+        self.claim_at(accounts, Instant::now())
+    }
+
+    fn claim_at<'a>(&self, accounts: impl IntoIterator<Item = &'a Pubkey>, now: Instant) {
+        let mut accounts = accounts.into_iter().peekable();
+        if self.shared.senders.len() < 2 || accounts.peek().is_none() {
+            return;
+        }
+        let mut owners = self.shared.owners.write().unwrap();
+        for account in accounts {
+            if let Some(claim) = owners.get_mut(account) {
+                if claim.is_expired(now) {
+                    claim.owner = self.id;
+                }
+                claim.refreshed = now;
+                continue;
+            }
+            if owners.len() >= MAX_CLAIMED_ACCOUNTS {
+                owners.retain(|_, claim| !claim.is_expired(now));
+            }
+            if owners.len() >= MAX_CLAIMED_ACCOUNTS {
+                let least_recent = owners
+                    .iter()
+                    .min_by_key(|(_, claim)| claim.refreshed)
+                    .map(|(account, _)| *account)
+                    .unwrap();
+                owners.remove(&least_recent);
+            }
+            owners.insert(
+                *account,
+                Claim {
+                    owner: self.id,
+                    refreshed: now,
+                },
+            );
+        }
+    }
+
+    /// Hand the packets writing an account claimed by another thread over to
+    /// that thread. Returns the packets this thread should buffer itself, and
+    /// the number of packets handed over.
+    pub fn route(
+        &self,
+        packets: Vec<ImmutableDeserializedPacket>,
+    ) -> (Vec<ImmutableDeserializedPacket>, usize) {
+        self.route_at(packets, Instant::now())
+    }
+
+    fn route_at(
+        &self,
+        packets: Vec<ImmutableDeserializedPacket>,
+        now: Instant,
+    ) -> (Vec<ImmutableDeserializedPacket>, usize) {
+        let owners = self.shared.owners.read().unwrap();
+        if owners.is_empty() {
+            return (packets, 0);
+        }
+
+        let mut kept_packets = Vec::with_capacity(packets.len());
+        let mut handed_off_packets: HashMap<u32, Vec<_>> = HashMap::new();
+        for packet in packets {
+            let message = &packet.transaction().get_message().message;
+            // the fee payer is written by every transaction it pays for, and is
+            // never claimed
+            let owner = message
+                .static_account_keys()
+                .iter()
+                .enumerate()
+                .skip(1)
+                .filter(|(index, _)| message.is_maybe_writable(*index))
+                .filter_map(|(_, account)| owners.get(account))
+                .find(|claim| !claim.is_expired(now))
+                .map(|claim| claim.owner)
+                .filter(|owner| *owner != self.id);
+            match owner {
+                Some(owner) => handed_off_packets.entry(owner).or_default().push(packet),
+                None => kept_packets.push(packet),
+            }
+        }
+        drop(owners);
+
+        let mut handed_off_count = 0;
+        for (owner, packets) in handed_off_packets {
+            let num_packets = packets.len();
+            // a thread that has exited can not take its packets
+            match self.shared.senders[&owner].send(packets) {
+                Ok(()) => handed_off_count += num_packets,
+                Err(SendError(packets)) => kept_packets.extend(packets),
+            }
+        }
+        (kept_packets, handed_off_count)
+    }
+
+    /// Packets other threads have handed over to this thread.
+    pub fn receive_handed_off(&self) -> Vec<ImmutableDeserializedPacket> {
+        self.receiver.try_iter().flatten().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_perf::packet::Packet,
+        solana_sdk::{
+            hash::Hash,
+            signature::{Keypair, Signer},
+            system_transaction,
+        },
+    };
+
+    fn transfer_packet(from: &Keypair, to: &Pubkey) -> ImmutableDeserializedPacket {
+        let tx = system_transaction::transfer(from, to, 1, Hash::default());
+        ImmutableDeserializedPacket::new(Packet::from_data(None, tx).unwrap(), None).unwrap()
+    }
+
+    #[test]
+    fn test_account_affinity_route() {
+        let mut affinities = AccountAffinity::new_for_threads([2, 3]);
+        let first = affinities.remove(&2).unwrap();
+        let second = affinities.remove(&3).unwrap();
+
+        let payer = Keypair::new();
+        let contended = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+
+        // nothing is claimed yet
+        let (kept, handed_off) = second.route(vec![transfer_packet(&payer, &contended)]);
+        assert_eq!((kept.len(), handed_off), (1, 0));
+
+        first.claim([&contended]);
+        // a later claim does not steal the account
+        second.claim([&contended]);
+
+        let (kept, handed_off) = second.route(vec![
+            transfer_packet(&payer, &contended),
+            transfer_packet(&payer, &other),
+        ]);
+        assert_eq!((kept.len(), handed_off), (1, 1));
+        assert_eq!(
+            kept[0]
+                .transaction()
+                .get_message()
+                .message
+                .static_account_keys()[1],
+            other
+        );
+        assert!(second.receive_handed_off().is_empty());
+        let received = first.receive_handed_off();
+        assert_eq!(received.len(), 1);
+        assert_eq!(
+            received[0]
+                .transaction()
+                .get_message()
+                .message
+                .static_account_keys()[1],
+            contended
+        );
+
+        // the owner keeps its own packets
+        let (kept, handed_off) = first.route(vec![transfer_packet(&payer, &contended)]);
+        assert_eq!((kept.len(), handed_off), (1, 0));
+
+        // packets for an exited thread stay where they are
+        drop(first);
+        let (kept, handed_off) = second.route(vec![transfer_packet(&payer, &contended)]);
+        assert_eq!((kept.len(), handed_off), (1, 0));
+    }
+
+    #[test]
+    fn test_account_affinity_unshared() {
+        let affinity = AccountAffinity::new_unshared(2);
+        let contended = Pubkey::new_unique();
+        affinity.claim([&contended]);
+        let (kept, handed_off) = affinity.route(vec![transfer_packet(&Keypair::new(), &contended)]);
+        assert_eq!((kept.len(), handed_off), (1, 0));
+    }
+
+    #[test]
+    fn test_account_affinity_claim_expires() {
+        let mut affinities = AccountAffinity::new_for_threads([2, 3]);
+        let first = affinities.remove(&2).unwrap();
+        let second = affinities.remove(&3).unwrap();
+        let payer = Keypair::new();
+        let contended = Pubkey::new_unique();
+        let start = Instant::now();
+
+        first.claim_at([&contended], start);
+        // a conflict on another thread keeps the claim alive without moving it
+        second.claim_at([&contended], start + CLAIM_TIMEOUT / 2);
+        let (kept, handed_off) = second.route_at(
+            vec![transfer_packet(&payer, &contended)],
+            start + CLAIM_TIMEOUT,
+        );
+        assert_eq!((kept.len(), handed_off), (0, 1));
+        assert_eq!(first.receive_handed_off().len(), 1);
+
+        // an idle claim lapses, and the next conflict claims the account anew
+        let expired = start + CLAIM_TIMEOUT / 2 + CLAIM_TIMEOUT;
+        let (kept, handed_off) =
+            second.route_at(vec![transfer_packet(&payer, &contended)], expired);
+        assert_eq!((kept.len(), handed_off), (1, 0));
+        second.claim_at([&contended], expired);
+        let (kept, handed_off) = first.route_at(vec![transfer_packet(&payer, &contended)], expired);
+        assert_eq!((kept.len(), handed_off), (0, 1));
+        assert_eq!(second.receive_handed_off().len(), 1);
+    }
+
+    #[test]
+    fn test_account_affinity_evicts_least_recent_claim() {
+        let mut affinities = AccountAffinity::new_for_threads([2, 3]);
+        let first = affinities.remove(&2).unwrap();
+        let second = affinities.remove(&3).unwrap();
+        let payer = Keypair::new();
+        let start = Instant::now();
+
+        let accounts: Vec<_> = (0..MAX_CLAIMED_ACCOUNTS)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        first.claim_at(&accounts[..1], start);
+        first.claim_at(&accounts[1..], start + Duration::from_millis(1));
+        first.claim_at([&Pubkey::new_unique()], start + Duration::from_millis(2));
+
+        // only the least recently refreshed claim made room
+        let now = start + Duration::from_millis(3);
+        let (kept, handed_off) = second.route_at(
+            vec![
+                transfer_packet(&payer, &accounts[0]),
+                transfer_packet(&payer, &accounts[1]),
+            ],
+            now,
+        );
+        assert_eq!((kept.len(), handed_off), (1, 1));
+        assert_eq!(
+            kept[0]
+                .transaction()
+                .get_message()
+                .message
+                .static_account_keys()[1],
+            accounts[0]
+        );
+    }
+
+    #[test]
+    fn test_account_affinity_ignores_fee_payer() {
+        let mut affinities = AccountAffinity::new_for_threads([2, 3]);
+        let first = affinities.remove(&2).unwrap();
+        let second = affinities.remove(&3).unwrap();
+        let payer = Keypair::new();
+
+        // an account claimed as a written account does not pull in the
+        // transactions it only pays for
+        first.claim([&payer.pubkey()]);
+        let (kept, handed_off) = second.route(vec![transfer_packet(&payer, &Pubkey::new_unique())]);
+        assert_eq!((kept.len(), handed_off), (1, 0));
+    }
+}
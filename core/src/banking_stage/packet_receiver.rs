@@ -1,5 +1,5 @@
 use {
-    super::BankingStageStats,
+    super::{account_affinity::AccountAffinity, BankingStageStats},
     crate::{
         immutable_deserialized_packet::ImmutableDeserializedPacket,
         leader_slot_banking_stage_metrics::LeaderSlotMetricsTracker,
@@ -33,7 +33,17 @@ impl PacketReceiver {
         banking_stage_stats: &mut BankingStageStats,
         tracer_packet_stats: &mut TracerPacketStats,
         slot_metrics_tracker: &mut LeaderSlotMetricsTracker,
+        account_affinity: &AccountAffinity,
     ) -> Result<(), RecvTimeoutError> {
+        // Buffer the packets other threads handed over, even if no new packets arrive
+        Self::buffer_handed_off_packets(
+            account_affinity,
+            unprocessed_transaction_storage,
+            banking_stage_stats,
+            slot_metrics_tracker,
+            tracer_packet_stats,
+        );
+
         let mut recv_time = Measure::start("receive_and_buffer_packets_recv");
         let ReceivePacketResults {
             deserialized_packets,
@@ -61,6 +71,9 @@ impl PacketReceiver {
         slot_metrics_tracker.increment_total_new_valid_packets(passed_sigverify_count);
         slot_metrics_tracker.increment_newly_failed_sigverify_count(failed_sigverify_count);
 
+        let (deserialized_packets, handed_off_count) = account_affinity.route(deserialized_packets);
+        slot_metrics_tracker.increment_handed_off_packets_count(handed_off_count as u64);
+
         let mut dropped_packets_count = 0;
         let mut newly_buffered_packets_count = 0;
         Self::push_unprocessed(
@@ -93,6 +106,37 @@ impl PacketReceiver {
         Ok(())
     }
 
+    fn buffer_handed_off_packets(
+        account_affinity: &AccountAffinity,
+        unprocessed_transaction_storage: &mut UnprocessedTransactionStorage,
+        banking_stage_stats: &mut BankingStageStats,
+        slot_metrics_tracker: &mut LeaderSlotMetricsTracker,
+        tracer_packet_stats: &mut TracerPacketStats,
+    ) {
+        let handed_off_packets = account_affinity.receive_handed_off();
+        if handed_off_packets.is_empty() {
+            return;
+        }
+
+        let mut dropped_packets_count = 0;
+        let mut newly_buffered_packets_count = 0;
+        Self::push_unprocessed(
+            unprocessed_transaction_storage,
+            handed_off_packets,
+            &mut dropped_packets_count,
+            &mut newly_buffered_packets_count,
+            banking_stage_stats,
+            slot_metrics_tracker,
+            tracer_packet_stats,
+        );
+        banking_stage_stats
+            .dropped_packets_count
+            .fetch_add(dropped_packets_count, Ordering::Relaxed);
+        banking_stage_stats
+            .newly_buffered_packets_count
+            .fetch_add(newly_buffered_packets_count, Ordering::Relaxed);
+    }
+
     fn push_unprocessed(
         unprocessed_transaction_storage: &mut UnprocessedTransactionStorage,
         deserialized_packets: Vec<ImmutableDeserializedPacket>,
@@ -4,7 +4,12 @@ use {
     solana_ledger::blockstore::Blockstore,
     solana_measure::measure::Measure,
     solana_runtime::bank::Bank,
+    solana_sdk::{
+        account::{Account, ReadableAccount},
+        pubkey::Pubkey,
+    },
     std::{
+        collections::HashSet,
         sync::{
             atomic::{AtomicBool, Ordering},
             Arc,
@@ -27,6 +32,7 @@ impl CacheBlockMetaService {
     pub fn new(
         cache_block_meta_receiver: CacheBlockMetaReceiver,
         blockstore: Arc<Blockstore>,
+        account_history_owners: HashSet<Pubkey>,
         exit: &Arc<AtomicBool>,
     ) -> Self {
         let exit = exit.clone();
@@ -43,7 +49,7 @@ impl CacheBlockMetaService {
                     }
                     Ok(bank) => {
                         let mut cache_block_meta_timer = Measure::start("cache_block_meta_timer");
-                        Self::cache_block_meta(bank, &blockstore);
+                        Self::cache_block_meta(bank, &blockstore, &account_history_owners);
                         cache_block_meta_timer.stop();
                         if cache_block_meta_timer.as_ms() > CACHE_BLOCK_TIME_WARNING_MS {
                             warn!(
@@ -59,13 +65,76 @@ impl CacheBlockMetaService {
         Self { thread_hdl }
     }
 
-    fn cache_block_meta(bank: Arc<Bank>, blockstore: &Arc<Blockstore>) {
+    fn cache_block_meta(
+        bank: Arc<Bank>,
+        blockstore: &Arc<Blockstore>,
+        account_history_owners: &HashSet<Pubkey>,
+    ) {
         if let Err(e) = blockstore.cache_block_time(bank.slot(), bank.clock().unix_timestamp) {
             error!("cache_block_time failed: slot {:?} {:?}", bank.slot(), e);
         }
         if let Err(e) = blockstore.cache_block_height(bank.slot(), bank.block_height()) {
             error!("cache_block_height failed: slot {:?} {:?}", bank.slot(), e);
         }
+        // The sysvar keeps the last accumulator until a bank commits a new one, so only store it
+        // for the slot that committed it.
+        if let Some(accumulator) = bank
+            .get_accumulator()
+            .filter(|accumulator| accumulator.slot == bank.slot())
+        {
+            if let Err(e) = blockstore.cache_accumulator(bank.slot(), &accumulator) {
+                error!("cache_accumulator failed: slot {:?} {:?}", bank.slot(), e);
+            }
+        }
+        if !account_history_owners.is_empty() {
+            Self::cache_account_history(&bank, blockstore, account_history_owners);
+        }
+    }
+
+    /// Records the accounts owned by `account_history_owners` that the bank wrote. Closed
+    /// accounts lose their owner, so they are recorded if their earlier states were.
+    ///
+    /// Accounts missing from the parent bank are recorded as created, which tells lookups
+    /// before their first state apart from lookups of accounts that were not written since
+    /// the history started.
+    fn cache_account_history(
+        bank: &Bank,
+        blockstore: &Blockstore,
+        account_history_owners: &HashSet<Pubkey>,
+    ) {
+        let accounts: Vec<_> = bank
+            .get_all_accounts_modified_since_parent()
+            .into_iter()
+            .filter(|(pubkey, account)| {
+                account_history_owners.contains(account.owner())
+                    || (account.lamports() == 0
+                        && blockstore.has_account_history(pubkey).unwrap_or(false))
+            })
+            .map(|(pubkey, account)| (pubkey, Account::from(account)))
+            .collect();
+        if accounts.is_empty() {
+            return;
+        }
+        // The parent is dropped once the bank is rooted, after which creations can't be told
+        let created: Vec<_> = bank
+            .parent()
+            .map(|parent| {
+                accounts
+                    .iter()
+                    .filter(|(pubkey, account)| {
+                        account.lamports > 0 && parent.get_account(pubkey).is_none()
+                    })
+                    .map(|(pubkey, _)| *pubkey)
+                    .collect()
+            })
+            .unwrap_or_default();
+        if let Err(e) = blockstore.write_account_history(bank.slot(), &accounts, &created) {
+            error!(
+                "write_account_history failed: slot {:?} {:?}",
+                bank.slot(),
+                e
+            );
+        }
     }
 
     pub fn join(self) -> thread::Result<()> {
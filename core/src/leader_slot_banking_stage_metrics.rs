@@ -5,10 +5,13 @@ use {
     },
     solana_poh::poh_recorder::BankStart,
     solana_runtime::transaction_error_metrics::*,
-    solana_sdk::{clock::Slot, saturating_add_assign},
-    std::time::Instant,
+    solana_sdk::{clock::Slot, pubkey::Pubkey, saturating_add_assign},
+    std::{collections::HashMap, time::Instant},
 };
 
+/// Number of most conflicted accounts reported per leader slot and thread
+const MAX_REPORTED_CONFLICTED_ACCOUNTS: usize = 10;
+
 /// A summary of what happened to transactions passed to the execution pipeline.
 /// Transactions can
 /// 1) Did not even make it to execution due to being filtered out by things like AccountInUse
@@ -52,6 +55,10 @@ pub(crate) struct ProcessTransactionsSummary {
 
     // Breakdown of all the transaction errors from transactions passed for execution
     pub error_counters: TransactionErrorMetrics,
+
+    // Writable accounts of the transactions that were not executed because an account
+    // was locked by another batch, once per transaction
+    pub account_lock_conflicts: Vec<Pubkey>,
 }
 
 // Metrics describing packets ingested/processed in various parts of BankingStage during this
@@ -138,6 +145,10 @@ struct LeaderSlotPacketCountMetrics {
     // total number of forwardable batches that were attempted for forwarding. A forwardable batch
     // is defined in `ForwardPacketBatchesByAccounts` in `forward_packet_batches_by_accounts.rs`
     forwardable_batches_count: u64,
+
+    // total number of received packets that were handed over to the thread that claimed one of
+    // their writable accounts, see `AccountAffinity` in `banking_stage/account_affinity.rs`
+    handed_off_packets_count: u64,
 }
 
 impl LeaderSlotPacketCountMetrics {
@@ -255,10 +266,61 @@ impl LeaderSlotPacketCountMetrics {
                 self.end_of_slot_unprocessed_buffer_len as i64,
                 i64
             ),
+            (
+                "handed_off_packets_count",
+                self.handed_off_packets_count as i64,
+                i64
+            ),
         );
     }
 }
 
+// Metrics describing which writable accounts transactions failed to lock during this
+// validator's leader slot
+#[derive(Debug, Default)]
+struct AccountLockConflictMetrics {
+    // number of transactions that failed to lock their accounts, by writable account
+    conflicts_by_account: HashMap<Pubkey, u64>,
+}
+
+impl AccountLockConflictMetrics {
+    fn new() -> Self {
+        Self { ..Self::default() }
+    }
+
+    fn accumulate(&mut self, accounts: &[Pubkey]) {
+        for account in accounts {
+            saturating_add_assign!(*self.conflicts_by_account.entry(*account).or_default(), 1);
+        }
+    }
+
+    /// The most conflicted accounts, most conflicted first
+    fn most_conflicted_accounts(&self) -> Vec<(Pubkey, u64)> {
+        let mut accounts = self
+            .conflicts_by_account
+            .iter()
+            .map(|(account, count)| (*account, *count))
+            .collect::<Vec<_>>();
+        accounts.sort_unstable_by(|(a_account, a_count), (b_account, b_count)| {
+            b_count.cmp(a_count).then(a_account.cmp(b_account))
+        });
+        accounts.truncate(MAX_REPORTED_CONFLICTED_ACCOUNTS);
+        accounts
+    }
+
+    fn report(&self, id: u32, slot: Slot) {
+        for (account, count) in self.most_conflicted_accounts() {
+            datapoint_info!(
+                "banking_stage-leader_slot_account_lock_conflicts",
+                ("id", id as i64, i64),
+                ("slot", slot as i64, i64),
+                ("account", account.to_string(), String),
+                ("conflict_count", count as i64, i64),
+            );
+        }
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct LeaderSlotMetrics {
     // banking_stage creates one QosService instance per working threads, that is uniquely
@@ -275,6 +337,8 @@ pub(crate) struct LeaderSlotMetrics {
 
     vote_packet_count_metrics: VotePacketCountMetrics,
 
+    account_lock_conflict_metrics: AccountLockConflictMetrics,
+
     timing_metrics: LeaderSlotTimingMetrics,
 
     // Used by tests to check if the `self.report()` method was called
@@ -289,6 +353,7 @@ impl LeaderSlotMetrics {
             packet_count_metrics: LeaderSlotPacketCountMetrics::new(),
             transaction_error_metrics: TransactionErrorMetrics::new(),
             vote_packet_count_metrics: VotePacketCountMetrics::new(),
+            account_lock_conflict_metrics: AccountLockConflictMetrics::new(),
             timing_metrics: LeaderSlotTimingMetrics::new(bank_creation_time),
             is_reported: false,
         }
@@ -301,6 +366,8 @@ impl LeaderSlotMetrics {
         self.transaction_error_metrics.report(self.id, self.slot);
         self.packet_count_metrics.report(self.id, self.slot);
         self.vote_packet_count_metrics.report(self.id, self.slot);
+        self.account_lock_conflict_metrics
+            .report(self.id, self.slot);
     }
 
     /// Returns `Some(self.slot)` if the metrics have been reported, otherwise returns None
@@ -449,6 +516,7 @@ impl LeaderSlotMetricsTracker {
                 cost_model_us,
                 ref execute_and_commit_timings,
                 error_counters,
+                ref account_lock_conflicts,
                 ..
             } = process_transactions_summary;
 
@@ -529,6 +597,10 @@ impl LeaderSlotMetricsTracker {
                 .timing_metrics
                 .execute_and_commit_timings
                 .accumulate(execute_and_commit_timings);
+
+            leader_slot_metrics
+                .account_lock_conflict_metrics
+                .accumulate(account_lock_conflicts);
         }
     }
 
@@ -669,6 +741,17 @@ impl LeaderSlotMetricsTracker {
         }
     }
 
+    pub(crate) fn increment_handed_off_packets_count(&mut self, count: u64) {
+        if let Some(leader_slot_metrics) = &mut self.leader_slot_metrics {
+            saturating_add_assign!(
+                leader_slot_metrics
+                    .packet_count_metrics
+                    .handed_off_packets_count,
+                count
+            );
+        }
+    }
+
     pub(crate) fn increment_retryable_packets_count(&mut self, count: u64) {
         if let Some(leader_slot_metrics) = &mut self.leader_slot_metrics {
             saturating_add_assign!(
@@ -1103,4 +1186,25 @@ mod tests {
             assert!(leader_slot_metrics_tracker.leader_slot_metrics.is_none());
         }
     }
+
+    #[test]
+    fn test_most_conflicted_accounts() {
+        let hot = Pubkey::new_unique();
+        let warm = Pubkey::new_unique();
+        let mut metrics = AccountLockConflictMetrics::new();
+        metrics.accumulate(&[hot, warm, hot]);
+        metrics.accumulate(&[hot]);
+        assert_eq!(
+            metrics.most_conflicted_accounts(),
+            vec![(hot, 3), (warm, 1)]
+        );
+
+        let many = (0..MAX_REPORTED_CONFLICTED_ACCOUNTS)
+            .map(|_| Pubkey::new_unique())
+            .collect::<Vec<_>>();
+        metrics.accumulate(&many);
+        let most_conflicted = metrics.most_conflicted_accounts();
+        assert_eq!(most_conflicted.len(), MAX_REPORTED_CONFLICTED_ACCOUNTS);
+        assert_eq!(most_conflicted[0], (hot, 3));
+    }
 }
@@ -48,6 +48,7 @@ pub mod packet_threshold;
 pub mod poh_timing_report_service;
 pub mod poh_timing_reporter;
 pub mod progress_map;
+pub mod publisher_lane;
 pub mod qos_service;
 pub mod read_write_account_set;
 pub mod repair_generic_traversal;
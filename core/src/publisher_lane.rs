@@ -0,0 +1,95 @@
+//! Block capacity reserved for Pyth price publishers.
+//!
+//! A leader can reserve part of every block for transactions signed by an
+//! allow-listed set of publishers, and schedule those transactions ahead of
+//! the rest of its buffer, so that other traffic cannot crowd out price
+//! updates. The lane is shared by all banking threads and can be replaced at
+//! runtime through the admin RPC.
+
+use {
+    crate::immutable_deserialized_packet::ImmutableDeserializedPacket,
+    solana_sdk::{pubkey::Pubkey, transaction::SanitizedTransaction},
+    std::collections::HashSet,
+};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PublisherLane {
+    /// Publishers whose transactions are scheduled first and may use the
+    /// reserved block capacity.
+    pub publishers: HashSet<Pubkey>,
+    /// Compute units of every block that only publisher transactions may use.
+    pub reserved_block_units: u64,
+}
+
+impl PublisherLane {
+    pub fn new(publishers: HashSet<Pubkey>, reserved_block_units: u64) -> Self {
+        Self {
+            publishers,
+            reserved_block_units,
+        }
+    }
+
+    /// Whether the transaction is signed by an allow-listed publisher.
+    pub fn is_publisher_transaction(&self, transaction: &SanitizedTransaction) -> bool {
+        let message = transaction.message();
+        let num_signers = usize::from(message.header().num_required_signatures);
+        self.has_publisher(message.account_keys().iter().take(num_signers))
+    }
+
+    /// Whether the packet's transaction is signed by an allow-listed publisher.
+    pub fn is_publisher_packet(&self, packet: &ImmutableDeserializedPacket) -> bool {
+        let message = &packet.transaction().get_message().message;
+        let num_signers = usize::from(message.header().num_required_signatures);
+        self.has_publisher(message.static_account_keys().iter().take(num_signers))
+    }
+
+    fn has_publisher<'a>(&self, mut signers: impl Iterator<Item = &'a Pubkey>) -> bool {
+        !self.publishers.is_empty() && signers.any(|signer| self.publishers.contains(signer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_perf::packet::Packet,
+        solana_sdk::{
+            hash::Hash,
+            signature::{Keypair, Signer},
+            system_transaction,
+        },
+    };
+
+    #[test]
+    fn test_is_publisher_transaction() {
+        let publisher = Keypair::new();
+        let other = Keypair::new();
+        let lane = PublisherLane::new(HashSet::from([publisher.pubkey()]), 1_000);
+
+        let publisher_tx =
+            system_transaction::transfer(&publisher, &other.pubkey(), 1, Hash::default());
+        let other_tx =
+            system_transaction::transfer(&other, &publisher.pubkey(), 1, Hash::default());
+
+        assert!(
+            lane.is_publisher_transaction(&SanitizedTransaction::from_transaction_for_tests(
+                publisher_tx.clone()
+            ))
+        );
+        // a publisher that is only a non-signing account does not qualify
+        assert!(
+            !lane.is_publisher_transaction(&SanitizedTransaction::from_transaction_for_tests(
+                other_tx.clone()
+            ))
+        );
+
+        let packet = |tx| {
+            ImmutableDeserializedPacket::new(Packet::from_data(None, tx).unwrap(), None).unwrap()
+        };
+        assert!(lane.is_publisher_packet(&packet(publisher_tx)));
+        assert!(!lane.is_publisher_packet(&packet(other_tx)));
+        assert!(!PublisherLane::default().is_publisher_packet(&packet(
+            system_transaction::transfer(&publisher, &other.pubkey(), 1, Hash::default())
+        )));
+    }
+}
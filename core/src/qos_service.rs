@@ -4,7 +4,10 @@
 //!
 
 use {
-    crate::banking_stage::{committer::CommitTransactionDetails, BatchedTransactionDetails},
+    crate::{
+        banking_stage::{committer::CommitTransactionDetails, BatchedTransactionDetails},
+        publisher_lane::PublisherLane,
+    },
     crossbeam_channel::{unbounded, Receiver, Sender},
     solana_measure::measure::Measure,
     solana_runtime::{
@@ -21,7 +24,7 @@ use {
     std::{
         sync::{
             atomic::{AtomicBool, AtomicU64, Ordering},
-            Arc,
+            Arc, RwLock,
         },
         thread::{self, Builder, JoinHandle},
         time::Duration,
@@ -47,6 +50,8 @@ pub struct QosService {
     // metrics reporting runs on a private thread
     reporting_thread: Option<JoinHandle<()>>,
     running_flag: Arc<AtomicBool>,
+    // publishers whose transactions may use the block units reserved for them
+    publisher_lane: Arc<RwLock<Arc<PublisherLane>>>,
 }
 
 impl Drop for QosService {
@@ -62,6 +67,13 @@ impl Drop for QosService {
 
 impl QosService {
     pub fn new(id: u32) -> Self {
+        Self::new_with_publisher_lane(id, Arc::default())
+    }
+
+    pub fn new_with_publisher_lane(
+        id: u32,
+        publisher_lane: Arc<RwLock<Arc<PublisherLane>>>,
+    ) -> Self {
         let (report_sender, report_receiver) = unbounded();
         let running_flag = Arc::new(AtomicBool::new(true));
         let metrics = Arc::new(QosServiceMetrics::new(id));
@@ -82,9 +94,16 @@ impl QosService {
             reporting_thread,
             running_flag,
             report_sender,
+            publisher_lane,
         }
     }
 
+    /// The publisher lane currently in effect. Replacing the lane swaps the
+    /// `Arc`, so a caller keeps a consistent view without copying it.
+    pub fn publisher_lane(&self) -> Arc<PublisherLane> {
+        self.publisher_lane.read().unwrap().clone()
+    }
+
     /// Calculate cost of transactions, determine which ones to include in the slot, and
     /// accumulate costs in the cost tracker.
     /// Returns a vector of transaction costs, a vector of results indicating which transactions
@@ -119,9 +138,11 @@ impl QosService {
         transactions: impl Iterator<Item = &'a SanitizedTransaction>,
     ) -> Vec<TransactionCost> {
         let mut compute_cost_time = Measure::start("compute_cost_time");
+        let publisher_lane = self.publisher_lane();
         let txs_costs: Vec<_> = transactions
             .map(|tx| {
-                let cost = CostModel::calculate_cost(tx, feature_set);
+                let mut cost = CostModel::calculate_cost(tx, feature_set);
+                cost.is_publisher = publisher_lane.is_publisher_transaction(tx);
                 debug!(
                     "transaction {:?}, cost {:?}, cost sum {}",
                     tx,
@@ -154,6 +175,7 @@ impl QosService {
     ) -> (Vec<transaction::Result<()>>, usize) {
         let mut cost_tracking_time = Measure::start("cost_tracking_time");
         let mut cost_tracker = bank.write_cost_tracker().unwrap();
+        cost_tracker.set_publisher_reserved_cost(self.publisher_lane().reserved_block_units);
         let mut num_included = 0;
         let select_results = transactions
             .zip(transactions_costs)
@@ -663,6 +685,7 @@ mod tests {
             system_transaction,
         },
         solana_vote_program::vote_transaction,
+        std::collections::HashSet,
     };
 
     #[test]
@@ -754,6 +777,49 @@ mod tests {
         assert!(results[3].is_err());
     }
 
+    #[test]
+    fn test_select_transactions_per_cost_publisher_lane() {
+        solana_logger::setup();
+        let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(10);
+        let bank = Arc::new(Bank::new_for_tests(&genesis_config));
+
+        let keypair = Keypair::new();
+        let publisher = Keypair::new();
+        let transfer_tx = SanitizedTransaction::from_transaction_for_tests(
+            system_transaction::transfer(&keypair, &keypair.pubkey(), 1, Hash::default()),
+        );
+        let publisher_tx = SanitizedTransaction::from_transaction_for_tests(
+            system_transaction::transfer(&publisher, &publisher.pubkey(), 1, Hash::default()),
+        );
+        let transfer_tx_cost =
+            CostModel::calculate_cost(&transfer_tx, &FeatureSet::all_enabled()).sum();
+
+        // reserve the whole block for the publisher
+        let publisher_lane =
+            PublisherLane::new(HashSet::from([publisher.pubkey()]), transfer_tx_cost);
+        let qos_service =
+            QosService::new_with_publisher_lane(1, Arc::new(RwLock::new(Arc::new(publisher_lane))));
+        let txs = vec![transfer_tx, publisher_tx];
+        let txs_costs =
+            qos_service.compute_transaction_costs(&FeatureSet::all_enabled(), txs.iter());
+        assert!(!txs_costs[0].is_publisher);
+        assert!(txs_costs[1].is_publisher);
+
+        bank.write_cost_tracker().unwrap().set_limits(
+            transfer_tx_cost,
+            transfer_tx_cost,
+            transfer_tx_cost,
+        );
+        let (results, num_selected) =
+            qos_service.select_transactions_per_cost(txs.iter(), txs_costs.iter(), &bank);
+        assert_eq!(num_selected, 1);
+        assert_eq!(
+            results[0],
+            Err(TransactionError::WouldExceedMaxBlockCostLimit)
+        );
+        assert!(results[1].is_ok());
+    }
+
     #[test]
     fn test_update_or_remove_transaction_costs_commited() {
         solana_logger::setup();
@@ -149,6 +149,11 @@ struct SkippedSlotsInfo {
     last_skipped_slot: u64,
 }
 
+/// Called with every bank this validator creates for one of its own leader
+/// slots, before the bank is inserted into bank forks and handed to banking
+/// stage, so that nothing else is writing to the bank yet.
+pub type LeaderBankCallback = Arc<dyn Fn(&Bank) + Send + Sync>;
+
 pub struct ReplayStageConfig {
     pub vote_account: Pubkey,
     pub authorized_voter_keypairs: Arc<RwLock<Vec<Arc<Keypair>>>>,
@@ -169,6 +174,7 @@ pub struct ReplayStageConfig {
     // duplicate voting which can lead to slashing.
     pub wait_to_vote_slot: Option<Slot>,
     pub replay_slots_concurrently: bool,
+    pub leader_bank_callback: Option<LeaderBankCallback>,
 }
 
 #[derive(Default)]
@@ -432,6 +438,7 @@ impl ReplayStage {
             tower_storage,
             wait_to_vote_slot,
             replay_slots_concurrently,
+            leader_bank_callback,
         } = config;
 
         trace!("replay stage");
@@ -948,6 +955,7 @@ impl ReplayStage {
                         &banking_tracer,
                         has_new_vote_been_rooted,
                         transaction_status_sender.is_some(),
+                        leader_bank_callback.as_ref(),
                     );
 
                     let poh_bank = poh_recorder.read().unwrap().bank();
@@ -1660,6 +1668,7 @@ impl ReplayStage {
         banking_tracer: &Arc<BankingTracer>,
         has_new_vote_been_rooted: bool,
         track_transaction_indexes: bool,
+        leader_bank_callback: Option<&LeaderBankCallback>,
     ) {
         // all the individual calls to poh_recorder.read() are designed to
         // increase granularity, decrease contention
@@ -1782,6 +1791,9 @@ impl ReplayStage {
             // new()-ing of its child bank
             banking_tracer.hash_event(parent.slot(), &parent.last_blockhash(), &parent.hash());
 
+            if let Some(leader_bank_callback) = leader_bank_callback {
+                leader_bank_callback(&tpu_bank);
+            }
             let tpu_bank = bank_forks.write().unwrap().insert(tpu_bank);
             poh_recorder
                 .write()
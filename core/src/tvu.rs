@@ -17,7 +17,7 @@ use {
         drop_bank_service::DropBankService,
         ledger_cleanup_service::LedgerCleanupService,
         repair_service::RepairInfo,
-        replay_stage::{ReplayStage, ReplayStageConfig},
+        replay_stage::{LeaderBankCallback, ReplayStage, ReplayStageConfig},
         retransmit_stage::RetransmitStage,
         rewards_recorder_service::RewardsRecorderSender,
         shred_fetch_stage::ShredFetchStage,
@@ -91,6 +91,7 @@ pub struct TvuConfig {
     pub repair_whitelist: Arc<RwLock<HashSet<Pubkey>>>,
     pub wait_for_vote_to_start_leader: bool,
     pub replay_slots_concurrently: bool,
+    pub leader_bank_callback: Option<LeaderBankCallback>,
 }
 
 impl Tvu {
@@ -249,6 +250,7 @@ impl Tvu {
             tower_storage: tower_storage.clone(),
             wait_to_vote_slot,
             replay_slots_concurrently: tvu_config.replay_slots_concurrently,
+            leader_bank_callback: tvu_config.leader_bank_callback,
         };
 
         let (voting_sender, voting_receiver) = unbounded();
@@ -9,6 +9,7 @@ use {
         },
         leader_slot_banking_stage_metrics::LeaderSlotMetricsTracker,
         multi_iterator_scanner::{MultiIteratorScanner, ProcessingDecision},
+        publisher_lane::PublisherLane,
         read_write_account_set::ReadWriteAccountSet,
         unprocessed_packet_batches::{
             DeserializedPacket, PacketBatchInsertionMetrics, UnprocessedPacketBatches,
@@ -344,6 +345,7 @@ impl UnprocessedTransactionStorage {
         bank: Arc<Bank>,
         banking_stage_stats: &BankingStageStats,
         slot_metrics_tracker: &mut LeaderSlotMetricsTracker,
+        publisher_lane: &PublisherLane,
         processing_function: F,
     ) -> bool
     where
@@ -358,6 +360,7 @@ impl UnprocessedTransactionStorage {
                     &bank,
                     banking_stage_stats,
                     slot_metrics_tracker,
+                    publisher_lane,
                     processing_function,
                 ),
             Self::VoteStorage(vote_storage) => vote_storage.process_packets(
@@ -857,6 +860,7 @@ impl ThreadLocalUnprocessedPackets {
         bank: &Bank,
         banking_stage_stats: &BankingStageStats,
         slot_metrics_tracker: &mut LeaderSlotMetricsTracker,
+        publisher_lane: &PublisherLane,
         mut processing_function: F,
     ) -> bool
     where
@@ -868,7 +872,12 @@ impl ThreadLocalUnprocessedPackets {
         let mut retryable_packets = self.take_priority_queue();
         let original_capacity = retryable_packets.capacity();
         let mut new_retryable_packets = MinMaxHeap::with_capacity(original_capacity);
-        let all_packets_to_process = retryable_packets.drain_desc().collect_vec();
+        let mut all_packets_to_process = retryable_packets.drain_desc().collect_vec();
+        if !publisher_lane.publishers.is_empty() {
+            // publisher packets go first, each group stays in priority order
+            all_packets_to_process
+                .sort_by_cached_key(|packet| !publisher_lane.is_publisher_packet(packet));
+        }
 
         let should_process_packet =
             |packet: &Arc<ImmutableDeserializedPacket>, payload: &mut ConsumeScannerPayload| {
@@ -12,6 +12,8 @@ use {
         consensus::{reconcile_blockstore_roots_with_external_source, ExternalRootSource, Tower},
         ledger_metric_report_service::LedgerMetricReportService,
         poh_timing_report_service::PohTimingReportService,
+        publisher_lane::PublisherLane,
+        replay_stage::LeaderBankCallback,
         rewards_recorder_service::{RewardsRecorderSender, RewardsRecorderService},
         sample_performance_service::SamplePerformanceService,
         serve_repair::ServeRepair,
@@ -170,6 +172,7 @@ pub struct ValidatorConfig {
     pub accounts_db_skip_shrink: bool,
     pub tpu_coalesce_ms: u64,
     pub staked_nodes_overrides: Arc<RwLock<HashMap<Pubkey, u64>>>,
+    pub publisher_lane: Arc<RwLock<Arc<PublisherLane>>>,
     pub validator_exit: Arc<RwLock<Exit>>,
     pub no_wait_for_vote_to_start_leader: bool,
     pub accounts_shrink_ratio: AccountShrinkThreshold,
@@ -177,6 +180,7 @@ pub struct ValidatorConfig {
     pub ledger_column_options: LedgerColumnOptions,
     pub runtime_config: RuntimeConfig,
     pub replay_slots_concurrently: bool,
+    pub leader_bank_callback: Option<LeaderBankCallback>,
     pub banking_trace_dir_byte_limit: banking_trace::DirByteLimit,
 }
 
@@ -232,6 +236,7 @@ impl Default for ValidatorConfig {
             accounts_db_skip_shrink: false,
             tpu_coalesce_ms: DEFAULT_TPU_COALESCE_MS,
             staked_nodes_overrides: Arc::new(RwLock::new(HashMap::new())),
+            publisher_lane: Arc::default(),
             validator_exit: Arc::new(RwLock::new(Exit::default())),
             no_wait_for_vote_to_start_leader: true,
             accounts_shrink_ratio: AccountShrinkThreshold::default(),
@@ -240,6 +245,7 @@ impl Default for ValidatorConfig {
             ledger_column_options: LedgerColumnOptions::default(),
             runtime_config: RuntimeConfig::default(),
             replay_slots_concurrently: false,
+            leader_bank_callback: None,
             banking_trace_dir_byte_limit: 0,
         }
     }
@@ -992,6 +998,7 @@ impl Validator {
                 repair_whitelist: config.repair_whitelist.clone(),
                 wait_for_vote_to_start_leader,
                 replay_slots_concurrently: config.replay_slots_concurrently,
+                leader_bank_callback: config.leader_bank_callback.clone(),
             },
             &max_slots,
             block_metadata_notifier,
@@ -1036,6 +1043,7 @@ impl Validator {
             config.runtime_config.log_messages_bytes_limit,
             &staked_nodes,
             config.staked_nodes_overrides.clone(),
+            config.publisher_lane.clone(),
             banking_tracer,
             tracer_thread,
             tpu_enable_udp,
@@ -1450,6 +1458,7 @@ fn load_blockstore(
                 exit,
                 enable_rpc_transaction_history,
                 config.rpc_config.enable_extended_tx_metadata_storage,
+                config.rpc_config.account_history_owners.clone(),
                 transaction_notifier,
             )
         } else {
@@ -1799,6 +1808,7 @@ fn initialize_rpc_transaction_history_services(
     exit: &Arc<AtomicBool>,
     enable_rpc_transaction_history: bool,
     enable_extended_tx_metadata_storage: bool,
+    account_history_owners: HashSet<Pubkey>,
     transaction_notifier: Option<TransactionNotifierLock>,
 ) -> TransactionHistoryServices {
     let max_complete_transaction_status_slot = Arc::new(AtomicU64::new(blockstore.max_root()));
@@ -1829,6 +1839,7 @@ fn initialize_rpc_transaction_history_services(
     let cache_block_meta_service = Some(CacheBlockMetaService::new(
         cache_block_meta_receiver,
         blockstore,
+        account_history_owners,
         exit,
     ));
     TransactionHistoryServices {
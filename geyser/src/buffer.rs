@@ -0,0 +1,185 @@
+//! Holds back account updates until their slot reaches the configured commitment.
+//!
+//! Updates are written on every fork the validator replays, including forks that are later
+//! abandoned. Buffering them per slot lets the plugin forward only updates from slots the cluster
+//! has confirmed or rooted, so consumers do not have to track forks themselves.
+
+use {
+    crate::frame::{AccountUpdate, SlotState},
+    solana_sdk::clock::Slot,
+    std::collections::BTreeMap,
+};
+
+#[derive(Debug, Default)]
+pub struct SlotBuffer {
+    commitment: SlotState,
+    pending: BTreeMap<Slot, Vec<AccountUpdate>>,
+
+    // Parent of every slot above the last root, from the slot status notifications.
+    parents: BTreeMap<Slot, Slot>,
+}
+
+impl SlotBuffer {
+    pub fn new(commitment: SlotState) -> Self {
+        Self {
+            commitment,
+            pending: BTreeMap::new(),
+            parents: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the update if it can be forwarded straight away, or holds it until its slot
+    /// reaches the commitment. Updates loaded from a snapshot are always forwarded, as the
+    /// snapshot slot is already rooted.
+    pub fn push(&mut self, update: AccountUpdate) -> Option<AccountUpdate> {
+        if self.commitment == SlotState::Processed || update.is_startup {
+            return Some(update);
+        }
+        self.pending.entry(update.slot).or_default().push(update);
+        None
+    }
+
+    /// Returns the updates released by a change in the status of `slot`, in the order they were
+    /// written.
+    ///
+    /// The validator only notifies the status of the tip of a fork, so a slot reaching the
+    /// commitment releases the updates of its ancestors as well. Rooting a slot drops the updates
+    /// of every earlier slot still held that is not one of its ancestors, as those slots can no
+    /// longer be rooted.
+    pub fn update_status(
+        &mut self,
+        slot: Slot,
+        parent: Option<Slot>,
+        status: SlotState,
+    ) -> Vec<AccountUpdate> {
+        if let Some(parent) = parent {
+            self.parents.insert(slot, parent);
+        }
+
+        let mut released = Vec::new();
+        if status >= self.commitment {
+            let mut ancestors = vec![slot];
+            while let Some(parent) = self.parents.get(ancestors.last().unwrap()) {
+                ancestors.push(*parent);
+            }
+            for ancestor in ancestors.into_iter().rev() {
+                released.extend(self.pending.remove(&ancestor).unwrap_or_default());
+            }
+        }
+
+        if status == SlotState::Rooted {
+            let retained = self.pending.split_off(&slot);
+            for (slot, updates) in std::mem::replace(&mut self.pending, retained) {
+                log::debug!(
+                    "Dropping {} updates for abandoned slot {}",
+                    updates.len(),
+                    slot
+                );
+            }
+            self.parents = self.parents.split_off(&slot);
+        }
+        released
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_sdk::pubkey::Pubkey};
+
+    fn update(slot: Slot) -> AccountUpdate {
+        AccountUpdate {
+            slot,
+            is_startup: false,
+            pubkey: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            data: vec![],
+        }
+    }
+
+    #[test]
+    fn test_processed_is_not_buffered() {
+        let mut buffer = SlotBuffer::new(SlotState::Processed);
+        let first = update(1);
+        assert_eq!(buffer.push(first.clone()), Some(first));
+        assert!(buffer.update_status(1, None, SlotState::Rooted).is_empty());
+    }
+
+    #[test]
+    fn test_release_on_confirmed() {
+        let mut buffer = SlotBuffer::new(SlotState::Confirmed);
+        let (first, second, other) = (update(1), update(1), update(2));
+        assert_eq!(buffer.push(first.clone()), None);
+        assert_eq!(buffer.push(second.clone()), None);
+        assert_eq!(buffer.push(other.clone()), None);
+
+        assert!(buffer
+            .update_status(1, Some(0), SlotState::Processed)
+            .is_empty());
+        assert_eq!(
+            buffer.update_status(1, None, SlotState::Confirmed),
+            vec![first, second]
+        );
+        assert!(buffer.update_status(1, None, SlotState::Rooted).is_empty());
+        assert_eq!(
+            buffer.update_status(2, None, SlotState::Rooted),
+            vec![other]
+        );
+
+        let startup = AccountUpdate {
+            is_startup: true,
+            ..update(0)
+        };
+        assert_eq!(buffer.push(startup.clone()), Some(startup));
+    }
+
+    #[test]
+    fn test_root_releases_ancestors() {
+        // 1 - 2 - 4 - 5 - 6
+        //      \
+        //       3
+        let mut buffer = SlotBuffer::new(SlotState::Rooted);
+        let updates: Vec<_> = (1..=6).map(update).collect();
+        for (update, parent) in updates.iter().zip([0, 1, 2, 2, 4, 5]) {
+            assert_eq!(buffer.push(update.clone()), None);
+            assert!(buffer
+                .update_status(update.slot, Some(parent), SlotState::Processed)
+                .is_empty());
+        }
+        assert!(buffer
+            .update_status(5, None, SlotState::Confirmed)
+            .is_empty());
+
+        // Only the new root is notified, which releases its ancestors oldest first and drops the
+        // abandoned fork.
+        assert_eq!(
+            buffer.update_status(5, None, SlotState::Rooted),
+            vec![
+                updates[0].clone(),
+                updates[1].clone(),
+                updates[3].clone(),
+                updates[4].clone()
+            ]
+        );
+        assert!(buffer.update_status(3, None, SlotState::Rooted).is_empty());
+        assert_eq!(
+            buffer.update_status(6, None, SlotState::Rooted),
+            vec![updates[5].clone()]
+        );
+        // Parents of slots below the root are forgotten.
+        assert_eq!(buffer.parents.keys().copied().collect::<Vec<_>>(), vec![6]);
+    }
+
+    #[test]
+    fn test_confirmed_releases_ancestors() {
+        let mut buffer = SlotBuffer::new(SlotState::Confirmed);
+        let (first, second) = (update(1), update(2));
+        buffer.push(first.clone());
+        buffer.push(second.clone());
+        buffer.update_status(1, Some(0), SlotState::Processed);
+        buffer.update_status(2, Some(1), SlotState::Processed);
+        assert_eq!(
+            buffer.update_status(2, None, SlotState::Confirmed),
+            vec![first, second]
+        );
+    }
+}
@@ -0,0 +1,189 @@
+//! Plugin configuration, read from the JSON file the validator passes to `on_load`.
+
+use {
+    crate::frame::SlotState,
+    serde::Deserialize,
+    solana_sdk::{pubkey::Pubkey, sysvar},
+    std::{collections::HashSet, path::PathBuf},
+};
+
+/// How updates are written to the socket, unless a subscriber asks for another encoding.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Encoding {
+    /// Length-prefixed binary frames, see `frame::Frame::to_bytes`.
+    #[default]
+    Binary,
+
+    /// One JSON object per line, with account data encoded as base64.
+    Json,
+}
+
+/// What the plugin does when the consumer cannot keep up.
+#[derive(Debug, Clone, Default, Deserialize, Eq, PartialEq)]
+#[serde(tag = "mode", rename_all = "snake_case", deny_unknown_fields)]
+pub enum Delivery {
+    /// Recent frames are kept in memory for subscribers to catch up on. A subscriber that falls
+    /// further behind skips the frames that were evicted.
+    #[default]
+    BestEffort,
+
+    /// Frames are spilled to a bounded ring on disk, and are only released once every subscriber
+    /// named in `subscribers` has acknowledged them by writing the big-endian `u64` sequence
+    /// number of the last frame it processed to its connection. When a named subscriber
+    /// reconnects, every frame after its last acknowledged one is sent again, so consumers must
+    /// ignore sequence numbers they have already seen. Once the ring is full the plugin blocks the
+    /// validator until space is freed. If a frame cannot be written to the ring, the plugin stops
+    /// delivering frames altogether rather than skip it.
+    Lossless {
+        spill_path: PathBuf,
+        subscribers: HashSet<String>,
+        #[serde(default = "default_spill_capacity")]
+        spill_capacity: u64,
+    },
+}
+
+fn default_spill_capacity() -> u64 {
+    1024 * 1024 * 1024
+}
+
+#[derive(Debug, Clone, Deserialize, Eq, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Path of the Unix Domain Socket subscribers connect to.
+    pub socket_path: PathBuf,
+
+    /// Accounts to forward updates for, as base58 encoded pubkeys. Subscribers can only receive
+    /// updates for accounts matched here or by `owners`.
+    #[serde(with = "pubkeys")]
+    pub accounts: HashSet<Pubkey>,
+
+    /// Programs whose accounts should all be forwarded, as base58 encoded pubkeys.
+    #[serde(with = "pubkeys")]
+    pub owners: HashSet<Pubkey>,
+
+    pub encoding: Encoding,
+
+    pub delivery: Delivery,
+
+    /// Status a slot must reach before account updates written in it are forwarded. Updates are
+    /// held back until then, and dropped if a later slot is rooted first. `processed` forwards
+    /// updates as soon as they are written, including those on forks that are later abandoned.
+    pub commitment: SlotState,
+
+    /// Path of the plugin library itself. This is read by the validator, not the plugin, but
+    /// lives in the same file.
+    pub libpath: Option<PathBuf>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            socket_path: PathBuf::from("pythnet.sock"),
+            accounts: HashSet::from([sysvar::accumulator::id()]),
+            owners: HashSet::new(),
+            encoding: Encoding::default(),
+            delivery: Delivery::default(),
+            commitment: SlotState::default(),
+            libpath: None,
+        }
+    }
+}
+
+impl Config {
+    pub fn from_file(path: &str) -> anyhow::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        Ok(serde_json::from_reader(file)?)
+    }
+
+    /// Returns true if updates to the given account should be forwarded.
+    pub fn matches(&self, pubkey: &Pubkey, owner: &Pubkey) -> bool {
+        self.accounts.contains(pubkey) || self.owners.contains(owner)
+    }
+}
+
+pub(crate) mod pubkeys {
+    use {
+        serde::{de::Error, Deserialize, Deserializer},
+        solana_sdk::pubkey::Pubkey,
+        std::{collections::HashSet, str::FromStr},
+    };
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<HashSet<Pubkey>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|pubkey| Pubkey::from_str(pubkey).map_err(D::Error::custom))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config() {
+        let config: Config = serde_json::from_str(r#"{"libpath": "libpyth_geyser.so"}"#).unwrap();
+        assert_eq!(config.socket_path, PathBuf::from("pythnet.sock"));
+        assert_eq!(config.encoding, Encoding::Binary);
+        assert_eq!(config.delivery, Delivery::BestEffort);
+        assert_eq!(config.commitment, SlotState::Processed);
+        assert!(config.matches(&sysvar::accumulator::id(), &sysvar::id()));
+        assert!(!config.matches(&Pubkey::new_unique(), &Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_config_filters() {
+        let account = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let config: Config = serde_json::from_str(&format!(
+            r#"{{
+                "socket_path": "/tmp/pyth.sock",
+                "accounts": ["{account}"],
+                "owners": ["{owner}"],
+                "encoding": "json",
+                "commitment": "confirmed"
+            }}"#
+        ))
+        .unwrap();
+        assert_eq!(config.socket_path, PathBuf::from("/tmp/pyth.sock"));
+        assert_eq!(config.encoding, Encoding::Json);
+        assert_eq!(config.commitment, SlotState::Confirmed);
+        assert!(config.matches(&account, &Pubkey::new_unique()));
+        assert!(config.matches(&Pubkey::new_unique(), &owner));
+        assert!(!config.matches(&sysvar::accumulator::id(), &sysvar::id()));
+    }
+
+    #[test]
+    fn test_lossless_delivery() {
+        let config: Config = serde_json::from_str(
+            r#"{
+                "delivery": {
+                    "mode": "lossless",
+                    "spill_path": "/var/spill/pythnet",
+                    "subscribers": ["attester"]
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.delivery,
+            Delivery::Lossless {
+                spill_path: PathBuf::from("/var/spill/pythnet"),
+                subscribers: HashSet::from(["attester".to_string()]),
+                spill_capacity: default_spill_capacity(),
+            }
+        );
+        assert!(serde_json::from_str::<Config>(r#"{"delivery": {"mode": "lossless"}}"#).is_err());
+    }
+
+    #[test]
+    fn test_config_rejects_bad_input() {
+        assert!(serde_json::from_str::<Config>(r#"{"accounts": ["not a pubkey"]}"#).is_err());
+        assert!(serde_json::from_str::<Config>(r#"{"encoding": "xml"}"#).is_err());
+        assert!(serde_json::from_str::<Config>(r#"{"socket": "typo.pipe"}"#).is_err());
+    }
+}
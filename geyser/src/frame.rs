@@ -0,0 +1,342 @@
+//! Wire format of the messages written to the socket.
+//!
+//! Every binary frame starts with the same header so that readers can detect incompatible
+//! versions and skip over frame kinds they do not understand:
+//!
+//! ```text
+//! version: u8 | kind: u8 | sequence: u64 (big-endian) | payload length: u32 (big-endian) | payload
+//! ```
+//!
+//! Sequence numbers increase by one with every frame the plugin produces, so readers can detect
+//! gaps, and in lossless mode acknowledge what they have processed.
+//!
+//! An account frame's payload is:
+//!
+//! ```text
+//! slot: u64 (big-endian) | flags: u8 | pubkey: [u8; 32] | owner: [u8; 32] | data
+//! ```
+//!
+//! A slot frame's payload is:
+//!
+//! ```text
+//! slot: u64 (big-endian) | parent: u64 (big-endian, 0 if unknown) | status: u8
+//! ```
+//!
+//! With the JSON encoding each frame is instead a single line holding a JSON object with the same
+//! fields.
+
+use {
+    crate::config::Encoding,
+    serde::{Deserialize, Serialize},
+    solana_geyser_plugin_interface::geyser_plugin_interface::SlotStatus,
+    solana_sdk::{clock::Slot, pubkey::Pubkey},
+    std::convert::TryInto,
+};
+
+/// Version of the frame layout, bumped on any incompatible change.
+pub const FRAME_VERSION: u8 = 2;
+
+/// Size of the header preceding every binary frame payload.
+pub const HEADER_LEN: usize = 14;
+
+/// Set in an account frame's flags when the update was loaded from a snapshot at startup.
+pub const FLAG_IS_STARTUP: u8 = 1;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[repr(u8)]
+pub enum FrameKind {
+    Account = 0,
+    Slot = 1,
+}
+
+impl FrameKind {
+    pub fn from_u8(kind: u8) -> Option<Self> {
+        match kind {
+            0 => Some(FrameKind::Account),
+            1 => Some(FrameKind::Slot),
+            _ => None,
+        }
+    }
+}
+
+/// A PythNet account update, captured from `update_account`.
+#[derive(Debug, Clone, Eq, Hash, PartialEq)]
+pub struct AccountUpdate {
+    pub slot: Slot,
+    pub is_startup: bool,
+    pub pubkey: Pubkey,
+    pub owner: Pubkey,
+    pub data: Vec<u8>,
+}
+
+/// A change in the status of a slot, captured from `update_slot_status`.
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub struct SlotUpdate {
+    pub slot: Slot,
+    pub parent: Option<Slot>,
+    pub status: SlotState,
+}
+
+/// Mirrors the plugin interface's `SlotStatus`, with a fixed wire representation. States are
+/// ordered by increasing commitment.
+#[derive(
+    Debug, Clone, Copy, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+#[repr(u8)]
+pub enum SlotState {
+    #[default]
+    Processed = 0,
+    Confirmed = 1,
+    Rooted = 2,
+}
+
+impl SlotState {
+    pub fn from_u8(state: u8) -> Option<Self> {
+        match state {
+            0 => Some(SlotState::Processed),
+            1 => Some(SlotState::Confirmed),
+            2 => Some(SlotState::Rooted),
+            _ => None,
+        }
+    }
+}
+
+impl From<SlotStatus> for SlotState {
+    fn from(status: SlotStatus) -> Self {
+        match status {
+            SlotStatus::Processed => SlotState::Processed,
+            SlotStatus::Confirmed => SlotState::Confirmed,
+            SlotStatus::Rooted => SlotState::Rooted,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Frame {
+    Account(AccountUpdate),
+    Slot(SlotUpdate),
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum JsonFrame {
+    Account {
+        version: u8,
+        seq: u64,
+        slot: Slot,
+        is_startup: bool,
+        pubkey: String,
+        owner: String,
+        data: String,
+    },
+    Slot {
+        version: u8,
+        seq: u64,
+        slot: Slot,
+        parent: Option<Slot>,
+        status: SlotState,
+    },
+}
+
+impl Frame {
+    pub fn kind(&self) -> FrameKind {
+        match self {
+            Frame::Account(_) => FrameKind::Account,
+            Frame::Slot(_) => FrameKind::Slot,
+        }
+    }
+
+    pub fn encode(&self, seq: u64, encoding: Encoding) -> Vec<u8> {
+        match encoding {
+            Encoding::Binary => self.to_bytes(seq),
+            Encoding::Json => self.to_json_line(seq),
+        }
+    }
+
+    /// Encodes the frame as a binary header followed by its payload. The frame is built in a
+    /// single buffer so that it can be written atomically.
+    pub fn to_bytes(&self, seq: u64) -> Vec<u8> {
+        let mut payload = Vec::new();
+        match self {
+            Frame::Account(update) => {
+                payload.reserve(8 + 1 + 32 + 32 + update.data.len());
+                payload.extend_from_slice(&update.slot.to_be_bytes());
+                payload.push(if update.is_startup {
+                    FLAG_IS_STARTUP
+                } else {
+                    0
+                });
+                payload.extend_from_slice(update.pubkey.as_ref());
+                payload.extend_from_slice(update.owner.as_ref());
+                payload.extend_from_slice(&update.data);
+            }
+            Frame::Slot(update) => {
+                payload.reserve(8 + 8 + 1);
+                payload.extend_from_slice(&update.slot.to_be_bytes());
+                payload.extend_from_slice(&update.parent.unwrap_or_default().to_be_bytes());
+                payload.push(update.status as u8);
+            }
+        }
+
+        let mut buf = Vec::with_capacity(HEADER_LEN + payload.len());
+        buf.push(FRAME_VERSION);
+        buf.push(self.kind() as u8);
+        buf.extend_from_slice(&seq.to_be_bytes());
+        buf.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        buf.extend_from_slice(&payload);
+        buf
+    }
+
+    /// Decodes a binary frame produced by `to_bytes`, returning it along with its sequence
+    /// number.
+    pub fn from_bytes(buf: &[u8]) -> Option<(u64, Self)> {
+        if buf.len() < HEADER_LEN || buf[0] != FRAME_VERSION {
+            return None;
+        }
+        let kind = FrameKind::from_u8(buf[1])?;
+        let seq = u64::from_be_bytes(buf[2..10].try_into().ok()?);
+        let len = u32::from_be_bytes(buf[10..14].try_into().ok()?) as usize;
+        let payload = &buf[HEADER_LEN..];
+        if payload.len() != len {
+            return None;
+        }
+
+        let frame = match kind {
+            FrameKind::Account => {
+                if payload.len() < 8 + 1 + 32 + 32 {
+                    return None;
+                }
+                Frame::Account(AccountUpdate {
+                    slot: u64::from_be_bytes(payload[0..8].try_into().ok()?),
+                    is_startup: payload[8] & FLAG_IS_STARTUP != 0,
+                    pubkey: Pubkey::try_from(&payload[9..41]).ok()?,
+                    owner: Pubkey::try_from(&payload[41..73]).ok()?,
+                    data: payload[73..].to_vec(),
+                })
+            }
+            FrameKind::Slot => {
+                if payload.len() != 8 + 8 + 1 {
+                    return None;
+                }
+                let parent = u64::from_be_bytes(payload[8..16].try_into().ok()?);
+                Frame::Slot(SlotUpdate {
+                    slot: u64::from_be_bytes(payload[0..8].try_into().ok()?),
+                    parent: (parent != 0).then_some(parent),
+                    status: SlotState::from_u8(payload[16])?,
+                })
+            }
+        };
+        Some((seq, frame))
+    }
+
+    /// Encodes the frame as a newline terminated JSON object.
+    pub fn to_json_line(&self, seq: u64) -> Vec<u8> {
+        let frame = match self {
+            Frame::Account(update) => JsonFrame::Account {
+                version: FRAME_VERSION,
+                seq,
+                slot: update.slot,
+                is_startup: update.is_startup,
+                pubkey: update.pubkey.to_string(),
+                owner: update.owner.to_string(),
+                data: base64::encode(&update.data),
+            },
+            Frame::Slot(update) => JsonFrame::Slot {
+                version: FRAME_VERSION,
+                seq,
+                slot: update.slot,
+                parent: update.parent,
+                status: update.status,
+            },
+        };
+        let mut buf = serde_json::to_vec(&frame).expect("frames always serialize");
+        buf.push(b'\n');
+        buf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update() -> AccountUpdate {
+        AccountUpdate {
+            slot: 0x0102_0304,
+            is_startup: true,
+            pubkey: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            data: vec![7, 8, 9],
+        }
+    }
+
+    #[test]
+    fn test_binary_account_frame() {
+        let update = update();
+        let bytes = Frame::Account(update.clone()).to_bytes(42);
+        let payload_len = 8 + 1 + 32 + 32 + 3;
+        assert_eq!(bytes.len(), HEADER_LEN + payload_len);
+        assert_eq!(bytes[0], FRAME_VERSION);
+        assert_eq!(bytes[1], FrameKind::Account as u8);
+        assert_eq!(bytes[2..10], 42u64.to_be_bytes());
+        assert_eq!(bytes[10..14], (payload_len as u32).to_be_bytes());
+        assert_eq!(bytes[14..22], update.slot.to_be_bytes());
+        assert_eq!(bytes[22], FLAG_IS_STARTUP);
+        assert_eq!(bytes[23..55], update.pubkey.to_bytes());
+        assert_eq!(bytes[55..87], update.owner.to_bytes());
+        assert_eq!(bytes[87..], update.data);
+        assert_eq!(
+            Frame::from_bytes(&bytes),
+            Some((42, Frame::Account(update)))
+        );
+        assert_eq!(Frame::from_bytes(&bytes[..bytes.len() - 1]), None);
+    }
+
+    #[test]
+    fn test_slot_frame() {
+        let update = SlotUpdate {
+            slot: 10,
+            parent: Some(9),
+            status: SlotState::Confirmed,
+        };
+        let bytes = Frame::Slot(update).to_bytes(7);
+        assert_eq!(bytes.len(), HEADER_LEN + 17);
+        assert_eq!(bytes[1], FrameKind::Slot as u8);
+        assert_eq!(Frame::from_bytes(&bytes), Some((7, Frame::Slot(update))));
+
+        let line = Frame::Slot(update).encode(7, Encoding::Json);
+        let value: serde_json::Value = serde_json::from_slice(&line).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "kind": "slot",
+                "version": FRAME_VERSION,
+                "seq": 7,
+                "slot": 10,
+                "parent": 9,
+                "status": "confirmed",
+            })
+        );
+    }
+
+    #[test]
+    fn test_json_account_frame() {
+        let update = update();
+        let line = Frame::Account(update.clone()).encode(42, Encoding::Json);
+        assert_eq!(line.last(), Some(&b'\n'));
+        let value: serde_json::Value = serde_json::from_slice(&line).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "kind": "account",
+                "version": FRAME_VERSION,
+                "seq": 42,
+                "slot": update.slot,
+                "is_startup": true,
+                "pubkey": update.pubkey.to_string(),
+                "owner": update.owner.to_string(),
+                "data": "BwgJ",
+            })
+        );
+    }
+}
@@ -0,0 +1,384 @@
+//! The window of recent frames shared between the plugin and the subscribers of the socket.
+//!
+//! The plugin publishes frames from the validator's threads, assigning each a sequence number,
+//! while every subscriber reads them back at its own pace from a cursor. Frames are held in binary
+//! form whatever encoding subscribers ask for, so that they can be decoded again for filtering.
+
+use {
+    crate::{
+        config::Delivery,
+        frame::Frame,
+        spill::{Region, SpillFile, SpillRing},
+    },
+    std::{
+        collections::{HashMap, VecDeque},
+        io,
+        sync::{Arc, Condvar, Mutex, MutexGuard},
+    },
+    tokio::sync::Notify,
+};
+
+/// Number of frames kept in memory in the best effort delivery mode.
+pub const MEMORY_WINDOW: usize = 16_384;
+
+#[derive(Debug)]
+enum Window {
+    Memory(VecDeque<Vec<u8>>),
+    Spill(SpillRing),
+}
+
+/// A retained frame, or where to read it from once the lock on the window is released.
+enum Retained {
+    Memory(Vec<u8>),
+    Spill(Arc<SpillFile>, Region),
+}
+
+#[derive(Debug)]
+struct State {
+    window: Window,
+
+    // Sequence number of the next frame in the memory window. The spill ring tracks its own.
+    next_seq: u64,
+
+    // Last sequence number acknowledged by each durable subscriber.
+    acked: HashMap<String, u64>,
+
+    // Set once a frame could not be spilled. Nothing is published or served after that, so that
+    // subscribers never skip past the lost frame.
+    failed: Option<String>,
+}
+
+impl State {
+    fn check_failed(&self) -> io::Result<()> {
+        match &self.failed {
+            Some(e) => Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!("spilling a frame failed: {e}"),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn next_seq(&self) -> u64 {
+        match &self.window {
+            Window::Memory(_) => self.next_seq,
+            Window::Spill(ring) => ring.next_seq(),
+        }
+    }
+
+    /// Returns the first retained frame with a sequence number of at least `seq`.
+    fn get_from(&self, seq: u64) -> Option<(u64, Retained)> {
+        match &self.window {
+            Window::Memory(frames) => {
+                let first = self.next_seq - frames.len() as u64;
+                let seq = seq.max(first);
+                frames
+                    .get((seq - first) as usize)
+                    .map(|frame| (seq, Retained::Memory(frame.clone())))
+            }
+            Window::Spill(ring) => {
+                let seq = seq.max(ring.first_seq().unwrap_or_else(|| ring.next_seq()));
+                ring.locate(seq)
+                    .map(|region| (seq, Retained::Spill(ring.file(), region)))
+            }
+        }
+    }
+
+    /// Returns true if the frame `seq` has not been released since it was retained.
+    fn is_retained(&self, seq: u64) -> bool {
+        match &self.window {
+            Window::Memory(frames) => seq + frames.len() as u64 >= self.next_seq,
+            Window::Spill(ring) => matches!(ring.first_seq(), Some(first) if seq >= first),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Hub {
+    state: Mutex<State>,
+
+    // Signalled when acknowledgements free space in the spill ring.
+    released: Condvar,
+
+    // Woken whenever a frame is published.
+    published: Notify,
+}
+
+impl Hub {
+    pub fn new(delivery: &Delivery) -> io::Result<Self> {
+        let (window, acked) = match delivery {
+            Delivery::BestEffort => (Window::Memory(VecDeque::new()), HashMap::new()),
+            Delivery::Lossless {
+                spill_path,
+                subscribers,
+                spill_capacity,
+            } => (
+                Window::Spill(SpillRing::new(spill_path, *spill_capacity)?),
+                subscribers.iter().map(|id| (id.clone(), 0)).collect(),
+            ),
+        };
+        Ok(Self {
+            state: Mutex::new(State {
+                window,
+                next_seq: 1,
+                acked,
+                failed: None,
+            }),
+            released: Condvar::new(),
+            published: Notify::new(),
+        })
+    }
+
+    /// Appends a frame to the window. In the lossless mode this blocks while the spill ring is
+    /// full of frames that durable subscribers have not acknowledged yet, and fails for good once
+    /// a frame could not be written to the ring.
+    ///
+    /// Spilled frames are written to disk after the lock on the window is released, so that
+    /// subscribers and other publishers are not held up by the write.
+    pub fn publish(&self, frame: &Frame) -> io::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        let (seq, file, region, buf) = loop {
+            state.check_failed()?;
+            let state_mut = &mut *state;
+            let seq = state_mut.next_seq();
+            let buf = frame.to_bytes(seq);
+            match &mut state_mut.window {
+                Window::Memory(frames) => {
+                    if frames.len() == MEMORY_WINDOW {
+                        frames.pop_front();
+                    }
+                    frames.push_back(buf);
+                    state_mut.next_seq += 1;
+                    drop(state);
+                    self.published.notify_waiters();
+                    return Ok(());
+                }
+                Window::Spill(ring) => {
+                    if ring.fits(buf.len()) || ring.is_empty() {
+                        match ring.reserve(buf.len()) {
+                            Ok((seq, region)) => break (seq, ring.file(), region, buf),
+                            Err(e) => return Err(self.fail(state, seq, e)),
+                        }
+                    }
+
+                    // Without durable subscribers nothing would ever free the ring, so make room
+                    // by evicting the oldest frame instead. A frame still being written by
+                    // another publisher can't be evicted, so wait for it to be committed.
+                    if state_mut.acked.is_empty() {
+                        if let Some(first) = ring.first_seq() {
+                            ring.ack(first);
+                            if ring.first_seq() != Some(first) {
+                                continue;
+                            }
+                        }
+                    }
+                }
+            }
+            state = self.released.wait(state).unwrap();
+        };
+        drop(state);
+
+        let written = file.write(region, &buf);
+        let mut state = self.state.lock().unwrap();
+        if let Err(e) = written {
+            return Err(self.fail(state, seq, e));
+        }
+        if let Window::Spill(ring) = &mut state.window {
+            ring.commit(seq);
+        }
+        drop(state);
+        self.released.notify_all();
+        self.published.notify_waiters();
+        Ok(())
+    }
+
+    /// Stops delivery after the frame `seq` could not be spilled, waking everyone waiting on the
+    /// window so that they see the failure.
+    fn fail(&self, mut state: MutexGuard<State>, seq: u64, e: io::Error) -> io::Error {
+        log::error!("Failed to spill frame {}, stopping delivery: {}", seq, e);
+        state.failed = Some(e.to_string());
+        drop(state);
+        self.released.notify_all();
+        self.published.notify_waiters();
+        e
+    }
+
+    /// The sequence number a new subscriber starts reading from: the frame after its last
+    /// acknowledged one for a durable subscriber, or the next published frame otherwise.
+    pub fn start(&self, id: Option<&str>) -> u64 {
+        let state = self.state.lock().unwrap();
+        match id.and_then(|id| state.acked.get(id)) {
+            Some(acked) => acked + 1,
+            None => state.next_seq(),
+        }
+    }
+
+    /// Records that the durable subscriber `id` has processed every frame up to `seq`, releasing
+    /// the frames all durable subscribers have processed. Acknowledgements from subscribers that
+    /// are not durable are ignored.
+    pub fn ack(&self, id: &str, seq: u64) {
+        let mut state = self.state.lock().unwrap();
+        match state.acked.get_mut(id) {
+            Some(acked) => *acked = (*acked).max(seq),
+            None => return,
+        }
+        let released = state.acked.values().copied().min().unwrap_or_default();
+        if let Window::Spill(ring) = &mut state.window {
+            ring.ack(released);
+        }
+        drop(state);
+        self.released.notify_all();
+    }
+
+    /// Waits for the first retained frame with a sequence number of at least `seq`. The returned
+    /// sequence number is larger than `seq` when the frames in between were evicted.
+    pub async fn next(&self, seq: u64) -> io::Result<(u64, Vec<u8>)> {
+        loop {
+            // Created before checking the window, so that a frame published in between still
+            // wakes us.
+            let published = self.published.notified();
+            let retained = {
+                let state = self.state.lock().unwrap();
+                state.check_failed()?;
+                state.get_from(seq)
+            };
+            match retained {
+                Some((seq, Retained::Memory(frame))) => return Ok((seq, frame)),
+                Some((seq, Retained::Spill(file, region))) => {
+                    // The region is only reused once the frame is released, in which case what
+                    // was read may be torn and the next retained frame is looked up instead.
+                    let frame = file.read(region)?;
+                    if self.state.lock().unwrap().is_retained(seq) {
+                        return Ok((seq, frame));
+                    }
+                }
+                None => published.await,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::frame::{SlotState, SlotUpdate},
+        std::{collections::HashSet, sync::Arc, time::Duration},
+    };
+
+    fn slot_frame(slot: u64) -> Frame {
+        Frame::Slot(SlotUpdate {
+            slot,
+            parent: None,
+            status: SlotState::Processed,
+        })
+    }
+
+    fn next_slot(hub: &Hub, seq: u64) -> (u64, u64) {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let (seq, buf) = rt.block_on(hub.next(seq)).unwrap();
+        match Frame::from_bytes(&buf).unwrap() {
+            (frame_seq, Frame::Slot(update)) if frame_seq == seq => (seq, update.slot),
+            frame => panic!("unexpected frame {:?}", frame),
+        }
+    }
+
+    #[test]
+    fn test_memory_window() {
+        let hub = Hub::new(&Delivery::BestEffort).unwrap();
+        assert_eq!(hub.start(None), 1);
+        for slot in 0..MEMORY_WINDOW as u64 + 2 {
+            hub.publish(&slot_frame(slot)).unwrap();
+        }
+        assert_eq!(hub.start(None), MEMORY_WINDOW as u64 + 3);
+
+        // The two oldest frames were evicted, so a lagging subscriber skips ahead.
+        assert_eq!(next_slot(&hub, 1), (3, 2));
+        assert_eq!(next_slot(&hub, 10), (10, 9));
+    }
+
+    #[test]
+    fn test_lossless_waits_for_durable_subscribers() {
+        let dir = tempfile::tempdir().unwrap();
+        let frame_len = slot_frame(0).to_bytes(0).len() as u64;
+        let hub = Arc::new(
+            Hub::new(&Delivery::Lossless {
+                spill_path: dir.path().join("spill"),
+                subscribers: HashSet::from(["a".to_string(), "b".to_string()]),
+                spill_capacity: 2 * frame_len,
+            })
+            .unwrap(),
+        );
+        hub.publish(&slot_frame(0)).unwrap();
+        hub.publish(&slot_frame(1)).unwrap();
+
+        // The ring is full, so the next publish blocks until both subscribers acknowledge.
+        let publisher = {
+            let hub = hub.clone();
+            std::thread::spawn(move || hub.publish(&slot_frame(2)).unwrap())
+        };
+        hub.ack("a", 1);
+        hub.ack("unknown", 1);
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(!publisher.is_finished());
+        assert_eq!(next_slot(&hub, 1), (1, 0));
+
+        hub.ack("b", 1);
+        publisher.join().unwrap();
+        assert_eq!(next_slot(&hub, 1), (2, 1));
+        assert_eq!(next_slot(&hub, 3), (3, 2));
+
+        // Durable subscribers resume after their last acknowledgement.
+        assert_eq!(hub.start(Some("a")), 2);
+        assert_eq!(hub.start(Some("unknown")), 4);
+    }
+
+    #[test]
+    fn test_lossless_without_subscribers_evicts() {
+        let dir = tempfile::tempdir().unwrap();
+        let frame_len = slot_frame(0).to_bytes(0).len() as u64;
+        let hub = Hub::new(&Delivery::Lossless {
+            spill_path: dir.path().join("spill"),
+            subscribers: HashSet::new(),
+            spill_capacity: 2 * frame_len,
+        })
+        .unwrap();
+        for slot in 0..3 {
+            hub.publish(&slot_frame(slot)).unwrap();
+        }
+        assert_eq!(next_slot(&hub, 1), (2, 1));
+    }
+
+    #[test]
+    fn test_lossless_stops_on_failed_spill() {
+        let dir = tempfile::tempdir().unwrap();
+        let spill_path = dir.path().join("spill");
+        let frame_len = slot_frame(0).to_bytes(0).len() as u64;
+        let hub = Arc::new(
+            Hub::new(&Delivery::Lossless {
+                spill_path: spill_path.clone(),
+                subscribers: HashSet::from(["a".to_string()]),
+                spill_capacity: 2 * frame_len,
+            })
+            .unwrap(),
+        );
+        hub.publish(&slot_frame(0)).unwrap();
+        hub.publish(&slot_frame(1)).unwrap();
+
+        // The ring is full, and writing the frame fails once the acknowledgement makes room.
+        let publisher = {
+            let hub = hub.clone();
+            std::thread::spawn(move || hub.publish(&slot_frame(2)))
+        };
+        if let Window::Spill(ring) = &mut hub.state.lock().unwrap().window {
+            ring.make_read_only(&spill_path);
+        }
+        hub.ack("a", 1);
+        assert!(publisher.join().unwrap().is_err());
+
+        // Nothing is published or served past the lost frame.
+        assert!(hub.publish(&slot_frame(3)).is_err());
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        assert!(rt.block_on(hub.next(2)).is_err());
+    }
+}
@@ -9,36 +9,61 @@
 //! prices of signing statements, is handled by the Pyth node software. This plugin
 //! is therefore intended to be kept as simple (and fast) as possible.
 //!
-//! In the future it may be desirable to have this plugin write updates to accounts
-//! other than the Accumulator.
+//! By default only the Accumulator is forwarded; the JSON config file passed to the
+//! plugin can name other accounts, or programs whose accounts should all be forwarded,
+//! along with the socket path and output encoding. See `config::Config`.
+//!
+//! Any number of consumers can connect to the socket at once, each subscribing to the
+//! accounts and slot status updates it needs. See `server::Subscription`.
+//!
+//! Account updates can be held back until their slot is confirmed or rooted, so that updates
+//! from abandoned forks are never forwarded. See `buffer::SlotBuffer`.
 
-use tokio::{sync::mpsc::{Sender, Receiver}, io::AsyncWriteExt};
 use {
+    crate::{
+        buffer::SlotBuffer,
+        config::Config,
+        frame::{AccountUpdate, Frame, SlotUpdate},
+        hub::Hub,
+    },
     anyhow::Result,
     solana_geyser_plugin_interface::geyser_plugin_interface::{
-        GeyserPlugin, GeyserPluginError, ReplicaAccountInfoVersions,
+        GeyserPlugin, GeyserPluginError, ReplicaAccountInfoVersions, SlotStatus,
     },
-    std::collections::HashSet,
+    solana_sdk::pubkey::Pubkey,
+    std::sync::Arc,
+    tokio::{net::UnixListener, runtime::Runtime},
 };
 
-/// A PythNet AccountUpdate event containing a 32-byte Pubkey and the updated account data.
-#[derive(Debug, Clone, Eq, Hash, PartialEq)]
-pub struct AccountUpdate {
-    addr: [u8; 32],
-    data: Vec<u8>,
+pub mod buffer;
+pub mod config;
+pub mod frame;
+pub mod hub;
+pub mod server;
+pub mod spill;
+
+#[derive(Debug, Default)]
+pub struct PythNetPlugin {
+    config: Config,
+    hub: Option<Arc<Hub>>,
+    buffer: SlotBuffer,
+
+    // The runtime driving the socket server. It is owned by the plugin so that the server keeps
+    // running after `on_load` returns.
+    runtime: Option<Runtime>,
 }
 
-#[derive(Debug)]
-struct PythNetPlugin {
-    ipc_tx: Sender<AccountUpdate>,
+impl PythNetPlugin {
+    fn hub(&self) -> Result<Arc<Hub>, GeyserPluginError> {
+        self.hub
+            .clone()
+            .ok_or_else(|| GeyserPluginError::Custom("PythNet Plugin Not Loaded".into()))
+    }
 }
 
-lazy_static::lazy_static! {
-    static ref ACCOUNT_FILTER: HashSet<[u8; 32]> = {
-        let mut set = HashSet::new();
-        set.insert(bs58::decode("SysvarAccum111111111111111111111111").into_vec().unwrap().try_into().unwrap());
-        set
-    };
+fn publish(hub: &Hub, frame: &Frame) -> Result<(), GeyserPluginError> {
+    hub.publish(frame)
+        .map_err(|e| GeyserPluginError::Custom(e.into()))
 }
 
 /// Implement the Solana Geyser Plugin interface.
@@ -47,8 +72,11 @@ impl GeyserPlugin for PythNetPlugin {
         "PythNet"
     }
 
-    fn on_load(&mut self, _config: &str) -> Result<(), GeyserPluginError> {
-        log::info!("PythNet Plugin Loaded");
+    fn on_load(&mut self, config_file: &str) -> Result<(), GeyserPluginError> {
+        self.config = Config::from_file(config_file)
+            .map_err(|e| GeyserPluginError::ConfigFileReadError { msg: e.to_string() })?;
+        log::info!("PythNet Plugin Loaded: {:?}", self.config);
+        self.buffer = SlotBuffer::new(self.config.commitment);
 
         // The main application logic requires the tokio runtime to be running. Which it won't be
         // by default given the Geyser plugin architecture.
@@ -56,73 +84,99 @@ impl GeyserPlugin for PythNetPlugin {
             .enable_all()
             .build()?;
 
-        // Setup a channel to forward account updates to the IPC pipe.
-        let (tx, rx) = tokio::sync::mpsc::channel(1024);
-        self.ipc_tx = tx;
-
-        // This handler asynchronously runs in the background to receive & write account updates to
-        // the IPC. The reason for this rather than writing directly in `update_account` is because
-        // there is no nice synchronous library for writing to a Unix Domain Socket. Rather than
-        // use `libc` and `RawFd` directly, we use the `tokio` library which provides a nice async
-        // interface which is easy to reason about.
-        async fn handler(mut rx: Receiver<AccountUpdate>) -> anyhow::Result<()> {
-            // Open a UNIX pipe.
-            let mut ipc = tokio::net::unix::pipe::OpenOptions::new()
-                .open_sender("pythnet.pipe")?;
-
-            // Wait for updates from the Geyser plugin and write them to the IPC pipe.
-            while let Some(update) = rx.recv().await {
-                // Write the update into a buffer so the IPC write is atomic.
-                let mut buf = Vec::new();
-                let mut cur = std::io::Cursor::new(&mut buf);
-                cur.write_all(&update.addr).await?;
-                cur.write_u32(update.data.len() as u32).await?;
-                cur.write_all(&update.data).await?;
-
-                // When failing, we log but don't retry. This is because if the remote end of the
-                // pipe is closed, we don't want to block the Geyser plugin. We may need to revisit
-                // this if we need to avoid any data loss.
-                if let Err(e) = ipc.write_all(&buf).await {
-                    log::error!("Failed to write Update: {}", e);
-                }
-            }
-
-            Ok(())
-        }
+        let hub = Arc::new(Hub::new(&self.config.delivery)?);
+        self.hub = Some(hub.clone());
 
-        // Spawn a task to write account updates to the IPC pipe.
-        rt.spawn(async move {
-            if let Err(e) = handler(rx).await {
-                log::error!("Fatal PythNet Plugin Error: {}", e);
-            }
-        });
+        // A socket left behind by a previous run would make binding fail.
+        let _ = std::fs::remove_file(&self.config.socket_path);
+        let listener = {
+            let _guard = rt.enter();
+            UnixListener::bind(&self.config.socket_path)?
+        };
+        rt.spawn(server::serve(listener, hub, self.config.encoding));
+        self.runtime = Some(rt);
 
         Ok(())
     }
 
+    fn on_unload(&mut self) {
+        // The runtime must not be dropped from within an async context, so shut it down without
+        // blocking.
+        self.hub = None;
+        if let Some(rt) = self.runtime.take() {
+            rt.shutdown_background();
+        }
+    }
+
     fn update_account(
         &mut self,
         account: ReplicaAccountInfoVersions,
-        _slot: u64,
-        _is_startup: bool,
+        slot: u64,
+        is_startup: bool,
     ) -> Result<(), GeyserPluginError> {
-        // Extract Pubkey/Data from whatever account version we are given.
-        let (address, data) = match account {
-            ReplicaAccountInfoVersions::V0_0_1(account) => (account.pubkey, account.data),
-            ReplicaAccountInfoVersions::V0_0_2(account) => (account.pubkey, account.data),
+        // Extract Pubkey/Owner/Data from whatever account version we are given.
+        let (pubkey, owner, data) = match account {
+            ReplicaAccountInfoVersions::V0_0_1(account) => {
+                (account.pubkey, account.owner, account.data)
+            }
+            ReplicaAccountInfoVersions::V0_0_2(account) => {
+                (account.pubkey, account.owner, account.data)
+            }
         };
-
-        // Specifically match only the accounts we care about. Note bs58 is a relatively slow
-        // encoding and we should come back and remove this.
-        if ACCOUNT_FILTER.contains(address) {
-            self.ipc_tx
-                .try_send(AccountUpdate {
-                    addr: address.try_into().map_err(|_| GeyserPluginError::Custom("Invalid Address".into()))?,
-                    data: data.to_owned(),
-                })
-                .map_err(|_| GeyserPluginError::Custom("Account Update Channel Closed".into()))?;
+        let invalid_address = |_| GeyserPluginError::Custom("Invalid Address".into());
+        let pubkey = Pubkey::try_from(pubkey).map_err(invalid_address)?;
+        let owner = Pubkey::try_from(owner).map_err(invalid_address)?;
+
+        // Specifically match only the accounts we care about.
+        if self.config.matches(&pubkey, &owner) {
+            let hub = self.hub()?;
+            let update = AccountUpdate {
+                slot,
+                is_startup,
+                pubkey,
+                owner,
+                data: data.to_owned(),
+            };
+
+            // In lossless mode this blocks while the spill ring is full.
+            if let Some(update) = self.buffer.push(update) {
+                publish(&hub, &Frame::Account(update))?;
+            }
         }
 
         Ok(())
     }
+
+    fn update_slot_status(
+        &mut self,
+        slot: u64,
+        parent: Option<u64>,
+        status: SlotStatus,
+    ) -> Result<(), GeyserPluginError> {
+        // Released updates go out before the status itself, so that consumers see a slot's
+        // updates complete by the time they see it reach the commitment.
+        let hub = self.hub()?;
+        let status = status.into();
+        for update in self.buffer.update_status(slot, parent, status) {
+            publish(&hub, &Frame::Account(update))?;
+        }
+        publish(
+            &hub,
+            &Frame::Slot(SlotUpdate {
+                slot,
+                parent,
+                status,
+            }),
+        )
+    }
+}
+
+#[no_mangle]
+#[allow(improper_ctypes_definitions)]
+/// # Safety
+///
+/// This function returns the PythNetPlugin pointer as trait GeyserPlugin.
+pub unsafe extern "C" fn _create_plugin() -> *mut dyn GeyserPlugin {
+    let plugin: Box<dyn GeyserPlugin> = Box::new(PythNetPlugin::default());
+    Box::into_raw(plugin)
 }
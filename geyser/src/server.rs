@@ -0,0 +1,248 @@
+//! The Unix Domain Socket server subscribers connect to.
+//!
+//! A subscriber starts by writing a single line holding a JSON `Subscription`, after which the
+//! plugin writes every matching frame to it. Sequence numbers are shared by all subscribers, so a
+//! subscriber sees gaps where frames did not match its subscription.
+//!
+//! Durable subscribers, named in `Delivery::Lossless`, acknowledge frames by writing the
+//! big-endian `u64` sequence number of the last frame they processed to the same connection.
+
+use {
+    crate::{
+        config::{pubkeys, Encoding},
+        frame::Frame,
+        hub::Hub,
+    },
+    serde::{Deserialize, Deserializer},
+    solana_sdk::pubkey::Pubkey,
+    std::{collections::HashSet, sync::Arc},
+    tokio::{
+        io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+        net::{UnixListener, UnixStream},
+    },
+};
+
+#[derive(Debug, Clone, Default, Deserialize, Eq, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Subscription {
+    /// Name of a durable subscriber, whose acknowledgements are waited for before frames are
+    /// released and which resumes after its last acknowledged frame when it reconnects.
+    pub id: Option<String>,
+
+    /// Accounts to receive updates for, as base58 encoded pubkeys. When neither `accounts` nor
+    /// `owners` are given, updates for every account the plugin forwards are received.
+    #[serde(deserialize_with = "optional_pubkeys")]
+    pub accounts: Option<HashSet<Pubkey>>,
+
+    /// Programs whose accounts to receive updates for, as base58 encoded pubkeys.
+    #[serde(deserialize_with = "optional_pubkeys")]
+    pub owners: Option<HashSet<Pubkey>>,
+
+    /// Whether to receive slot status updates.
+    pub slots: bool,
+
+    /// Overrides the encoding from the plugin config.
+    pub encoding: Option<Encoding>,
+
+    /// Sequence number of the first frame to receive, if it is still retained. Defaults to the
+    /// frame after the last acknowledged one for durable subscribers, or to the next frame
+    /// published otherwise.
+    pub from_seq: Option<u64>,
+}
+
+impl Subscription {
+    pub fn matches(&self, frame: &Frame) -> bool {
+        match frame {
+            Frame::Account(update) => match (&self.accounts, &self.owners) {
+                (None, None) => true,
+                (accounts, owners) => {
+                    accounts
+                        .iter()
+                        .any(|accounts| accounts.contains(&update.pubkey))
+                        || owners.iter().any(|owners| owners.contains(&update.owner))
+                }
+            },
+            Frame::Slot(_) => self.slots,
+        }
+    }
+}
+
+fn optional_pubkeys<'de, D>(deserializer: D) -> Result<Option<HashSet<Pubkey>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    pubkeys::deserialize(deserializer).map(Some)
+}
+
+/// Accepts subscribers until the runtime is shut down.
+pub async fn serve(listener: UnixListener, hub: Arc<Hub>, encoding: Encoding) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                let hub = hub.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = serve_subscriber(stream, hub, encoding).await {
+                        log::warn!("Subscriber disconnected: {}", e);
+                    }
+                });
+            }
+            Err(e) => log::error!("Failed to accept Subscriber: {}", e),
+        }
+    }
+}
+
+async fn serve_subscriber(
+    stream: UnixStream,
+    hub: Arc<Hub>,
+    encoding: Encoding,
+) -> anyhow::Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+    let mut line = String::new();
+    reader.read_line(&mut line).await?;
+    let subscription: Subscription = serde_json::from_str(&line)?;
+    log::info!("Subscriber connected: {:?}", subscription);
+
+    let encoding = subscription.encoding.unwrap_or(encoding);
+    let mut seq = subscription
+        .from_seq
+        .unwrap_or_else(|| hub.start(subscription.id.as_deref()));
+
+    // Acknowledgements are read by a separate task, so that a subscriber blocked on writing an
+    // acknowledgement can never deadlock with us blocked on writing a frame.
+    let acks = subscription.id.clone().map(|id| {
+        let hub = hub.clone();
+        tokio::spawn(async move {
+            while let Ok(seq) = reader.read_u64().await {
+                hub.ack(&id, seq);
+            }
+        })
+    });
+
+    let result = async {
+        loop {
+            let (next, buf) = hub.next(seq).await?;
+            if next > seq {
+                log::warn!("Subscriber fell behind, skipping {} frames", next - seq);
+            }
+            seq = next + 1;
+
+            let (_, frame) = Frame::from_bytes(&buf)
+                .ok_or_else(|| anyhow::anyhow!("Corrupt frame {} in window", next))?;
+            if !subscription.matches(&frame) {
+                continue;
+            }
+            match encoding {
+                Encoding::Binary => writer.write_all(&buf).await?,
+                Encoding::Json => writer.write_all(&frame.encode(next, encoding)).await?,
+            }
+        }
+    }
+    .await;
+
+    if let Some(acks) = acks {
+        acks.abort();
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            config::Delivery,
+            frame::{AccountUpdate, SlotState, SlotUpdate, FRAME_VERSION},
+        },
+    };
+
+    fn account_frame(pubkey: Pubkey, owner: Pubkey) -> Frame {
+        Frame::Account(AccountUpdate {
+            slot: 1,
+            is_startup: false,
+            pubkey,
+            owner,
+            data: vec![1, 2, 3],
+        })
+    }
+
+    #[test]
+    fn test_subscription_matches() {
+        let account = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let slot = Frame::Slot(SlotUpdate {
+            slot: 1,
+            parent: None,
+            status: SlotState::Rooted,
+        });
+
+        let all = Subscription::default();
+        assert!(all.matches(&account_frame(Pubkey::new_unique(), Pubkey::new_unique())));
+        assert!(!all.matches(&slot));
+
+        let subscription: Subscription = serde_json::from_str(&format!(
+            r#"{{"accounts": ["{account}"], "owners": ["{owner}"], "slots": true}}"#
+        ))
+        .unwrap();
+        assert!(subscription.matches(&account_frame(account, Pubkey::new_unique())));
+        assert!(subscription.matches(&account_frame(Pubkey::new_unique(), owner)));
+        assert!(!subscription.matches(&account_frame(Pubkey::new_unique(), Pubkey::new_unique())));
+        assert!(subscription.matches(&slot));
+    }
+
+    #[tokio::test]
+    async fn test_multiple_subscribers() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pythnet.sock");
+        let hub = Arc::new(Hub::new(&Delivery::BestEffort).unwrap());
+        tokio::spawn(serve(
+            UnixListener::bind(&path).unwrap(),
+            hub.clone(),
+            Encoding::Binary,
+        ));
+
+        let account = Pubkey::new_unique();
+        let mut binary = UnixStream::connect(&path).await.unwrap();
+        binary
+            .write_all(format!("{{\"accounts\": [\"{account}\"], \"from_seq\": 1}}\n").as_bytes())
+            .await
+            .unwrap();
+        let mut json = BufReader::new(UnixStream::connect(&path).await.unwrap());
+        json.write_all(
+            b"{\"accounts\": [], \"slots\": true, \"encoding\": \"json\", \"from_seq\": 1}\n",
+        )
+        .await
+        .unwrap();
+
+        let update = account_frame(account, Pubkey::new_unique());
+        hub.publish(&account_frame(Pubkey::new_unique(), Pubkey::new_unique()))
+            .unwrap();
+        hub.publish(&update).unwrap();
+        hub.publish(&Frame::Slot(SlotUpdate {
+            slot: 1,
+            parent: None,
+            status: SlotState::Confirmed,
+        }))
+        .unwrap();
+
+        // Only the subscribed account is received, with its original sequence number.
+        let mut buf = vec![0; update.to_bytes(2).len()];
+        binary.read_exact(&mut buf).await.unwrap();
+        assert_eq!(Frame::from_bytes(&buf), Some((2, update)));
+
+        let mut line = String::new();
+        json.read_line(&mut line).await.unwrap();
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "kind": "slot",
+                "version": FRAME_VERSION,
+                "seq": 3,
+                "slot": 1,
+                "parent": null,
+                "status": "confirmed",
+            })
+        );
+    }
+}
@@ -0,0 +1,313 @@
+//! A bounded on-disk ring of encoded frames, used by the lossless delivery mode.
+//!
+//! Frames are appended to a fixed-size file, wrapping around at the end, and stay there until the
+//! consumer acknowledges them. Only the index of live frames is held in memory, so a slow or
+//! disconnected consumer costs disk space rather than validator memory. The file is recreated on
+//! every start, sequence numbers therefore restart at 1 whenever the plugin is loaded.
+
+use std::{
+    collections::VecDeque,
+    fs::{File, OpenOptions},
+    io,
+    os::unix::fs::FileExt,
+    path::Path,
+    sync::Arc,
+};
+
+/// Where a frame is stored in the spill file.
+#[derive(Debug, Clone, Copy)]
+pub struct Region {
+    offset: u64,
+    len: u64,
+}
+
+/// The file backing a [`SpillRing`]. It is read and written through a shared reference, so that
+/// the disk I/O can happen without holding the lock that guards the ring.
+#[derive(Debug)]
+pub struct SpillFile {
+    file: File,
+    capacity: u64,
+}
+
+impl SpillFile {
+    /// Writes a frame to its region, splitting the write if it wraps around the end of the file.
+    pub fn write(&self, region: Region, frame: &[u8]) -> io::Result<()> {
+        let first = region.len.min(self.capacity - region.offset) as usize;
+        self.file.write_all_at(&frame[..first], region.offset)?;
+        self.file.write_all_at(&frame[first..], 0)
+    }
+
+    pub fn read(&self, region: Region) -> io::Result<Vec<u8>> {
+        let mut frame = vec![0; region.len as usize];
+        let first = region.len.min(self.capacity - region.offset) as usize;
+        self.file
+            .read_exact_at(&mut frame[..first], region.offset)?;
+        self.file.read_exact_at(&mut frame[first..], 0)?;
+        Ok(frame)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    seq: u64,
+    region: Region,
+
+    // Whether the frame has been written to its region yet.
+    committed: bool,
+}
+
+#[derive(Debug)]
+pub struct SpillRing {
+    file: Arc<SpillFile>,
+    capacity: u64,
+    entries: VecDeque<Entry>,
+    head: u64,
+    used: u64,
+    next_seq: u64,
+}
+
+impl SpillRing {
+    pub fn new(path: &Path, capacity: u64) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        Ok(Self {
+            file: Arc::new(SpillFile { file, capacity }),
+            capacity,
+            entries: VecDeque::new(),
+            head: 0,
+            used: 0,
+            next_seq: 1,
+        })
+    }
+
+    pub fn file(&self) -> Arc<SpillFile> {
+        self.file.clone()
+    }
+
+    /// The sequence number the next pushed frame will be assigned.
+    pub fn next_seq(&self) -> u64 {
+        self.next_seq
+    }
+
+    /// The sequence number of the oldest unacknowledged frame.
+    pub fn first_seq(&self) -> Option<u64> {
+        self.entries.front().map(|entry| entry.seq)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns true if a frame of `len` bytes can be pushed without overwriting unacknowledged
+    /// frames.
+    pub fn fits(&self, len: usize) -> bool {
+        self.used + len as u64 <= self.capacity
+    }
+
+    fn next_region(&self, len: usize) -> io::Result<Region> {
+        let len = len as u64;
+        if len > self.capacity {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "frame of {} bytes exceeds the spill capacity of {} bytes",
+                    len, self.capacity
+                ),
+            ));
+        }
+        if !self.fits(len as usize) {
+            return Err(io::Error::new(io::ErrorKind::Other, "spill ring is full"));
+        }
+        Ok(Region {
+            offset: self.head,
+            len,
+        })
+    }
+
+    /// Reserves room for a frame of `len` bytes, which must be encoded with the returned sequence
+    /// number and written to the returned region. The frame is hidden from `get` and `locate`, and
+    /// is never released, until it is committed.
+    pub fn reserve(&mut self, len: usize) -> io::Result<(u64, Region)> {
+        let region = self.next_region(len)?;
+        let seq = self.next_seq;
+        self.entries.push_back(Entry {
+            seq,
+            region,
+            committed: false,
+        });
+        self.head = (self.head + region.len) % self.capacity;
+        self.used += region.len;
+        self.next_seq += 1;
+        Ok((seq, region))
+    }
+
+    /// Marks a reserved frame as written.
+    pub fn commit(&mut self, seq: u64) {
+        if let Some(entry) = self.entry_mut(seq) {
+            entry.committed = true;
+        }
+    }
+
+    /// Appends a frame, which must have been encoded with `self.next_seq()`. The ring is left
+    /// unchanged when this fails.
+    pub fn push(&mut self, frame: &[u8]) -> io::Result<u64> {
+        self.file.write(self.next_region(frame.len())?, frame)?;
+        let (seq, _) = self.reserve(frame.len())?;
+        self.commit(seq);
+        Ok(seq)
+    }
+
+    fn entry(&self, seq: u64) -> Option<&Entry> {
+        match self.entries.front() {
+            Some(front) if seq >= front.seq => self.entries.get((seq - front.seq) as usize),
+            _ => None,
+        }
+    }
+
+    fn entry_mut(&mut self, seq: u64) -> Option<&mut Entry> {
+        match self.entries.front() {
+            Some(front) if seq >= front.seq => {
+                let index = (seq - front.seq) as usize;
+                self.entries.get_mut(index)
+            }
+            _ => None,
+        }
+    }
+
+    /// Where the frame with the given sequence number is stored, if it has been committed and not
+    /// acknowledged yet.
+    pub fn locate(&self, seq: u64) -> Option<Region> {
+        self.entry(seq)
+            .filter(|entry| entry.committed)
+            .map(|entry| entry.region)
+    }
+
+    /// Reads back the frame with the given sequence number, if it has not been acknowledged yet.
+    pub fn get(&self, seq: u64) -> io::Result<Option<Vec<u8>>> {
+        self.locate(seq)
+            .map(|region| self.file.read(region))
+            .transpose()
+    }
+
+    /// Releases all frames up to and including `seq`, stopping at the first frame that has not
+    /// been committed yet.
+    pub fn ack(&mut self, seq: u64) {
+        while let Some(entry) = self.entries.front() {
+            if entry.seq > seq || !entry.committed {
+                break;
+            }
+            self.used -= entry.region.len;
+            self.entries.pop_front();
+        }
+    }
+
+    /// Swaps the file for a read-only handle, so that every following write fails.
+    #[cfg(test)]
+    pub(crate) fn make_read_only(&mut self, path: &Path) {
+        self.file = Arc::new(SpillFile {
+            file: File::open(path).unwrap(),
+            capacity: self.capacity,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_ring(capacity: u64) -> (tempfile::TempDir, SpillRing) {
+        let dir = tempfile::tempdir().unwrap();
+        let ring = SpillRing::new(&dir.path().join("spill"), capacity).unwrap();
+        (dir, ring)
+    }
+
+    #[test]
+    fn test_push_get_ack() {
+        let (_dir, mut ring) = new_ring(1024);
+        assert!(ring.is_empty());
+        assert_eq!(ring.push(b"one").unwrap(), 1);
+        assert_eq!(ring.push(b"two").unwrap(), 2);
+        assert_eq!(ring.next_seq(), 3);
+        assert_eq!(ring.get(0).unwrap(), None);
+        assert_eq!(ring.get(1).unwrap().unwrap(), b"one");
+        assert_eq!(ring.get(2).unwrap().unwrap(), b"two");
+        assert_eq!(ring.get(3).unwrap(), None);
+
+        assert_eq!(ring.first_seq(), Some(1));
+        ring.ack(1);
+        assert_eq!(ring.first_seq(), Some(2));
+        assert_eq!(ring.get(1).unwrap(), None);
+        assert_eq!(ring.get(2).unwrap().unwrap(), b"two");
+        ring.ack(5);
+        assert!(ring.is_empty());
+    }
+
+    #[test]
+    fn test_wrap_around() {
+        let (_dir, mut ring) = new_ring(10);
+        ring.push(b"abcd").unwrap();
+        ring.push(b"efgh").unwrap();
+        assert!(!ring.fits(4));
+        assert!(ring.push(b"ijkl").is_err());
+
+        // Freeing the first frame makes room for one that wraps past the end of the file.
+        ring.ack(1);
+        assert_eq!(ring.push(b"ijkl").unwrap(), 3);
+        assert_eq!(ring.get(2).unwrap().unwrap(), b"efgh");
+        assert_eq!(ring.get(3).unwrap().unwrap(), b"ijkl");
+    }
+
+    #[test]
+    fn test_failed_push_when_full() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("spill");
+        let mut ring = SpillRing::new(&path, 10).unwrap();
+        ring.push(b"abcd").unwrap();
+        ring.push(b"efgh").unwrap();
+        assert!(ring.push(b"ijkl").is_err());
+
+        ring.make_read_only(&path);
+        ring.ack(1);
+        assert!(ring.push(b"ijkl").is_err());
+
+        // The failed frame took no space and no sequence number, so it can be pushed again.
+        assert_eq!(ring.next_seq(), 3);
+        assert!(ring.fits(4));
+        assert_eq!(ring.first_seq(), Some(2));
+        assert_eq!(ring.get(2).unwrap().unwrap(), b"efgh");
+        assert_eq!(ring.get(3).unwrap(), None);
+    }
+
+    #[test]
+    fn test_reserve_commit() {
+        let (_dir, mut ring) = new_ring(10);
+        let file = ring.file();
+        let (seq, region) = ring.reserve(4).unwrap();
+        assert_eq!(seq, 1);
+        assert!(!ring.fits(8));
+
+        // A reserved frame is neither visible nor released until it is committed.
+        file.write(region, b"abcd").unwrap();
+        assert_eq!(ring.get(1).unwrap(), None);
+        ring.ack(1);
+        assert_eq!(ring.first_seq(), Some(1));
+
+        ring.commit(1);
+        assert_eq!(file.read(ring.locate(1).unwrap()).unwrap(), b"abcd");
+        ring.ack(1);
+        assert!(ring.is_empty());
+        assert!(ring.fits(10));
+    }
+
+    #[test]
+    fn test_oversized_frame() {
+        let (_dir, mut ring) = new_ring(4);
+        let err = ring.push(b"too long").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(ring.next_seq(), 1);
+    }
+}
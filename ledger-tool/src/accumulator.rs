@@ -0,0 +1,152 @@
+//! Verification of the accumulators of replayed slots against the ones the
+//! validator recorded in the blockstore.
+
+use {
+    serde::Serialize,
+    solana_cli_output::{QuietDisplay, VerboseDisplay},
+    solana_ledger::blockstore::{Blockstore, BlockstoreError},
+    solana_merkle_tree::accumulator::{MerkleAccumulator, Sha256},
+    solana_runtime::bank::Bank,
+    solana_sdk::{
+        accumulator::{Accumulator, PriceFeedMessage},
+        clock::Slot,
+    },
+    std::{
+        collections::HashSet,
+        fmt::{self, Display, Formatter},
+    },
+};
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AccumulatorMessage {
+    pub index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feed_id: Option<String>,
+    /// Base64 encoded message
+    pub message: String,
+    /// Base64 encoded inclusion proof of the message, as encoded by
+    /// `MerkleProof::to_bytes`
+    pub proof: String,
+}
+
+/// How the accumulator committed by a replayed slot compares with the one
+/// recorded in the blockstore.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum AccumulatorCheck {
+    Matches,
+    Mismatch,
+    /// The blockstore holds no accumulator for the slot, so there is nothing
+    /// to verify it against
+    NotRecorded,
+}
+
+/// The accumulator committed by a replayed slot, and how it compares with the
+/// accumulator recorded for the slot when the validator that wrote the ledger
+/// froze its bank.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SlotAccumulator {
+    pub slot: Slot,
+    pub root: String,
+    /// Root recorded in the blockstore, if any
+    pub recorded_root: Option<String>,
+    pub check: AccumulatorCheck,
+    /// Feeds whose message is in only one of the committed and recorded
+    /// message sets
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub mismatched_feed_ids: Vec<String>,
+    pub messages: Vec<AccumulatorMessage>,
+}
+
+impl SlotAccumulator {
+    /// Read the accumulator a frozen bank committed to the sysvar and compare
+    /// it with the one recorded in `blockstore` for its slot.
+    ///
+    /// The committed accumulator is read rather than rebuilt, as rent
+    /// collection during freeze can store price accounts after it was built.
+    /// If the bank did not commit one, the accumulator is rebuilt from the
+    /// price accounts stored in the slot.
+    pub fn new(bank: &Bank, blockstore: &Blockstore) -> Result<Self, BlockstoreError> {
+        let committed = bank
+            .get_accumulator()
+            .filter(|accumulator| accumulator.slot == bank.slot())
+            .unwrap_or_else(|| bank.compute_accumulator());
+        let recorded = blockstore.get_accumulator(bank.slot())?;
+        let check = match &recorded {
+            None => AccumulatorCheck::NotRecorded,
+            Some(recorded) if *recorded == committed => AccumulatorCheck::Matches,
+            Some(_) => AccumulatorCheck::Mismatch,
+        };
+        let tree = MerkleAccumulator::<Sha256>::new(&committed.messages);
+        let messages = committed
+            .messages
+            .iter()
+            .enumerate()
+            .map(|(index, message)| AccumulatorMessage {
+                index,
+                feed_id: feed_id(message),
+                message: base64::encode(message),
+                proof: base64::encode(tree.prove(index).unwrap().to_bytes()),
+            })
+            .collect();
+        Ok(Self {
+            slot: bank.slot(),
+            root: committed.root.to_string(),
+            recorded_root: recorded.as_ref().map(|recorded| recorded.root.to_string()),
+            check,
+            mismatched_feed_ids: recorded
+                .map(|recorded| mismatched_feed_ids(&committed, &recorded))
+                .unwrap_or_default(),
+            messages,
+        })
+    }
+}
+
+fn feed_id(message: &[u8]) -> Option<String> {
+    PriceFeedMessage::from_bytes(message).map(|message| message.feed_id.to_string())
+}
+
+fn mismatched_feed_ids(committed: &Accumulator, recorded: &Accumulator) -> Vec<String> {
+    let committed_messages: HashSet<_> = committed.messages.iter().collect();
+    let recorded_messages: HashSet<_> = recorded.messages.iter().collect();
+    let mut feed_ids: Vec<_> = committed_messages
+        .symmetric_difference(&recorded_messages)
+        .map(|message| feed_id(message).unwrap_or_else(|| "<unknown>".to_string()))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    feed_ids.sort();
+    feed_ids
+}
+
+impl VerboseDisplay for SlotAccumulator {}
+impl QuietDisplay for SlotAccumulator {}
+
+impl Display for SlotAccumulator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Slot {}: {} messages, root {}",
+            self.slot,
+            self.messages.len(),
+            self.root
+        )?;
+        match self.check {
+            AccumulatorCheck::NotRecorded => writeln!(f, ", no accumulator recorded"),
+            AccumulatorCheck::Matches => writeln!(f, ", matches"),
+            AccumulatorCheck::Mismatch => {
+                writeln!(
+                    f,
+                    ", MISMATCH with recorded root {}",
+                    self.recorded_root.as_deref().unwrap_or_default()
+                )?;
+                for feed_id in &self.mismatched_feed_ids {
+                    writeln!(f, "  mismatched feed {feed_id}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
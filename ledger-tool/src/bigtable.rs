@@ -22,7 +22,7 @@ use {
         blockstore_options::{AccessType, ShredStorageType},
     },
     solana_sdk::{clock::Slot, pubkey::Pubkey, signature::Signature},
-    solana_storage_bigtable::CredentialType,
+    solana_storage_bigtable::{CredentialType, LedgerStorageAdapter, LocalLedgerStorage},
     solana_transaction_status::{
         BlockEncodingOptions, ConfirmedBlock, EncodeError, TransactionDetails,
         UiTransactionEncoding, VersionedConfirmedBlock,
@@ -30,7 +30,7 @@ use {
     std::{
         cmp::min,
         collections::HashSet,
-        path::Path,
+        path::{Path, PathBuf},
         process::exit,
         result::Result,
         str::FromStr,
@@ -38,14 +38,30 @@ use {
     },
 };
 
+/// Connect to the long-term ledger storage in the local database at `local_storage_path` if one
+/// is given, or to the BigTable instance of `config` otherwise. The local database is opened read
+/// only for a command that does not `write`, so that it can run next to the process uploading to
+/// it.
+async fn ledger_storage(
+    config: solana_storage_bigtable::LedgerStorageConfig,
+    local_storage_path: Option<&Path>,
+    write: bool,
+) -> solana_storage_bigtable::Result<Arc<dyn LedgerStorageAdapter>> {
+    Ok(match local_storage_path {
+        Some(path) => Arc::new(LocalLedgerStorage::open(path, !write || config.read_only)?),
+        None => Arc::new(solana_storage_bigtable::LedgerStorage::new_with_config(config).await?),
+    })
+}
+
 async fn upload(
     blockstore: Blockstore,
     starting_slot: Option<Slot>,
     ending_slot: Option<Slot>,
     force_reupload: bool,
     config: solana_storage_bigtable::LedgerStorageConfig,
+    local_storage_path: Option<&Path>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let bigtable = solana_storage_bigtable::LedgerStorage::new_with_config(config)
+    let bigtable = ledger_storage(config, local_storage_path, true)
         .await
         .map_err(|err| format!("Failed to connect to storage: {err:?}"))?;
 
@@ -90,9 +106,10 @@ async fn upload(
 async fn delete_slots(
     slots: Vec<Slot>,
     config: solana_storage_bigtable::LedgerStorageConfig,
+    local_storage_path: Option<&Path>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let dry_run = config.read_only;
-    let bigtable = solana_storage_bigtable::LedgerStorage::new_with_config(config)
+    let bigtable = ledger_storage(config, local_storage_path, true)
         .await
         .map_err(|err| format!("Failed to connect to storage: {err:?}"))?;
 
@@ -101,8 +118,9 @@ async fn delete_slots(
 
 async fn first_available_block(
     config: solana_storage_bigtable::LedgerStorageConfig,
+    local_storage_path: Option<&Path>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let bigtable = solana_storage_bigtable::LedgerStorage::new_with_config(config).await?;
+    let bigtable = ledger_storage(config, local_storage_path, false).await?;
     match bigtable.get_first_available_block().await? {
         Some(block) => println!("{block}"),
         None => println!("No blocks available"),
@@ -115,8 +133,9 @@ async fn block(
     slot: Slot,
     output_format: OutputFormat,
     config: solana_storage_bigtable::LedgerStorageConfig,
+    local_storage_path: Option<&Path>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let bigtable = solana_storage_bigtable::LedgerStorage::new_with_config(config)
+    let bigtable = ledger_storage(config, local_storage_path, false)
         .await
         .map_err(|err| format!("Failed to connect to storage: {err:?}"))?;
 
@@ -148,8 +167,9 @@ async fn blocks(
     starting_slot: Slot,
     limit: usize,
     config: solana_storage_bigtable::LedgerStorageConfig,
+    local_storage_path: Option<&Path>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let bigtable = solana_storage_bigtable::LedgerStorage::new_with_config(config)
+    let bigtable = ledger_storage(config, local_storage_path, false)
         .await
         .map_err(|err| format!("Failed to connect to storage: {err:?}"))?;
 
@@ -206,8 +226,9 @@ async fn confirm(
     verbose: bool,
     output_format: OutputFormat,
     config: solana_storage_bigtable::LedgerStorageConfig,
+    local_storage_path: Option<&Path>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let bigtable = solana_storage_bigtable::LedgerStorage::new_with_config(config)
+    let bigtable = ledger_storage(config, local_storage_path, false)
         .await
         .map_err(|err| format!("Failed to connect to storage: {err:?}"))?;
 
@@ -258,8 +279,9 @@ pub async fn transaction_history(
     show_transactions: bool,
     query_chunk_size: usize,
     config: solana_storage_bigtable::LedgerStorageConfig,
+    local_storage_path: Option<&Path>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let bigtable = solana_storage_bigtable::LedgerStorage::new_with_config(config).await?;
+    let bigtable = ledger_storage(config, local_storage_path, false).await?;
 
     let mut loaded_block: Option<(Slot, ConfirmedBlock)> = None;
     while limit > 0 {
@@ -600,7 +622,7 @@ impl BigTableSubCommand for App<'_, '_> {
     fn bigtable_subcommand(self) -> Self {
         self.subcommand(
             SubCommand::with_name("bigtable")
-                .about("Ledger data on a BigTable instance")
+                .about("Ledger data on a BigTable instance, or in a local long-term storage")
                 .setting(AppSettings::InferSubcommands)
                 .setting(AppSettings::SubcommandRequiredElseHelp)
                 .arg(
@@ -621,6 +643,16 @@ impl BigTableSubCommand for App<'_, '_> {
                         .default_value(solana_storage_bigtable::DEFAULT_APP_PROFILE_ID)
                         .help("Bigtable application profile id to use in requests")
                 )
+                .arg(
+                    Arg::with_name("local_storage")
+                        .global(true)
+                        .long("local-storage")
+                        .takes_value(true)
+                        .value_name("DIR")
+                        .help("Use the long-term ledger storage kept in a local database in this \
+                               directory instead of a BigTable instance. \
+                               Not supported by compare-blocks and copy")
+                )
                 .subcommand(
                     SubCommand::with_name("upload")
                         .about("Upload the ledger to BigTable")
@@ -1009,6 +1041,11 @@ pub fn bigtable_process_command(
         "rpc_bigtable_app_profile_id",
         solana_storage_bigtable::DEFAULT_APP_PROFILE_ID,
     );
+    let local_storage_path = matches
+        .value_of("local_storage")
+        .or_else(|| sub_matches.and_then(|sub_matches| sub_matches.value_of("local_storage")))
+        .map(PathBuf::from);
+    let local_storage_path = local_storage_path.as_deref();
 
     let future = match (subcommand, sub_matches) {
         ("upload", Some(arg_matches)) => {
@@ -1034,6 +1071,7 @@ pub fn bigtable_process_command(
                 ending_slot,
                 force_reupload,
                 config,
+                local_storage_path,
             ))
         }
         ("delete-slots", Some(arg_matches)) => {
@@ -1044,7 +1082,7 @@ pub fn bigtable_process_command(
                 app_profile_id,
                 ..solana_storage_bigtable::LedgerStorageConfig::default()
             };
-            runtime.block_on(delete_slots(slots, config))
+            runtime.block_on(delete_slots(slots, config, local_storage_path))
         }
         ("first-available-block", Some(_arg_matches)) => {
             let config = solana_storage_bigtable::LedgerStorageConfig {
@@ -1053,7 +1091,7 @@ pub fn bigtable_process_command(
                 app_profile_id,
                 ..solana_storage_bigtable::LedgerStorageConfig::default()
             };
-            runtime.block_on(first_available_block(config))
+            runtime.block_on(first_available_block(config, local_storage_path))
         }
         ("block", Some(arg_matches)) => {
             let slot = value_t_or_exit!(arg_matches, "slot", Slot);
@@ -1063,7 +1101,7 @@ pub fn bigtable_process_command(
                 app_profile_id,
                 ..solana_storage_bigtable::LedgerStorageConfig::default()
             };
-            runtime.block_on(block(slot, output_format, config))
+            runtime.block_on(block(slot, output_format, config, local_storage_path))
         }
         ("blocks", Some(arg_matches)) => {
             let starting_slot = value_t_or_exit!(arg_matches, "starting_slot", Slot);
@@ -1075,7 +1113,7 @@ pub fn bigtable_process_command(
                 ..solana_storage_bigtable::LedgerStorageConfig::default()
             };
 
-            runtime.block_on(blocks(starting_slot, limit, config))
+            runtime.block_on(blocks(starting_slot, limit, config, local_storage_path))
         }
         ("compare-blocks", Some(arg_matches)) => {
             let starting_slot = value_t_or_exit!(arg_matches, "starting_slot", Slot);
@@ -1120,7 +1158,13 @@ pub fn bigtable_process_command(
                 ..solana_storage_bigtable::LedgerStorageConfig::default()
             };
 
-            runtime.block_on(confirm(&signature, verbose, output_format, config))
+            runtime.block_on(confirm(
+                &signature,
+                verbose,
+                output_format,
+                config,
+                local_storage_path,
+            ))
         }
         ("transaction-history", Some(arg_matches)) => {
             let address = pubkey_of(arg_matches, "address").unwrap();
@@ -1149,6 +1193,7 @@ pub fn bigtable_process_command(
                 show_transactions,
                 query_chunk_size,
                 config,
+                local_storage_path,
             ))
         }
         ("copy", Some(arg_matches)) => runtime.block_on(copy(CopyArgs::process(arg_matches))),
@@ -0,0 +1,417 @@
+//! Portable archives of a range of rooted slots, written by the `export`
+//! subcommand and read by `import`.
+//!
+//! An archive is a zstd compressed stream of frames, each a little-endian
+//! `u64` length followed by that many bytes. The first frame is the JSON
+//! encoded [`ArchiveHeader`], which names the format and its version and lists
+//! the archived slots. Every following frame is the bincode encoding of an
+//! [`ArchivedSlot`], in the order of the header.
+//!
+//! The block of an archived slot is the protobuf encoding of a
+//! `solana.storage.ConfirmedBlock.ConfirmedBlock`, as in the long-term ledger
+//! storage, and holds the transactions with their status metadata, the
+//! rewards, the block time and the block height. The entries of the slot are
+//! kept as the number of hashes, the hash and the number of transactions of
+//! each entry, which is enough to rebuild them from the transactions of the
+//! block and to verify them against the previous blockhash on import.
+//!
+//! The shreds of the leaders are not archived. On import, the rebuilt entries
+//! are shredded again and signed with a throwaway key, so the imported shreds
+//! don't verify against the leader schedule and can't be served to the
+//! cluster. The imported slots are only meant to be read from the ledger.
+
+use {
+    log::*,
+    prost::Message,
+    serde::{Deserialize, Serialize},
+    solana_entry::entry::{Entry, EntrySlice},
+    solana_ledger::{
+        blockstore::Blockstore,
+        shred::{ProcessShredsStats, ReedSolomonCache, Shredder},
+    },
+    solana_sdk::{
+        clock::Slot,
+        hash::Hash,
+        message::{
+            v0::{LoadedAddresses, LoadedMessage},
+            VersionedMessage,
+        },
+        signature::Keypair,
+    },
+    solana_storage_proto::convert::generated,
+    solana_transaction_status::{
+        extract_memos::extract_and_fmt_memos, ConfirmedBlock, VersionedConfirmedBlock,
+    },
+    std::{
+        cmp::max,
+        error::Error,
+        fs::File,
+        io::{self, BufReader, BufWriter, Read, Write},
+        path::Path,
+        str::FromStr,
+    },
+};
+
+pub const ARCHIVE_FORMAT: &str = "pythnet-ledger-archive";
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// zstd compression level of the archives
+const ARCHIVE_COMPRESSION_LEVEL: i32 = 6;
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveHeader {
+    /// Always `ARCHIVE_FORMAT`
+    pub format: String,
+    /// Version of the layout of the archive
+    pub version: u32,
+    /// Version of the ledger-tool that wrote the archive
+    pub created_by: String,
+    /// Hash of the genesis config of the ledger the slots were exported from
+    pub genesis_hash: String,
+    /// Archived slots, in the order of the following frames
+    pub slots: Vec<Slot>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ArchivedEntry {
+    pub num_hashes: u64,
+    pub hash: Hash,
+    pub num_transactions: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ArchivedSlot {
+    pub slot: Slot,
+    pub entries: Vec<ArchivedEntry>,
+    /// Protobuf encoded `generated::ConfirmedBlock`
+    pub block: Vec<u8>,
+}
+
+fn write_frame<W: Write>(writer: &mut W, data: &[u8]) -> io::Result<()> {
+    writer.write_all(&(data.len() as u64).to_le_bytes())?;
+    writer.write_all(data)
+}
+
+fn read_frame<R: Read>(reader: &mut R) -> io::Result<Vec<u8>> {
+    let mut len = [0; 8];
+    reader.read_exact(&mut len)?;
+    let len = u64::from_le_bytes(len);
+    let mut data = vec![];
+    reader.take(len).read_to_end(&mut data)?;
+    if data.len() as u64 != len {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "archive frame is truncated",
+        ));
+    }
+    Ok(data)
+}
+
+/// Write the rooted slots of `blockstore` from `starting_slot` to
+/// `ending_slot` to a new archive at `path`. The archive starts at the first
+/// root at or after both `starting_slot` and the first available block of
+/// `blockstore`, which need not be a root itself.
+pub fn export_slots(
+    blockstore: &Blockstore,
+    genesis_hash: &Hash,
+    starting_slot: Slot,
+    ending_slot: Slot,
+    path: &Path,
+) -> Result<ArchiveHeader, Box<dyn Error>> {
+    let first_slot = max(starting_slot, blockstore.get_first_available_block()?);
+    // walking the roots column from `first_slot` starts at the first root at or after it
+    let slots: Vec<_> = blockstore
+        .rooted_slot_iterator(first_slot)?
+        .take_while(|slot| *slot <= ending_slot)
+        .collect();
+    let header = ArchiveHeader {
+        format: ARCHIVE_FORMAT.to_string(),
+        version: ARCHIVE_VERSION,
+        created_by: solana_version::version!().to_string(),
+        genesis_hash: genesis_hash.to_string(),
+        slots,
+    };
+
+    let file = File::create(path)
+        .map_err(|err| format!("Unable to create {}: {}", path.display(), err))?;
+    let mut encoder =
+        zstd::stream::write::Encoder::new(BufWriter::new(file), ARCHIVE_COMPRESSION_LEVEL)?;
+    encoder.include_checksum(true)?;
+    write_frame(&mut encoder, &serde_json::to_vec(&header)?)?;
+    for slot in &header.slots {
+        let slot = *slot;
+        let block = blockstore.get_rooted_block(slot, false).map_err(|err| {
+            format!(
+                "Unable to read block {slot}: {err:?}. Exported slots need their transaction \
+                 status metadata, which is only recorded with --enable-rpc-transaction-history"
+            )
+        })?;
+        let entries = blockstore
+            .get_slot_entries(slot, 0)?
+            .into_iter()
+            .map(|entry| ArchivedEntry {
+                num_hashes: entry.num_hashes,
+                hash: entry.hash,
+                num_transactions: entry.transactions.len() as u64,
+            })
+            .collect();
+        let archived_slot = ArchivedSlot {
+            slot,
+            entries,
+            block: generated::ConfirmedBlock::from(block).encode_to_vec(),
+        };
+        write_frame(&mut encoder, &bincode::serialize(&archived_slot)?)?;
+        debug!("exported slot {}", slot);
+    }
+    encoder.finish()?.flush()?;
+    Ok(header)
+}
+
+/// Read the header of the archive, failing if it isn't an archive of a
+/// supported version.
+fn read_header<R: Read>(reader: &mut R) -> Result<ArchiveHeader, Box<dyn Error>> {
+    let header: ArchiveHeader = serde_json::from_slice(&read_frame(reader)?)
+        .map_err(|err| format!("Not a ledger archive: {err}"))?;
+    if header.format != ARCHIVE_FORMAT {
+        return Err(format!("Unsupported archive format {}", header.format).into());
+    }
+    if header.version != ARCHIVE_VERSION {
+        return Err(format!(
+            "Unsupported archive version {}, only version {} is supported",
+            header.version, ARCHIVE_VERSION
+        )
+        .into());
+    }
+    Ok(header)
+}
+
+fn open_archive(path: &Path) -> Result<impl Read, Box<dyn Error>> {
+    let file =
+        File::open(path).map_err(|err| format!("Unable to open {}: {}", path.display(), err))?;
+    Ok(zstd::stream::read::Decoder::new(BufReader::new(file))?)
+}
+
+/// Read the header of the archive at `path`.
+pub fn archive_header(path: &Path) -> Result<ArchiveHeader, Box<dyn Error>> {
+    read_header(&mut open_archive(path)?)
+}
+
+fn is_writable(
+    message: &VersionedMessage,
+    index: usize,
+    loaded_addresses: &LoadedAddresses,
+) -> bool {
+    match message {
+        VersionedMessage::Legacy(message) => message.is_writable(index),
+        VersionedMessage::V0(message) => {
+            LoadedMessage::new_borrowed(message, loaded_addresses).is_writable(index)
+        }
+    }
+}
+
+/// Rebuild the entries of an archived slot, verifying their proof of history
+/// from `start_hash` unless it is unknown.
+fn rebuild_entries(
+    archived_slot: &ArchivedSlot,
+    block: &VersionedConfirmedBlock,
+    start_hash: Option<Hash>,
+) -> Result<Vec<Entry>, String> {
+    let slot = archived_slot.slot;
+    let num_transactions: u64 = archived_slot
+        .entries
+        .iter()
+        .map(|entry| entry.num_transactions)
+        .sum();
+    if num_transactions != block.transactions.len() as u64 {
+        return Err(format!(
+            "Slot {slot}: the entries hold {num_transactions} transactions, the block {}",
+            block.transactions.len()
+        ));
+    }
+    let mut transactions = block
+        .transactions
+        .iter()
+        .map(|transaction| transaction.transaction.clone());
+    let entries: Vec<_> = archived_slot
+        .entries
+        .iter()
+        .map(|entry| Entry {
+            num_hashes: entry.num_hashes,
+            hash: entry.hash,
+            transactions: transactions
+                .by_ref()
+                .take(entry.num_transactions as usize)
+                .collect(),
+        })
+        .collect();
+    if entries.last().map(|entry| entry.hash.to_string()) != Some(block.blockhash.clone()) {
+        return Err(format!(
+            "Slot {slot}: the last entry doesn't match blockhash {}",
+            block.blockhash
+        ));
+    }
+    match start_hash {
+        Some(start_hash) => {
+            if !entries.verify(&start_hash) {
+                return Err(format!(
+                    "Slot {slot}: the entries fail proof of history verification from {start_hash}"
+                ));
+            }
+        }
+        None => warn!(
+            "Slot {}: previous blockhash is unknown, proof of history not verified",
+            slot
+        ),
+    }
+    Ok(entries)
+}
+
+/// The blockhash of `slot` in `blockstore`, if the slot is complete there.
+fn ledger_blockhash(blockstore: &Blockstore, slot: Slot) -> Result<Option<Hash>, Box<dyn Error>> {
+    if !blockstore.is_full(slot) {
+        return Ok(None);
+    }
+    Ok(blockstore
+        .get_slot_entries(slot, 0)?
+        .last()
+        .map(|entry| entry.hash))
+}
+
+/// Write the slots of the archive at `path` to `blockstore` and mark them
+/// rooted. Every archived slot must chain to the previous one, by its parent
+/// slot and its previous blockhash, and the first one to the ledger if its
+/// parent is there. Slots the ledger already has shreds of are not written
+/// over. The shreds of the slots are rebuilt from their entries and signed
+/// with a throwaway key, see the module documentation.
+pub fn import_slots(blockstore: &Blockstore, path: &Path) -> Result<ArchiveHeader, Box<dyn Error>> {
+    let mut reader = open_archive(path)?;
+    let header = read_header(&mut reader)?;
+    let genesis_hash = Hash::from_str(&header.genesis_hash)?;
+    for slot in &header.slots {
+        if blockstore
+            .meta(*slot)?
+            .map(|meta| meta.received > 0)
+            .unwrap_or(false)
+        {
+            return Err(format!("Slot {slot} is already in the ledger").into());
+        }
+    }
+    // The shreds of the slots are rebuilt rather than archived, so they are
+    // signed with a new key instead of the key of the leader
+    let keypair = Keypair::new();
+    let reed_solomon_cache = ReedSolomonCache::default();
+    // The slot and blockhash of the previous archived slot
+    let mut previous_archived_slot: Option<(Slot, Hash)> = None;
+    for expected_slot in &header.slots {
+        let archived_slot: ArchivedSlot = bincode::deserialize(&read_frame(&mut reader)?)?;
+        let slot = archived_slot.slot;
+        if slot != *expected_slot {
+            return Err(format!("Expected slot {expected_slot} in archive, found {slot}").into());
+        }
+        let block =
+            ConfirmedBlock::try_from(generated::ConfirmedBlock::decode(&*archived_slot.block)?)?;
+        let block = VersionedConfirmedBlock::try_from(block)?;
+
+        let previous_blockhash = Hash::from_str(&block.previous_blockhash)?;
+        if let Some((previous_slot, previous_slot_blockhash)) = previous_archived_slot {
+            if block.parent_slot != previous_slot {
+                return Err(format!(
+                    "Slot {slot}: parent slot {} is not the previous archived slot {previous_slot}",
+                    block.parent_slot
+                )
+                .into());
+            }
+            if previous_blockhash != previous_slot_blockhash {
+                return Err(format!(
+                    "Slot {slot}: previous blockhash {previous_blockhash} is not the blockhash \
+                     {previous_slot_blockhash} of the previous archived slot {previous_slot}"
+                )
+                .into());
+            }
+        } else if slot > 0 {
+            if let Some(root) = blockstore
+                .rooted_slot_iterator(block.parent_slot + 1)?
+                .next()
+                .filter(|root| *root < slot)
+            {
+                return Err(format!(
+                    "Slot {slot}: parent slot {} skips the rooted slot {root} of the ledger",
+                    block.parent_slot
+                )
+                .into());
+            }
+            if let Some(parent_blockhash) = ledger_blockhash(blockstore, block.parent_slot)?
+                .filter(|_| previous_blockhash != Hash::default())
+            {
+                if previous_blockhash != parent_blockhash {
+                    return Err(format!(
+                        "Slot {slot}: previous blockhash {previous_blockhash} is not the \
+                         blockhash {parent_blockhash} of its parent slot {} in the ledger",
+                        block.parent_slot
+                    )
+                    .into());
+                }
+            }
+        }
+        let start_hash = if slot == 0 {
+            Some(genesis_hash)
+        } else {
+            Some(previous_blockhash)
+                .filter(|previous_blockhash| *previous_blockhash != Hash::default())
+        };
+        let entries = rebuild_entries(&archived_slot, &block, start_hash)?;
+        previous_archived_slot = Some((slot, Hash::from_str(&block.blockhash)?));
+        let shredder = Shredder::new(slot, block.parent_slot, 0, 0)
+            .map_err(|err| format!("Slot {slot}: {err:?}"))?;
+        let (data_shreds, _coding_shreds) = shredder.entries_to_shreds(
+            &keypair,
+            &entries,
+            true,  // is_last_in_slot
+            0,     // next_shred_index
+            0,     // next_code_index
+            false, // merkle_variant
+            &reed_solomon_cache,
+            &mut ProcessShredsStats::default(),
+        );
+        blockstore.insert_shreds(data_shreds, None, false)?;
+
+        for transaction in &block.transactions {
+            let signature = transaction.transaction.signatures[0];
+            let mut writable_keys = vec![];
+            let mut readonly_keys = vec![];
+            let account_keys = transaction.account_keys();
+            for (index, key) in account_keys.iter().enumerate() {
+                if is_writable(
+                    &transaction.transaction.message,
+                    index,
+                    &transaction.meta.loaded_addresses,
+                ) {
+                    writable_keys.push(key);
+                } else {
+                    readonly_keys.push(key);
+                }
+            }
+            if let Some(memos) = extract_and_fmt_memos(transaction) {
+                blockstore.write_transaction_memos(&signature, memos)?;
+            }
+            blockstore.write_transaction_status(
+                slot,
+                signature,
+                writable_keys,
+                readonly_keys,
+                transaction.meta.clone(),
+            )?;
+        }
+        blockstore.write_rewards(slot, block.rewards)?;
+        if let Some(block_time) = block.block_time {
+            blockstore.cache_block_time(slot, block_time)?;
+        }
+        if let Some(block_height) = block.block_height {
+            blockstore.cache_block_height(slot, block_height)?;
+        }
+        debug!("imported slot {}", slot);
+    }
+    blockstore.set_roots(header.slots.iter())?;
+    Ok(header)
+}
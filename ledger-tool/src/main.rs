@@ -1,6 +1,6 @@
 #![allow(clippy::integer_arithmetic)]
 use {
-    crate::{bigtable::*, ledger_path::*, output::*},
+    crate::{accumulator::*, bigtable::*, ledger_archive::*, ledger_path::*, output::*},
     chrono::{DateTime, Utc},
     clap::{
         crate_description, crate_name, value_t, value_t_or_exit, values_t_or_exit, App,
@@ -72,7 +72,7 @@ use {
         clock::{Epoch, Slot},
         feature::{self, Feature},
         feature_set::{self, FeatureSet},
-        genesis_config::{ClusterType, GenesisConfig},
+        genesis_config::{ClusterType, GenesisConfig, DEFAULT_GENESIS_FILE},
         hash::Hash,
         inflation::Inflation,
         native_token::{lamports_to_sol, sol_to_lamports, Sol},
@@ -104,7 +104,9 @@ use {
     },
 };
 
+mod accumulator;
 mod bigtable;
+mod ledger_archive;
 mod ledger_path;
 mod output;
 
@@ -835,6 +837,9 @@ fn analyze_storage(database: &Database) {
     analyze_column::<BlockHeight>(database, "BlockHeight");
     analyze_column::<ProgramCosts>(database, "ProgramCosts");
     analyze_column::<OptimisticSlots>(database, "OptimisticSlots");
+    analyze_column::<AccumulatorMessages>(database, "AccumulatorMessages");
+    analyze_column::<AccountHistory>(database, "AccountHistory");
+    analyze_column::<AccountHistoryIndex>(database, "AccountHistoryIndex");
 }
 
 /// Open blockstore with temporary primary access to allow necessary,
@@ -973,6 +978,13 @@ fn raw_key_to_slot(key: &[u8], column_name: &str) -> Option<Slot> {
         cf::OptimisticSlots::NAME => {
             Some(cf::OptimisticSlots::slot(cf::OptimisticSlots::index(key)))
         }
+        cf::AccumulatorMessages::NAME => Some(cf::AccumulatorMessages::slot(
+            cf::AccumulatorMessages::index(key),
+        )),
+        cf::AccountHistory::NAME => Some(cf::AccountHistory::slot(cf::AccountHistory::index(key))),
+        cf::AccountHistoryIndex::NAME => Some(cf::AccountHistoryIndex::slot(
+            cf::AccountHistoryIndex::index(key),
+        )),
         &_ => None,
     }
 }
@@ -1622,6 +1634,37 @@ fn main() {
                     .help("Target db"),
             )
         )
+        .subcommand(
+            SubCommand::with_name("export")
+            .about("Export the rooted slots of a range to a portable archive of their blocks, \
+                    entries, transaction status metadata, rewards and block times")
+            .arg(&starting_slot_arg)
+            .arg(&ending_slot_arg)
+            .arg(&max_genesis_archive_unpacked_size_arg)
+            .arg(
+                Arg::with_name("archive")
+                    .index(1)
+                    .value_name("ARCHIVE")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Path of the archive to write"),
+            )
+        )
+        .subcommand(
+            SubCommand::with_name("import")
+            .about("Import the slots of an archive written by `export` into the ledger, \
+                    verifying their entries, and mark them rooted. The shreds of the imported \
+                    slots are signed with a throwaway key rather than by their leader")
+            .arg(&max_genesis_archive_unpacked_size_arg)
+            .arg(
+                Arg::with_name("archive")
+                    .index(1)
+                    .value_name("ARCHIVE")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Path of the archive to import"),
+            )
+        )
         .subcommand(
             SubCommand::with_name("slot")
             .about("Print the contents of one or more slots")
@@ -1753,6 +1796,24 @@ fn main() {
             .arg(&max_genesis_archive_unpacked_size_arg)
             .arg(&halt_at_slot_arg)
         )
+        .subcommand(
+            SubCommand::with_name("accumulator")
+            .about("Replay a range of slots and verify the accumulator of each against the \
+                    accumulator recorded in the ledger")
+            .arg(&no_snapshot_arg)
+            .arg(&account_paths_arg)
+            .arg(&hard_forks_arg)
+            .arg(&max_genesis_archive_unpacked_size_arg)
+            .arg(&starting_slot_arg)
+            .arg(&ending_slot_arg)
+            .arg(
+                Arg::with_name("output_json")
+                    .long("output-json")
+                    .value_name("FILE")
+                    .takes_value(true)
+                    .help("Write the messages of every slot and their proofs to FILE as JSON"),
+            )
+        )
         .subcommand(
             SubCommand::with_name("bounds")
             .about(
@@ -2337,6 +2398,77 @@ fn main() {
                     }
                 }
             }
+            ("export", Some(arg_matches)) => {
+                let starting_slot = value_t_or_exit!(arg_matches, "starting_slot", Slot);
+                let ending_slot = value_t!(arg_matches, "ending_slot", Slot).unwrap_or(Slot::MAX);
+                let archive_path = PathBuf::from(value_t_or_exit!(arg_matches, "archive", String));
+                let genesis_hash = open_genesis_config_by(&ledger_path, arg_matches).hash();
+                let blockstore = open_blockstore(
+                    &ledger_path,
+                    AccessType::Secondary,
+                    wal_recovery_mode,
+                    &shred_storage_type,
+                    force_update_to_open,
+                );
+                match export_slots(
+                    &blockstore,
+                    &genesis_hash,
+                    starting_slot,
+                    ending_slot,
+                    &archive_path,
+                ) {
+                    Ok(header) => match (header.slots.first(), header.slots.last()) {
+                        (Some(first_slot), Some(last_slot)) => println!(
+                            "Exported {} slots from {} to {} to {}",
+                            header.slots.len(),
+                            first_slot,
+                            last_slot,
+                            archive_path.display()
+                        ),
+                        _ => eprintln!("No rooted slot found from slot {starting_slot}"),
+                    },
+                    Err(err) => {
+                        eprintln!("Failed to export slots: {err}");
+                        exit(1);
+                    }
+                }
+            }
+            ("import", Some(arg_matches)) => {
+                let archive_path = PathBuf::from(value_t_or_exit!(arg_matches, "archive", String));
+                let header = archive_header(&archive_path).unwrap_or_else(|err| {
+                    eprintln!("Failed to read {}: {}", archive_path.display(), err);
+                    exit(1);
+                });
+                if ledger_path.join(DEFAULT_GENESIS_FILE).exists() {
+                    let genesis_hash = open_genesis_config_by(&ledger_path, arg_matches).hash();
+                    if genesis_hash.to_string() != header.genesis_hash {
+                        eprintln!(
+                            "The archive was exported from a ledger with genesis hash {}, \
+                             which doesn't match the genesis hash {} of this ledger",
+                            header.genesis_hash, genesis_hash
+                        );
+                        exit(1);
+                    }
+                }
+                let blockstore = open_blockstore(
+                    &ledger_path,
+                    AccessType::Primary,
+                    wal_recovery_mode,
+                    &shred_storage_type,
+                    force_update_to_open,
+                );
+                match import_slots(&blockstore, &archive_path) {
+                    Ok(header) => println!(
+                        "Imported {} slots exported by {}",
+                        header.slots.len(),
+                        header.created_by
+                    ),
+                    Err(err) => {
+                        eprintln!("Failed to import slots: {err}");
+                        exit(1);
+                    }
+                }
+            }
             ("genesis", Some(arg_matches)) => {
                 let genesis_config = open_genesis_config_by(&ledger_path, arg_matches);
                 let print_accounts = arg_matches.is_present("accounts");
@@ -2517,6 +2649,115 @@ fn main() {
                     }
                 }
             }
+            ("accumulator", Some(arg_matches)) => {
+                let starting_slot = value_t_or_exit!(arg_matches, "starting_slot", Slot);
+                let ending_slot = value_t!(arg_matches, "ending_slot", Slot).ok();
+                let output_json = value_t!(arg_matches, "output_json", String).ok();
+
+                let genesis_config = open_genesis_config_by(&ledger_path, arg_matches);
+                let blockstore = Arc::new(open_blockstore(
+                    &ledger_path,
+                    AccessType::Secondary,
+                    wal_recovery_mode,
+                    &shred_storage_type,
+                    force_update_to_open,
+                ));
+                let slot_accumulators: Arc<RwLock<Vec<SlotAccumulator>>> = Arc::default();
+                let slot_callback = {
+                    let slot_accumulators = slot_accumulators.clone();
+                    let blockstore = blockstore.clone();
+                    Arc::new(move |bank: &Bank| {
+                        if bank.slot() >= starting_slot
+                            && ending_slot.map_or(true, |ending_slot| bank.slot() <= ending_slot)
+                        {
+                            let slot_accumulator = SlotAccumulator::new(bank, &blockstore)
+                                .unwrap_or_else(|err| {
+                                    eprintln!(
+                                        "Failed to read the accumulator of slot {}: {err:?}",
+                                        bank.slot()
+                                    );
+                                    exit(1);
+                                });
+                            slot_accumulators.write().unwrap().push(slot_accumulator);
+                        }
+                    })
+                };
+                let process_options = ProcessOptions {
+                    new_hard_forks: hardforks_of(arg_matches, "hard_forks"),
+                    halt_at_slot: ending_slot,
+                    poh_verify: false,
+                    slot_callback: Some(slot_callback),
+                    ..ProcessOptions::default()
+                };
+                if let Err(err) = load_bank_forks(
+                    arg_matches,
+                    &genesis_config,
+                    &blockstore,
+                    process_options,
+                    snapshot_archive_path,
+                    incremental_snapshot_archive_path,
+                ) {
+                    eprintln!("Failed to load ledger: {err:?}");
+                    exit(1);
+                }
+
+                let mut slot_accumulators =
+                    std::mem::take(&mut *slot_accumulators.write().unwrap());
+                slot_accumulators.sort_by_key(|slot_accumulator| slot_accumulator.slot);
+                match slot_accumulators.first() {
+                    None => eprintln!(
+                        "No slot from {starting_slot} was replayed. Slots older than the \
+                         snapshot the ledger is loaded from are not replayed, see --no-snapshot"
+                    ),
+                    Some(first) if first.slot > starting_slot => eprintln!(
+                        "Replay started at slot {}. Slots older than the snapshot the ledger \
+                         is loaded from are not replayed, see --no-snapshot",
+                        first.slot
+                    ),
+                    Some(_) => {}
+                }
+                for slot_accumulator in &slot_accumulators {
+                    print!("{slot_accumulator}");
+                }
+
+                if let Some(output_json) = output_json {
+                    let result = File::create(&output_json)
+                        .map_err(|err| err.to_string())
+                        .and_then(|file| {
+                            serde_json::to_writer_pretty(file, &slot_accumulators)
+                                .map_err(|err| err.to_string())
+                        });
+                    match result {
+                        Ok(()) => println!("Wrote {output_json}"),
+                        Err(err) => {
+                            eprintln!("Unable to write {output_json}: {err}");
+                            exit(1);
+                        }
+                    }
+                }
+
+                let count = |check| {
+                    slot_accumulators
+                        .iter()
+                        .filter(|slot_accumulator| slot_accumulator.check == check)
+                        .count()
+                };
+                let not_recorded = count(AccumulatorCheck::NotRecorded);
+                if not_recorded > 0 {
+                    eprintln!(
+                        "{not_recorded} of {} slots have no recorded accumulator to verify against",
+                        slot_accumulators.len()
+                    );
+                }
+                let mismatches = count(AccumulatorCheck::Mismatch);
+                if mismatches > 0 {
+                    eprintln!(
+                        "{mismatches} of {} slots do not match their recorded accumulator",
+                        slot_accumulators.len()
+                    );
+                    exit(1);
+                }
+            }
             ("slot", Some(arg_matches)) => {
                 let slots = values_t_or_exit!(arg_matches, "slots", Slot);
                 let allow_dead_slots = arg_matches.is_present("allow_dead_slots");
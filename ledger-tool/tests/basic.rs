@@ -2,14 +2,26 @@ use {
     assert_cmd::prelude::*,
     solana_entry::entry,
     solana_ledger::{
-        blockstore, blockstore::Blockstore, blockstore_options::ShredStorageType,
-        create_new_tmp_ledger, create_new_tmp_ledger_fifo, genesis_utils::create_genesis_config,
+        blockstore,
+        blockstore::{Blockstore, PurgeType},
+        blockstore_options::ShredStorageType,
+        create_new_tmp_ledger, create_new_tmp_ledger_fifo,
+        genesis_utils::create_genesis_config,
         get_tmp_ledger_path_auto_delete,
     },
-    solana_sdk::hash::Hash,
+    solana_sdk::{
+        accumulator::Accumulator,
+        clock::Slot,
+        genesis_config::DEFAULT_GENESIS_FILE,
+        hash::Hash,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        system_transaction,
+    },
+    solana_transaction_status::{Reward, RewardType, TransactionStatusMeta},
     std::{
         fs,
-        path::Path,
+        path::{Path, PathBuf},
         process::{Command, Output},
     },
 };
@@ -135,3 +147,227 @@ fn copy_test() {
     ledger_tool_copy_test("fifo", "level");
     ledger_tool_copy_test("fifo", "fifo");
 }
+
+const EXPORT_ENDING_SLOT: Slot = 5;
+
+/// Create a ledger with a transfer in each slot up to `EXPORT_ENDING_SLOT`, along with the
+/// transaction status metadata `export` needs, and mark `roots` rooted.
+fn create_ledger_to_export(roots: &[Slot]) -> PathBuf {
+    let genesis_config = create_genesis_config(100).genesis_config;
+    let (ledger_path, mut blockhash) = create_new_tmp_ledger!(&genesis_config);
+    let blockstore = Blockstore::open(&ledger_path).unwrap();
+    let payer = Keypair::new();
+    for slot in 1..=EXPORT_ENDING_SLOT {
+        let transaction =
+            system_transaction::transfer(&payer, &Pubkey::new_unique(), slot, blockhash);
+        let mut entries = vec![entry::next_entry_mut(
+            &mut blockhash,
+            1,
+            vec![transaction.clone()],
+        )];
+        entries.extend(entry::create_ticks(
+            genesis_config.ticks_per_slot,
+            0,
+            blockhash,
+        ));
+        blockhash = entries.last().unwrap().hash;
+        let shreds = blockstore::entries_to_test_shreds(
+            &entries,
+            slot,
+            slot - 1,
+            true,
+            0,
+            /*merkle_variant:*/ true,
+        );
+        blockstore.insert_shreds(shreds, None, false).unwrap();
+        blockstore
+            .write_transaction_status(
+                slot,
+                transaction.signatures[0],
+                vec![&payer.pubkey()],
+                vec![],
+                TransactionStatusMeta {
+                    fee: 5_000,
+                    ..TransactionStatusMeta::default()
+                },
+            )
+            .unwrap();
+        blockstore
+            .write_rewards(
+                slot,
+                vec![Reward {
+                    pubkey: payer.pubkey().to_string(),
+                    lamports: 10,
+                    post_balance: 110,
+                    reward_type: Some(RewardType::Fee),
+                    commission: None,
+                }],
+            )
+            .unwrap();
+        blockstore.cache_block_time(slot, slot as i64).unwrap();
+    }
+    blockstore.set_roots(roots.iter()).unwrap();
+    drop(blockstore);
+    ledger_path
+}
+
+#[test]
+fn export_import_test() {
+    let ledger_path = create_ledger_to_export(&(0..=EXPORT_ENDING_SLOT).collect::<Vec<_>>());
+
+    let archive_dir = get_tmp_ledger_path_auto_delete!();
+    let archive = archive_dir.path().join("ledger.archive");
+    let archive = archive.to_str().unwrap();
+    let ledger_path = ledger_path.to_str().unwrap();
+    let output = run_ledger_tool(&[
+        "-l",
+        ledger_path,
+        "export",
+        "--ending-slot",
+        &(EXPORT_ENDING_SLOT - 1).to_string(),
+        archive,
+    ]);
+    assert!(output.status.success());
+
+    let target_ledger_path = get_tmp_ledger_path_auto_delete!();
+    let target_ledger_path = target_ledger_path.path().to_str().unwrap();
+    let output = run_ledger_tool(&["-l", target_ledger_path, "import", archive]);
+    assert!(output.status.success());
+
+    let source = Blockstore::open(Path::new(ledger_path)).unwrap();
+    let target = Blockstore::open(Path::new(target_ledger_path)).unwrap();
+    for slot in 0..EXPORT_ENDING_SLOT {
+        assert!(target.is_root(slot));
+        assert_eq!(
+            target.get_rooted_block(slot, false).unwrap(),
+            source.get_rooted_block(slot, false).unwrap()
+        );
+    }
+    assert_eq!(target.get_block_time(1).unwrap(), Some(1));
+    assert!(!target.is_root(EXPORT_ENDING_SLOT));
+    assert!(target.meta(EXPORT_ENDING_SLOT).unwrap().is_none());
+    drop(target);
+
+    // imported slots are not written over
+    let output = run_ledger_tool(&["-l", target_ledger_path, "import", archive]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Slot 0 is already in the ledger"));
+
+    // the archive can't be imported into the ledger of another genesis
+    let other_genesis_config = create_genesis_config(200).genesis_config;
+    let (other_ledger_path, _blockhash) = create_new_tmp_ledger!(&other_genesis_config);
+    let output = run_ledger_tool(&["-l", other_ledger_path.to_str().unwrap(), "import", archive]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn export_import_chain_test() {
+    // slot 1 is not rooted, so slot 2 doesn't chain to the root before it
+    let ledger_path = create_ledger_to_export(&[0, 2, 3]);
+    let ledger_path = ledger_path.to_str().unwrap();
+    let archive_dir = get_tmp_ledger_path_auto_delete!();
+    let archive = archive_dir.path().join("ledger.archive");
+    let archive = archive.to_str().unwrap();
+
+    // the export starts at the first root after a starting slot that is not rooted
+    let output = run_ledger_tool(&["-l", ledger_path, "export", "--starting-slot", "1", archive]);
+    assert!(output.status.success());
+    let target_ledger_path = get_tmp_ledger_path_auto_delete!();
+    let target_ledger_path = target_ledger_path.path().to_str().unwrap();
+    let output = run_ledger_tool(&["-l", target_ledger_path, "import", archive]);
+    assert!(output.status.success());
+    let target = Blockstore::open(Path::new(target_ledger_path)).unwrap();
+    assert!(!target.is_root(1));
+    assert!(target.is_root(2));
+    assert!(target.is_root(3));
+    drop(target);
+
+    // slots that don't chain to the previous archived slot are not imported
+    let output = run_ledger_tool(&["-l", ledger_path, "export", archive]);
+    assert!(output.status.success());
+    let target_ledger_path = get_tmp_ledger_path_auto_delete!();
+    let target_ledger_path = target_ledger_path.path().to_str().unwrap();
+    let output = run_ledger_tool(&["-l", target_ledger_path, "import", archive]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("Slot 2: parent slot 1 is not the previous archived slot 0"));
+}
+
+#[test]
+fn import_into_ledger_test() {
+    let ledger_path = create_ledger_to_export(&(0..=EXPORT_ENDING_SLOT).collect::<Vec<_>>());
+    let archive_dir = get_tmp_ledger_path_auto_delete!();
+    let archive = archive_dir.path().join("ledger.archive");
+    let archive = archive.to_str().unwrap();
+    let output = run_ledger_tool(&[
+        "-l",
+        ledger_path.to_str().unwrap(),
+        "export",
+        "--starting-slot",
+        "2",
+        archive,
+    ]);
+    assert!(output.status.success());
+    let purge_archived_slots = |ledger_path: &Path| {
+        Blockstore::open(ledger_path)
+            .unwrap()
+            .purge_slots(2, EXPORT_ENDING_SLOT, PurgeType::Exact);
+    };
+
+    // the first archived slot doesn't chain to slot 1 of another ledger
+    let other_ledger_path = create_ledger_to_export(&[0, 1]);
+    purge_archived_slots(&other_ledger_path);
+    fs::remove_file(other_ledger_path.join(DEFAULT_GENESIS_FILE)).unwrap();
+    let output = run_ledger_tool(&["-l", other_ledger_path.to_str().unwrap(), "import", archive]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("of its parent slot 1 in the ledger"));
+
+    // but it chains to slot 1 of the ledger it was exported from
+    purge_archived_slots(&ledger_path);
+    let output = run_ledger_tool(&["-l", ledger_path.to_str().unwrap(), "import", archive]);
+    assert!(output.status.success());
+    let target = Blockstore::open(&ledger_path).unwrap();
+    assert!((2..=EXPORT_ENDING_SLOT).all(|slot| target.is_root(slot)));
+}
+
+#[test]
+fn accumulator_test() {
+    let genesis_config = create_genesis_config(100).genesis_config;
+    let (ledger_path, _blockhash) = create_new_tmp_ledger!(&genesis_config);
+    let record_accumulator = |accumulator: &Accumulator| {
+        let blockstore = Blockstore::open(&ledger_path).unwrap();
+        blockstore.cache_accumulator(0, accumulator).unwrap();
+    };
+    let verify_accumulators = || {
+        run_ledger_tool(&[
+            "-l",
+            ledger_path.to_str().unwrap(),
+            "accumulator",
+            "--no-snapshot",
+            "--ending-slot",
+            "0",
+        ])
+    };
+
+    // a slot without a recorded accumulator is reported, not verified
+    let output = verify_accumulators();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("no accumulator recorded"));
+
+    // genesis has no price account, so slot 0 commits to no message
+    let mut accumulator = Accumulator {
+        slot: 0,
+        root: Hash::default(),
+        messages: vec![],
+    };
+    record_accumulator(&accumulator);
+    let output = verify_accumulators();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("matches"));
+
+    accumulator.messages.push(vec![1, 2, 3]);
+    record_accumulator(&accumulator);
+    let output = verify_accumulators();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("MISMATCH"));
+}
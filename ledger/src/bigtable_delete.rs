@@ -1,10 +1,16 @@
-use {log::*, solana_measure::measure::Measure, solana_sdk::clock::Slot, std::result::Result};
+use {
+    log::*,
+    solana_measure::measure::Measure,
+    solana_sdk::clock::Slot,
+    solana_storage_bigtable::LedgerStorageAdapter,
+    std::{result::Result, sync::Arc},
+};
 
 // Attempt to delete this many blocks in parallel
 const NUM_BLOCKS_TO_DELETE_IN_PARALLEL: usize = 32;
 
 pub async fn delete_confirmed_blocks(
-    bigtable: solana_storage_bigtable::LedgerStorage,
+    bigtable: Arc<dyn LedgerStorageAdapter>,
     blocks_to_delete: Vec<Slot>,
     dry_run: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
@@ -4,6 +4,7 @@ use {
     log::*,
     solana_measure::measure::Measure,
     solana_sdk::clock::Slot,
+    solana_storage_bigtable::LedgerStorageAdapter,
     std::{
         cmp::{max, min},
         collections::HashSet,
@@ -41,12 +42,12 @@ struct BlockstoreLoadStats {
     pub elapsed: Duration,
 }
 
-/// Uploads a range of blocks from a Blockstore to bigtable LedgerStorage
+/// Uploads a range of blocks from a Blockstore to long-term ledger storage
 /// Returns the Slot of the last block checked. If no blocks in the range `[staring_slot,
 /// ending_slot]` are found in Blockstore, this value is equal to `ending_slot`.
 pub async fn upload_confirmed_blocks(
     blockstore: Arc<Blockstore>,
-    bigtable: solana_storage_bigtable::LedgerStorage,
+    bigtable: Arc<dyn LedgerStorageAdapter>,
     starting_slot: Slot,
     ending_slot: Slot,
     config: ConfirmedBlockUploadConfig,
@@ -181,7 +182,16 @@ pub async fn upload_confirmed_blocks(
                                 let _ = match blockstore.get_rooted_block(slot, true) {
                                     Ok(confirmed_block) => {
                                         num_blocks_read += 1;
-                                        sender.send((slot, Some(confirmed_block)))
+                                        let accumulator = blockstore
+                                            .get_accumulator(slot)
+                                            .unwrap_or_else(|err| {
+                                                warn!(
+                                                    "Failed to load accumulator from slot {}: {:?}",
+                                                    slot, err
+                                                );
+                                                None
+                                            });
+                                        sender.send((slot, Some((confirmed_block, accumulator))))
                                     }
                                     Err(err) => {
                                         warn!(
@@ -224,9 +234,14 @@ pub async fn upload_confirmed_blocks(
                 num_blocks -= 1;
                 None
             }
-            Some(confirmed_block) => {
+            Some((confirmed_block, accumulator)) => {
                 let bt = bigtable.clone();
                 Some(tokio::spawn(async move {
+                    // Upload the accumulator first, so that it is in place by the time the block
+                    // is visible and the slot is no longer uploaded again.
+                    if let Some(accumulator) = accumulator {
+                        bt.upload_accumulator(slot, accumulator).await?;
+                    }
                     bt.upload_confirmed_block(slot, confirmed_block).await
                 }))
             }
@@ -41,6 +41,8 @@ use {
     solana_rayon_threadlimit::get_max_thread_count,
     solana_runtime::hardened_unpack::{unpack_genesis_archive, MAX_GENESIS_ARCHIVE_UNPACKED_SIZE},
     solana_sdk::{
+        account::Account,
+        accumulator::Accumulator,
         clock::{Slot, UnixTimestamp, DEFAULT_TICKS_PER_SECOND, MS_PER_TICK},
         genesis_config::{GenesisConfig, DEFAULT_GENESIS_ARCHIVE, DEFAULT_GENESIS_FILE},
         hash::Hash,
@@ -173,6 +175,9 @@ pub struct Blockstore {
     program_costs_cf: LedgerColumn<cf::ProgramCosts>,
     bank_hash_cf: LedgerColumn<cf::BankHash>,
     optimistic_slots_cf: LedgerColumn<cf::OptimisticSlots>,
+    accumulator_messages_cf: LedgerColumn<cf::AccumulatorMessages>,
+    account_history_cf: LedgerColumn<cf::AccountHistory>,
+    account_history_index_cf: LedgerColumn<cf::AccountHistoryIndex>,
     last_root: RwLock<Slot>,
     insert_shreds_lock: Mutex<()>,
     new_shreds_signals: Mutex<Vec<Sender<bool>>>,
@@ -281,6 +286,9 @@ impl Blockstore {
         let program_costs_cf = db.column();
         let bank_hash_cf = db.column();
         let optimistic_slots_cf = db.column();
+        let accumulator_messages_cf = db.column();
+        let account_history_cf = db.column();
+        let account_history_index_cf = db.column();
 
         let db = Arc::new(db);
 
@@ -333,6 +341,9 @@ impl Blockstore {
             program_costs_cf,
             bank_hash_cf,
             optimistic_slots_cf,
+            accumulator_messages_cf,
+            account_history_cf,
+            account_history_index_cf,
             new_shreds_signals: Mutex::default(),
             completed_slots_senders: Mutex::default(),
             shred_timing_point_sender: None,
@@ -691,6 +702,9 @@ impl Blockstore {
         self.program_costs_cf.submit_rocksdb_cf_metrics();
         self.bank_hash_cf.submit_rocksdb_cf_metrics();
         self.optimistic_slots_cf.submit_rocksdb_cf_metrics();
+        self.accumulator_messages_cf.submit_rocksdb_cf_metrics();
+        self.account_history_cf.submit_rocksdb_cf_metrics();
+        self.account_history_index_cf.submit_rocksdb_cf_metrics();
     }
 
     fn try_shred_recovery(
@@ -1902,6 +1916,129 @@ impl Blockstore {
         self.block_height_cf.put(slot, &block_height)
     }
 
+    /// Returns the accumulator committed in `slot`, holding the messages behind its root.
+    pub fn get_accumulator(&self, slot: Slot) -> Result<Option<Accumulator>> {
+        datapoint_info!("blockstore-rpc-api", ("method", "get_accumulator", String));
+        let _lock = self.check_lowest_cleanup_slot(slot)?;
+        self.accumulator_messages_cf.get(slot)
+    }
+
+    /// Like [`Blockstore::get_accumulator`], but fails for slots that are not
+    /// rooted, as the accumulator of every frozen bank is cached, including
+    /// banks of abandoned forks.
+    pub fn get_rooted_accumulator(&self, slot: Slot) -> Result<Option<Accumulator>> {
+        datapoint_info!(
+            "blockstore-rpc-api",
+            ("method", "get_rooted_accumulator", String)
+        );
+        let _lock = self.check_lowest_cleanup_slot(slot)?;
+
+        if self.is_root(slot) {
+            return self.accumulator_messages_cf.get(slot);
+        }
+        Err(BlockstoreError::SlotNotRooted)
+    }
+
+    pub fn cache_accumulator(&self, slot: Slot, accumulator: &Accumulator) -> Result<()> {
+        self.accumulator_messages_cf.put(slot, accumulator)
+    }
+
+    /// Records the states `accounts` were left in by `slot`, for lookups with
+    /// [`Blockstore::get_account_at_slot`]. `created` lists the accounts that did not
+    /// exist before `slot`.
+    pub fn write_account_history(
+        &self,
+        slot: Slot,
+        accounts: &[(Pubkey, Account)],
+        created: &[Pubkey],
+    ) -> Result<()> {
+        let mut write_batch = self.db.batch()?;
+        for (pubkey, account) in accounts {
+            write_batch.put::<cf::AccountHistory>((*pubkey, slot), account)?;
+        }
+        let index = AccountHistoryIndexMeta {
+            accounts: accounts.iter().map(|(pubkey, _)| *pubkey).collect(),
+            created: created.to_vec(),
+        };
+        write_batch.put::<cf::AccountHistoryIndex>(slot, &index)?;
+        self.db.write(write_batch)
+    }
+
+    /// Returns the lowest slot that recorded account states, if any. The account history
+    /// can't tell the state of an account before it.
+    pub fn lowest_account_history_slot(&self) -> Result<Option<Slot>> {
+        Ok(self
+            .account_history_index_cf
+            .iter(IteratorMode::Start)?
+            .next()
+            .map(|(slot, _)| slot))
+    }
+
+    /// Returns whether any state of `pubkey` has been recorded.
+    pub fn has_account_history(&self, pubkey: &Pubkey) -> Result<bool> {
+        Ok(self
+            .account_history_cf
+            .iter(IteratorMode::From((*pubkey, 0), IteratorDirection::Forward))?
+            .next()
+            .map(|((address, _), _)| address == *pubkey)
+            .unwrap_or(false))
+    }
+
+    /// Returns whether the first state of `pubkey` recorded in a rooted slot after `slot` is
+    /// the one it was created with, in which case the account did not exist at `slot`.
+    pub fn is_account_created_after(&self, pubkey: &Pubkey, slot: Slot) -> Result<bool> {
+        let iterator = self.account_history_cf.iter(IteratorMode::From(
+            (*pubkey, slot.saturating_add(1)),
+            IteratorDirection::Forward,
+        ))?;
+        for ((address, account_slot), _) in iterator {
+            if address != *pubkey {
+                break;
+            }
+            if self.is_root(account_slot) {
+                return Ok(self
+                    .account_history_index_cf
+                    .get(account_slot)?
+                    .map(|index| index.created.contains(pubkey))
+                    .unwrap_or(false));
+            }
+        }
+        Ok(false)
+    }
+
+    /// Returns the newest state of `pubkey` recorded in a rooted slot at or before `slot`,
+    /// along with the slot it was recorded in. States are only recorded in the slots that
+    /// wrote them, so `None` is returned for an account that was not written since its
+    /// history has been recorded.
+    pub fn get_account_at_slot(
+        &self,
+        pubkey: &Pubkey,
+        slot: Slot,
+    ) -> Result<Option<(Slot, Account)>> {
+        datapoint_info!(
+            "blockstore-rpc-api",
+            ("method", "get_account_at_slot", String)
+        );
+        let lowest_cleanup_slot = self.check_lowest_cleanup_slot(slot)?;
+        let iterator = self.account_history_cf.iter(IteratorMode::From(
+            (*pubkey, slot),
+            IteratorDirection::Reverse,
+        ))?;
+        for ((address, account_slot), data) in iterator {
+            // states recorded in cleaned up slots may already be purged, so they can't tell
+            // the state of the account any more
+            if address != *pubkey
+                || (*lowest_cleanup_slot > 0 && account_slot <= *lowest_cleanup_slot)
+            {
+                break;
+            }
+            if self.is_root(account_slot) {
+                return Ok(Some((account_slot, deserialize(&data)?)));
+            }
+        }
+        Ok(None)
+    }
+
     /// The first complete block that is available in the Blockstore ledger
     pub fn get_first_available_block(&self) -> Result<Slot> {
         let mut root_iterator = self.rooted_slot_iterator(self.lowest_slot_with_genesis())?;
@@ -9783,4 +9920,101 @@ pub mod tests {
             assert_eq!(read_cost, *cost_table.get(&read_key).unwrap());
         }
     }
+
+    #[test]
+    fn test_accumulator_messages() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+
+        let accumulators: Vec<_> = (1..4)
+            .map(|slot| Accumulator {
+                slot,
+                root: hash::hash(&[slot as u8]),
+                messages: vec![vec![slot as u8; 3]],
+            })
+            .collect();
+        for accumulator in &accumulators {
+            blockstore
+                .cache_accumulator(accumulator.slot, accumulator)
+                .unwrap();
+        }
+        assert_eq!(blockstore.get_accumulator(0).unwrap(), None);
+        assert_eq!(
+            blockstore.get_accumulator(2).unwrap().as_ref(),
+            Some(&accumulators[1])
+        );
+        assert_matches!(
+            blockstore.get_rooted_accumulator(2),
+            Err(BlockstoreError::SlotNotRooted)
+        );
+        blockstore.set_roots([2].iter()).unwrap();
+        assert_eq!(
+            blockstore.get_rooted_accumulator(2).unwrap().as_ref(),
+            Some(&accumulators[1])
+        );
+
+        blockstore.run_purge(0, 2, PurgeType::Exact).unwrap();
+        assert_eq!(blockstore.get_accumulator(2).unwrap(), None);
+        assert_eq!(
+            blockstore.get_accumulator(3).unwrap().as_ref(),
+            Some(&accumulators[2])
+        );
+    }
+
+    #[test]
+    fn test_account_history() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+
+        let pubkey = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let account = |lamports| Account {
+            lamports,
+            ..Account::default()
+        };
+        for slot in [1, 3, 5] {
+            let created = if slot == 1 { vec![pubkey] } else { vec![] };
+            blockstore
+                .write_account_history(slot, &[(pubkey, account(slot))], &created)
+                .unwrap();
+        }
+        blockstore.set_roots([1, 3].iter()).unwrap();
+
+        assert!(blockstore.has_account_history(&pubkey).unwrap());
+        assert!(!blockstore.has_account_history(&other).unwrap());
+        assert_eq!(blockstore.lowest_account_history_slot().unwrap(), Some(1));
+        assert_eq!(blockstore.get_account_at_slot(&pubkey, 0).unwrap(), None);
+        assert_eq!(
+            blockstore.get_account_at_slot(&pubkey, 2).unwrap(),
+            Some((1, account(1)))
+        );
+        assert_eq!(
+            blockstore.get_account_at_slot(&pubkey, 3).unwrap(),
+            Some((3, account(3)))
+        );
+        // slot 5 is not rooted
+        assert_eq!(
+            blockstore.get_account_at_slot(&pubkey, 6).unwrap(),
+            Some((3, account(3)))
+        );
+        assert_eq!(blockstore.get_account_at_slot(&other, 6).unwrap(), None);
+        assert!(blockstore.is_account_created_after(&pubkey, 0).unwrap());
+        assert!(!blockstore.is_account_created_after(&pubkey, 1).unwrap());
+        assert!(!blockstore.is_account_created_after(&other, 0).unwrap());
+
+        blockstore.run_purge(3, 3, PurgeType::Exact).unwrap();
+        blockstore.set_roots([5].iter()).unwrap();
+        assert_eq!(
+            blockstore.get_account_at_slot(&pubkey, 4).unwrap(),
+            Some((1, account(1)))
+        );
+        assert_eq!(
+            blockstore.get_account_at_slot(&pubkey, 6).unwrap(),
+            Some((5, account(5)))
+        );
+
+        blockstore.run_purge(0, 1, PurgeType::Exact).unwrap();
+        assert_eq!(blockstore.lowest_account_history_slot().unwrap(), Some(5));
+        assert_eq!(blockstore.get_account_at_slot(&pubkey, 4).unwrap(), None);
+    }
 }
@@ -214,12 +214,21 @@ impl Blockstore {
             & self
                 .db
                 .delete_range_cf::<cf::OptimisticSlots>(&mut write_batch, from_slot, to_slot)
+                .is_ok()
+            & self
+                .db
+                .delete_range_cf::<cf::AccumulatorMessages>(&mut write_batch, from_slot, to_slot)
+                .is_ok()
+            & self
+                .db
+                .delete_range_cf::<cf::AccountHistoryIndex>(&mut write_batch, from_slot, to_slot)
                 .is_ok();
         let mut w_active_transaction_status_index =
             self.active_transaction_status_index.write().unwrap();
         match purge_type {
             PurgeType::Exact => {
                 self.purge_special_columns_exact(&mut write_batch, from_slot, to_slot)?;
+                self.purge_account_history_exact(&mut write_batch, from_slot, to_slot)?;
             }
             PurgeType::PrimaryIndex => {
                 self.purge_special_columns_with_primary_index(
@@ -337,6 +346,14 @@ impl Blockstore {
                 .db
                 .delete_file_in_range_cf::<cf::OptimisticSlots>(from_slot, to_slot)
                 .is_ok()
+            & self
+                .db
+                .delete_file_in_range_cf::<cf::AccumulatorMessages>(from_slot, to_slot)
+                .is_ok()
+            & self
+                .db
+                .delete_file_in_range_cf::<cf::AccountHistoryIndex>(from_slot, to_slot)
+                .is_ok()
     }
 
     /// Purges special columns (using a non-Slot primary-index) exactly, by
@@ -390,6 +407,31 @@ impl Blockstore {
         Ok(())
     }
 
+    /// Purges the account history recorded in \[`from_slot`, `to_slot`\].
+    /// `cf::AccountHistory` is keyed by address first, so the addresses
+    /// recorded by each slot are looked up in `cf::AccountHistoryIndex`.
+    /// Otherwise, the column relies on the compaction filter.
+    fn purge_account_history_exact(
+        &self,
+        batch: &mut WriteBatch,
+        from_slot: Slot,
+        to_slot: Slot,
+    ) -> Result<()> {
+        let iterator = self
+            .account_history_index_cf
+            .iter(IteratorMode::From(from_slot, IteratorDirection::Forward))?;
+        for (slot, index) in iterator {
+            if slot > to_slot {
+                break;
+            }
+            let index: AccountHistoryIndexMeta = deserialize(&index)?;
+            for pubkey in index.accounts {
+                batch.delete::<cf::AccountHistory>((pubkey, slot))?;
+            }
+        }
+        Ok(())
+    }
+
     /// Purges special columns (using a non-Slot primary-index) by range. Purge
     /// occurs if frozen primary index has a max-slot less than the highest slot
     /// being purged.
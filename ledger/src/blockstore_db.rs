@@ -94,6 +94,12 @@ const BLOCK_HEIGHT_CF: &str = "block_height";
 const PROGRAM_COSTS_CF: &str = "program_costs";
 /// Column family for optimistic slots
 const OPTIMISTIC_SLOTS_CF: &str = "optimistic_slots";
+/// Column family for Accumulator Messages
+const ACCUMULATOR_MESSAGES_CF: &str = "accumulator_messages";
+/// Column family for Account History
+const ACCOUNT_HISTORY_CF: &str = "account_history";
+/// Column family for Account History Index
+const ACCOUNT_HISTORY_INDEX_CF: &str = "account_history_index";
 
 #[derive(Error, Debug)]
 pub enum BlockstoreError {
@@ -314,6 +320,28 @@ pub mod columns {
     /// * value type: [`blockstore_meta::OptimisticSlotMetaVersioned`]
     pub struct OptimisticSlots;
 
+    #[derive(Debug)]
+    /// The accumulator messages column
+    ///
+    /// * index type: `u64` (see [`SlotColumn`])
+    /// * value type: [`solana_sdk::accumulator::Accumulator`]
+    pub struct AccumulatorMessages;
+
+    #[derive(Debug)]
+    /// The account history column
+    ///
+    /// * index type: `(`[`Pubkey`]`, `[`Slot`]`)`
+    /// * value type: [`solana_sdk::account::Account`]
+    pub struct AccountHistory;
+
+    #[derive(Debug)]
+    /// The account history index column, listing the addresses whose states
+    /// were recorded in [`AccountHistory`] by each slot
+    ///
+    /// * index type: `u64` (see [`SlotColumn`])
+    /// * value type: [`blockstore_meta::AccountHistoryIndexMeta`]
+    pub struct AccountHistoryIndex;
+
     // When adding a new column ...
     // - Add struct below and implement `Column` and `ColumnName` traits
     // - Add descriptor in Rocks::cf_descriptors() and name in Rocks::columns()
@@ -440,6 +468,9 @@ impl Rocks {
             new_cf_descriptor::<BlockHeight>(options, oldest_slot),
             new_cf_descriptor::<ProgramCosts>(options, oldest_slot),
             new_cf_descriptor::<OptimisticSlots>(options, oldest_slot),
+            new_cf_descriptor::<AccumulatorMessages>(options, oldest_slot),
+            new_cf_descriptor::<AccountHistory>(options, oldest_slot),
+            new_cf_descriptor::<AccountHistoryIndex>(options, oldest_slot),
         ]
     }
 
@@ -467,6 +498,9 @@ impl Rocks {
             BlockHeight::NAME,
             ProgramCosts::NAME,
             OptimisticSlots::NAME,
+            AccumulatorMessages::NAME,
+            AccountHistory::NAME,
+            AccountHistoryIndex::NAME,
         ]
     }
 
@@ -1019,6 +1053,57 @@ impl TypedColumn for columns::OptimisticSlots {
     type Type = blockstore_meta::OptimisticSlotMetaVersioned;
 }
 
+impl SlotColumn for columns::AccumulatorMessages {}
+impl ColumnName for columns::AccumulatorMessages {
+    const NAME: &'static str = ACCUMULATOR_MESSAGES_CF;
+}
+impl TypedColumn for columns::AccumulatorMessages {
+    type Type = solana_sdk::accumulator::Accumulator;
+}
+
+impl Column for columns::AccountHistory {
+    type Index = (Pubkey, Slot);
+
+    fn key((pubkey, slot): (Pubkey, Slot)) -> Vec<u8> {
+        let mut key = vec![0; 32 + 8]; // size_of Pubkey + size_of Slot
+        key[0..32].clone_from_slice(&pubkey.as_ref()[0..32]);
+        BigEndian::write_u64(&mut key[32..40], slot);
+        key
+    }
+
+    fn index(key: &[u8]) -> (Pubkey, Slot) {
+        let pubkey = Pubkey::try_from(&key[0..32]).unwrap();
+        let slot = BigEndian::read_u64(&key[32..40]);
+        (pubkey, slot)
+    }
+
+    fn primary_index(_index: Self::Index) -> u64 {
+        unimplemented!()
+    }
+
+    fn slot(index: Self::Index) -> Slot {
+        index.1
+    }
+
+    fn as_index(_index: u64) -> Self::Index {
+        (Pubkey::default(), 0)
+    }
+}
+impl ColumnName for columns::AccountHistory {
+    const NAME: &'static str = ACCOUNT_HISTORY_CF;
+}
+impl TypedColumn for columns::AccountHistory {
+    type Type = solana_sdk::account::Account;
+}
+
+impl SlotColumn for columns::AccountHistoryIndex {}
+impl ColumnName for columns::AccountHistoryIndex {
+    const NAME: &'static str = ACCOUNT_HISTORY_INDEX_CF;
+}
+impl TypedColumn for columns::AccountHistoryIndex {
+    type Type = blockstore_meta::AccountHistoryIndexMeta;
+}
+
 #[derive(Debug)]
 pub struct Database {
     backend: Arc<Rocks>,
@@ -5,6 +5,7 @@ use {
     solana_sdk::{
         clock::{Slot, UnixTimestamp},
         hash::Hash,
+        pubkey::Pubkey,
     },
     std::{
         collections::BTreeSet,
@@ -418,6 +419,15 @@ pub struct PerfSampleV2 {
     pub num_non_vote_transactions: u64,
 }
 
+/// The account states a slot recorded in the account history.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct AccountHistoryIndexMeta {
+    /// Addresses of the recorded states
+    pub accounts: Vec<Pubkey>,
+    /// Addresses, among `accounts`, of the accounts that did not exist before the slot
+    pub created: Vec<Pubkey>,
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
 pub struct ProgramCost {
     pub cost: u64,
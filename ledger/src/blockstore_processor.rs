@@ -598,6 +598,8 @@ pub struct ProcessOptions {
     pub full_leader_cache: bool,
     pub halt_at_slot: Option<Slot>,
     pub entry_callback: Option<ProcessCallback>,
+    /// Called with every bank replayed from the blockstore once it is frozen
+    pub slot_callback: Option<ProcessCallback>,
     pub new_hard_forks: Option<Vec<Slot>>,
     pub debug_keys: Option<Arc<HashSet<Pubkey>>>,
     pub account_indexes: AccountSecondaryIndexes,
@@ -1237,6 +1239,9 @@ fn process_bank_0(
     )
     .expect("Failed to process bank 0 from ledger. Did you forget to provide a snapshot?");
     bank0.freeze();
+    if let Some(slot_callback) = &opts.slot_callback {
+        slot_callback(bank0);
+    }
     if blockstore.is_primary_access() {
         blockstore.insert_bank_hash(bank0.slot(), bank0.hash(), false);
     }
@@ -1624,6 +1629,9 @@ fn process_single_slot(
     })?;
 
     bank.freeze(); // all banks handled by this routine are created from complete slots
+    if let Some(slot_callback) = &opts.slot_callback {
+        slot_callback(bank);
+    }
     if blockstore.is_primary_access() {
         blockstore.insert_bank_hash(bank.slot(), bank.hash(), false);
     }
@@ -2702,6 +2710,33 @@ pub mod tests {
         assert_eq!(*callback_counter.write().unwrap(), 2);
     }
 
+    #[test]
+    fn test_process_ledger_options_slot_callback() {
+        let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(100);
+        let ticks_per_slot = genesis_config.ticks_per_slot;
+        let (ledger_path, blockhash) = create_new_tmp_ledger_auto_delete!(&genesis_config);
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+        let blockhash =
+            fill_blockstore_slot_with_ticks(&blockstore, ticks_per_slot, 1, 0, blockhash);
+        fill_blockstore_slot_with_ticks(&blockstore, ticks_per_slot, 2, 1, blockhash);
+
+        let frozen_slots: Arc<RwLock<Vec<Slot>>> = Arc::default();
+        let slot_callback = {
+            let frozen_slots = frozen_slots.clone();
+            Arc::new(move |bank: &Bank| {
+                assert!(bank.is_frozen());
+                frozen_slots.write().unwrap().push(bank.slot());
+            })
+        };
+
+        let opts = ProcessOptions {
+            slot_callback: Some(slot_callback),
+            ..ProcessOptions::default()
+        };
+        test_process_blockstore(&genesis_config, &blockstore, &opts, &Arc::default());
+        assert_eq!(*frozen_slots.read().unwrap(), vec![0, 1, 2]);
+    }
+
     #[test]
     fn test_process_entries_tick() {
         let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(1000);
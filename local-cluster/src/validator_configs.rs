@@ -54,6 +54,7 @@ pub fn safe_clone_config(config: &ValidatorConfig) -> ValidatorConfig {
         accounts_db_skip_shrink: config.accounts_db_skip_shrink,
         tpu_coalesce_ms: config.tpu_coalesce_ms,
         staked_nodes_overrides: config.staked_nodes_overrides.clone(),
+        publisher_lane: config.publisher_lane.clone(),
         validator_exit: Arc::new(RwLock::new(Exit::default())),
         poh_hashes_per_batch: config.poh_hashes_per_batch,
         process_ledger_before_services: config.process_ledger_before_services,
@@ -64,6 +65,7 @@ pub fn safe_clone_config(config: &ValidatorConfig) -> ValidatorConfig {
         ledger_column_options: config.ledger_column_options.clone(),
         runtime_config: config.runtime_config.clone(),
         replay_slots_concurrently: config.replay_slots_concurrently,
+        leader_bank_callback: config.leader_bank_callback.clone(),
         banking_trace_dir_byte_limit: config.banking_trace_dir_byte_limit,
     }
 }
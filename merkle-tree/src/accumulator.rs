@@ -0,0 +1,384 @@
+//! A Merkle tree generic over its hash function, with owned inclusion proofs.
+//!
+//! [`MerkleAccumulator`] builds the same tree shape as [`MerkleTree`]: leaves
+//! and intermediate nodes are domain separated, and the last node of an odd
+//! level is paired with itself. With the [`Sha256`] hasher both produce the
+//! same root.
+//!
+//! Unlike the borrowed [`Proof`] returned by [`MerkleTree::find_path`], a
+//! [`MerkleProof`] owns its hashes and has a compact wire encoding, so it can
+//! be handed to other chains. The [`Keccak160`] hasher truncates keccak-256 to
+//! 20 bytes, which keeps proofs small and is cheap to verify on the EVM.
+//!
+//! [`MerkleTree`]: crate::MerkleTree
+//! [`MerkleTree::find_path`]: crate::MerkleTree::find_path
+//! [`Proof`]: crate::merkle_tree::Proof
+
+use {
+    crate::{
+        merkle_tree::{INTERMEDIATE_PREFIX, LEAF_PREFIX},
+        MerkleTree,
+    },
+    serde::{de::DeserializeOwned, Deserialize, Serialize},
+    solana_program::{hash, keccak},
+    std::{collections::HashMap, fmt::Debug},
+    thiserror::Error,
+};
+
+/// A hash function used to build a [`MerkleAccumulator`].
+pub trait Hasher: Clone + Debug + PartialEq + Eq {
+    type Hash: AsRef<[u8]>
+        + for<'a> TryFrom<&'a [u8]>
+        + Copy
+        + Debug
+        + Default
+        + Eq
+        + Serialize
+        + DeserializeOwned;
+
+    /// Length in bytes of `Self::Hash`.
+    const HASH_LEN: usize;
+
+    fn hashv(data: &[&[u8]]) -> Self::Hash;
+
+    fn hash_leaf(leaf: &[u8]) -> Self::Hash {
+        Self::hashv(&[LEAF_PREFIX, leaf])
+    }
+
+    fn hash_intermediate(left: &Self::Hash, right: &Self::Hash) -> Self::Hash {
+        Self::hashv(&[INTERMEDIATE_PREFIX, left.as_ref(), right.as_ref()])
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sha256;
+
+impl Hasher for Sha256 {
+    type Hash = [u8; 32];
+    const HASH_LEN: usize = 32;
+
+    fn hashv(data: &[&[u8]]) -> Self::Hash {
+        hash::hashv(data).to_bytes()
+    }
+}
+
+/// Keccak-256 truncated to its first 20 bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Keccak160;
+
+impl Hasher for Keccak160 {
+    type Hash = [u8; 20];
+    const HASH_LEN: usize = 20;
+
+    fn hashv(data: &[&[u8]]) -> Self::Hash {
+        let mut hash = [0u8; 20];
+        hash.copy_from_slice(&keccak::hashv(data).to_bytes()[..20]);
+        hash
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ProofError {
+    #[error("proof is truncated")]
+    Truncated,
+    #[error("proof has {0} trailing bytes")]
+    TrailingBytes(usize),
+}
+
+/// An owned proof that a leaf is included in a [`MerkleAccumulator`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(bound = "")]
+pub struct MerkleProof<H: Hasher> {
+    /// Position of the proven leaf among all leaves.
+    pub index: u32,
+    /// Sibling hashes from the leaf's level up to, but excluding, the root.
+    pub path: Vec<H::Hash>,
+}
+
+impl<H: Hasher> MerkleProof<H> {
+    /// Encodes the proof as a big-endian `u32` leaf index, a `u8` path length
+    /// and the concatenated sibling hashes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(5 + self.path.len() * H::HASH_LEN);
+        bytes.extend_from_slice(&self.index.to_be_bytes());
+        bytes.push(self.path.len() as u8);
+        for hash in &self.path {
+            bytes.extend_from_slice(hash.as_ref());
+        }
+        bytes
+    }
+
+    /// Decodes a proof produced by [`MerkleProof::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProofError> {
+        let index = bytes
+            .get(..4)
+            .and_then(|index| index.try_into().ok())
+            .map(u32::from_be_bytes)
+            .ok_or(ProofError::Truncated)?;
+        let path_len = *bytes.get(4).ok_or(ProofError::Truncated)? as usize;
+        let path_bytes = &bytes[5..];
+        let expected_len = path_len * H::HASH_LEN;
+        if path_bytes.len() < expected_len {
+            return Err(ProofError::Truncated);
+        }
+        if path_bytes.len() > expected_len {
+            return Err(ProofError::TrailingBytes(path_bytes.len() - expected_len));
+        }
+        let path = path_bytes
+            .chunks_exact(H::HASH_LEN)
+            .map(|hash| H::Hash::try_from(hash).map_err(|_| ProofError::Truncated))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { index, path })
+    }
+
+    /// Returns `true` if `leaf` is included at `self.index` in the tree of
+    /// `leaf_count` leaves with the given `root`.
+    ///
+    /// The last leaf of an odd level is paired with itself, so the path of the
+    /// last leaf also leads to the root from the index past it: `leaf_count`
+    /// must come from a trusted source, such as the accumulator sysvar.
+    pub fn verify(&self, root: &H::Hash, leaf_count: usize, leaf: &[u8]) -> bool {
+        Self::verify_batch(root, leaf_count, [(leaf, self)])
+    }
+
+    /// Verifies many leaves against the same root.
+    ///
+    /// Nodes already proven to lead to `root` are remembered, so the shared
+    /// upper levels of the tree are only hashed once.
+    pub fn verify_batch<'a>(
+        root: &H::Hash,
+        leaf_count: usize,
+        proofs: impl IntoIterator<Item = (&'a [u8], &'a Self)>,
+    ) -> bool
+    where
+        H: 'a,
+    {
+        // (level, index) -> hash of nodes known to be on a path to `root`
+        let mut verified = HashMap::new();
+        proofs.into_iter().all(|(leaf, proof)| {
+            if proof.index as usize >= leaf_count {
+                return false;
+            }
+            let mut node = H::hash_leaf(leaf);
+            let mut index = proof.index as usize;
+            let mut computed = Vec::with_capacity(proof.path.len());
+            for (level, sibling) in proof.path.iter().enumerate() {
+                if let Some(known) = verified.get(&(level, index)) {
+                    return if *known == node {
+                        verified.extend(computed);
+                        true
+                    } else {
+                        false
+                    };
+                }
+                computed.push(((level, index), node));
+                node = if index % 2 == 0 {
+                    H::hash_intermediate(&node, sibling)
+                } else {
+                    H::hash_intermediate(sibling, &node)
+                };
+                index /= 2;
+            }
+            if index == 0 && node == *root {
+                verified.extend(computed);
+                true
+            } else {
+                false
+            }
+        })
+    }
+}
+
+/// A Merkle tree over arbitrary byte strings using the hash function `H`.
+#[derive(Clone, Debug)]
+pub struct MerkleAccumulator<H: Hasher> {
+    leaf_count: usize,
+    nodes: Vec<H::Hash>,
+}
+
+impl<H: Hasher> MerkleAccumulator<H> {
+    pub fn new<T: AsRef<[u8]>>(items: &[T]) -> Self {
+        let mut nodes = Vec::with_capacity(MerkleTree::calculate_vec_capacity(items.len()));
+        nodes.extend(items.iter().map(|item| H::hash_leaf(item.as_ref())));
+
+        let mut level_start = 0;
+        let mut level_len = items.len();
+        while level_len > 1 {
+            for i in (0..level_len).step_by(2) {
+                let left = nodes[level_start + i];
+                // Duplicate last entry if the level length is odd
+                let right = nodes[level_start + (i + 1).min(level_len - 1)];
+                nodes.push(H::hash_intermediate(&left, &right));
+            }
+            level_start += level_len;
+            level_len = MerkleTree::next_level_len(level_len);
+        }
+
+        Self {
+            leaf_count: items.len(),
+            nodes,
+        }
+    }
+
+    pub fn leaf_count(&self) -> usize {
+        self.leaf_count
+    }
+
+    /// Returns the root of the tree, or `None` if it has no leaves.
+    pub fn root(&self) -> Option<&H::Hash> {
+        self.nodes.last()
+    }
+
+    /// Builds the inclusion proof of the leaf at `index`.
+    pub fn prove(&self, index: usize) -> Option<MerkleProof<H>> {
+        if index >= self.leaf_count {
+            return None;
+        }
+
+        let mut path = Vec::new();
+        let mut level_start = 0;
+        let mut level_len = self.leaf_count;
+        let mut node_index = index;
+        while level_len > 1 {
+            let sibling = if node_index % 2 == 0 {
+                (node_index + 1).min(level_len - 1)
+            } else {
+                node_index - 1
+            };
+            path.push(self.nodes[level_start + sibling]);
+            node_index /= 2;
+            level_start += level_len;
+            level_len = MerkleTree::next_level_len(level_len);
+        }
+
+        Some(MerkleProof {
+            index: index as u32,
+            path,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST: &[&[u8]] = &[
+        b"my", b"very", b"eager", b"mother", b"just", b"served", b"us", b"nine", b"pizzas",
+        b"make", b"prime",
+    ];
+
+    fn test_prove_and_verify<H: Hasher>() {
+        for len in 1..=TEST.len() {
+            let items = &TEST[..len];
+            let accumulator = MerkleAccumulator::<H>::new(items);
+            let root = accumulator.root().unwrap();
+            for (i, item) in items.iter().enumerate() {
+                let proof = accumulator.prove(i).unwrap();
+                assert!(proof.verify(root, len, item));
+                assert!(!proof.verify(root, len, b"bad"));
+                assert!(!proof.verify(&H::Hash::default(), len, item));
+
+                let bytes = proof.to_bytes();
+                assert_eq!(bytes.len(), 5 + proof.path.len() * H::HASH_LEN);
+                assert_eq!(MerkleProof::<H>::from_bytes(&bytes), Ok(proof));
+            }
+            assert_eq!(accumulator.prove(len), None);
+        }
+    }
+
+    #[test]
+    fn test_prove_and_verify_sha256() {
+        test_prove_and_verify::<Sha256>();
+    }
+
+    #[test]
+    fn test_prove_and_verify_keccak160() {
+        test_prove_and_verify::<Keccak160>();
+    }
+
+    #[test]
+    fn test_matches_merkle_tree() {
+        for len in 0..=TEST.len() {
+            let items = &TEST[..len];
+            assert_eq!(
+                MerkleAccumulator::<Sha256>::new(items).root(),
+                MerkleTree::new(items)
+                    .get_root()
+                    .map(|root| root.to_bytes())
+                    .as_ref()
+            );
+        }
+    }
+
+    #[test]
+    fn test_proof_at_wrong_index() {
+        let accumulator = MerkleAccumulator::<Keccak160>::new(TEST);
+        let root = accumulator.root().unwrap();
+        let mut proof = accumulator.prove(2).unwrap();
+        proof.index = 3;
+        assert!(!proof.verify(root, TEST.len(), TEST[2]));
+        // An index beyond the tree cannot reach the root.
+        proof.index = 2 + (1 << proof.path.len());
+        assert!(!proof.verify(root, TEST.len(), TEST[2]));
+    }
+
+    #[test]
+    fn test_proof_past_duplicated_last_leaf() {
+        // The last of the odd number of leaves is paired with itself, so its
+        // path also hashes to the root from the index past it.
+        assert_eq!(TEST.len() % 2, 1);
+        let accumulator = MerkleAccumulator::<Sha256>::new(TEST);
+        let root = accumulator.root().unwrap();
+        let last = TEST.len() - 1;
+        let mut proof = accumulator.prove(last).unwrap();
+        assert!(proof.verify(root, TEST.len(), TEST[last]));
+        proof.index = TEST.len() as u32;
+        assert!(!proof.verify(root, TEST.len(), TEST[last]));
+        assert!(!MerkleProof::verify_batch(
+            root,
+            TEST.len(),
+            [(TEST[last], &proof)]
+        ));
+    }
+
+    #[test]
+    fn test_verify_batch() {
+        let accumulator = MerkleAccumulator::<Keccak160>::new(TEST);
+        let root = accumulator.root().unwrap();
+        let proofs: Vec<_> = (0..TEST.len())
+            .map(|i| accumulator.prove(i).unwrap())
+            .collect();
+
+        let batch = TEST.iter().copied().zip(proofs.iter());
+        assert!(MerkleProof::verify_batch(root, TEST.len(), batch));
+
+        // A single bad leaf fails the batch, even once its path joins nodes
+        // that were already verified.
+        let mut leaves = TEST.to_vec();
+        leaves[5] = b"bad";
+        let batch = leaves.iter().copied().zip(proofs.iter());
+        assert!(!MerkleProof::verify_batch(root, TEST.len(), batch));
+
+        assert!(MerkleProof::<Keccak160>::verify_batch(root, TEST.len(), []));
+    }
+
+    #[test]
+    fn test_proof_from_bytes_malformed() {
+        let accumulator = MerkleAccumulator::<Sha256>::new(TEST);
+        let bytes = accumulator.prove(0).unwrap().to_bytes();
+        assert_eq!(
+            MerkleProof::<Sha256>::from_bytes(&bytes[..3]),
+            Err(ProofError::Truncated)
+        );
+        assert_eq!(
+            MerkleProof::<Sha256>::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(ProofError::Truncated)
+        );
+        let mut trailing = bytes;
+        trailing.push(0);
+        assert_eq!(
+            MerkleProof::<Sha256>::from_bytes(&trailing),
+            Err(ProofError::TrailingBytes(1))
+        );
+    }
+}
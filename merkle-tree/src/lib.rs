@@ -4,5 +4,9 @@
 #[macro_use]
 extern crate matches;
 
+pub mod accumulator;
 pub mod merkle_tree;
-pub use merkle_tree::MerkleTree;
+pub use {
+    accumulator::{Keccak160, MerkleAccumulator, MerkleProof, Sha256},
+    merkle_tree::MerkleTree,
+};
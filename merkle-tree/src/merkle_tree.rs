@@ -3,8 +3,8 @@ use solana_program::hash::{hashv, Hash};
 // We need to discern between leaf and intermediate nodes to prevent trivial second
 // pre-image attacks.
 // https://flawed.net.nz/2018/02/21/attacking-merkle-trees-with-a-second-preimage-attack
-const LEAF_PREFIX: &[u8] = &[0];
-const INTERMEDIATE_PREFIX: &[u8] = &[1];
+pub(crate) const LEAF_PREFIX: &[u8] = &[0];
+pub(crate) const INTERMEDIATE_PREFIX: &[u8] = &[1];
 
 macro_rules! hash_leaf {
     {$d:ident} => {
@@ -64,7 +64,7 @@ impl<'a> Proof<'a> {
 
 impl MerkleTree {
     #[inline]
-    fn next_level_len(level_len: usize) -> usize {
+    pub(crate) fn next_level_len(level_len: usize) -> usize {
         if level_len == 1 {
             0
         } else {
@@ -72,7 +72,7 @@ impl MerkleTree {
         }
     }
 
-    fn calculate_vec_capacity(leaf_count: usize) -> usize {
+    pub(crate) fn calculate_vec_capacity(leaf_count: usize) -> usize {
         // the most nodes consuming case is when n-1 is full balanced binary tree
         // then n will cause the previous tree add a left only path to the root
         // this cause the total nodes number increased by tree height, we use this
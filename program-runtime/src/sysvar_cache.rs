@@ -3,6 +3,7 @@ use solana_sdk::sysvar::{fees::Fees, recent_blockhashes::RecentBlockhashes};
 use {
     crate::invoke_context::InvokeContext,
     solana_sdk::{
+        accumulator::{Accumulator, AccumulatorRoot},
         instruction::InstructionError,
         pubkey::Pubkey,
         sysvar::{
@@ -33,6 +34,7 @@ pub struct SysvarCache {
     #[allow(deprecated)]
     recent_blockhashes: Option<Arc<RecentBlockhashes>>,
     stake_history: Option<Arc<StakeHistory>>,
+    accumulator_root: Option<Arc<AccumulatorRoot>>,
 }
 
 impl SysvarCache {
@@ -110,6 +112,16 @@ impl SysvarCache {
         self.stake_history = Some(Arc::new(stake_history));
     }
 
+    pub fn get_accumulator_root(&self) -> Result<Arc<AccumulatorRoot>, InstructionError> {
+        self.accumulator_root
+            .clone()
+            .ok_or(InstructionError::UnsupportedSysvar)
+    }
+
+    pub fn set_accumulator_root(&mut self, accumulator_root: AccumulatorRoot) {
+        self.accumulator_root = Some(Arc::new(accumulator_root));
+    }
+
     pub fn fill_missing_entries<F: FnMut(&Pubkey, &mut dyn FnMut(&[u8]))>(
         &mut self,
         mut get_account_data: F,
@@ -165,6 +177,14 @@ impl SysvarCache {
                 }
             });
         }
+        if self.accumulator_root.is_none() {
+            // Only the leading slot and root are decoded, the messages are never read.
+            get_account_data(&Accumulator::id(), &mut |data: &[u8]| {
+                if let Ok(accumulator_root) = bincode::deserialize(data) {
+                    self.set_accumulator_root(accumulator_root);
+                }
+            });
+        }
     }
 
     pub fn reset(&mut self) {
@@ -4,6 +4,7 @@
 // Export tokio for test clients
 pub use tokio;
 use {
+    crate::pyth::PythOracle,
     async_trait::async_trait,
     chrono_humanize::{Accuracy, HumanTime, Tense},
     log::*,
@@ -27,6 +28,7 @@ use {
     solana_sdk::{
         account::{Account, AccountSharedData},
         account_info::AccountInfo,
+        accumulator::AccumulatorRoot,
         clock::Slot,
         entrypoint::{deserialize, ProgramResult, SUCCESS},
         feature_set::FEATURE_NAMES,
@@ -49,7 +51,7 @@ use {
         convert::TryFrom,
         fs::File,
         io::{self, Read},
-        mem::transmute,
+        mem::{size_of, transmute},
         path::{Path, PathBuf},
         sync::{
             atomic::{AtomicBool, Ordering},
@@ -69,6 +71,7 @@ pub use {
 };
 
 pub mod programs;
+pub mod pyth;
 
 #[macro_use]
 extern crate solana_bpf_loader_program;
@@ -375,6 +378,27 @@ impl solana_sdk::program_stubs::SyscallStubs for SyscallStubs {
         get_sysvar(get_invoke_context().get_sysvar_cache().get_rent(), var_addr)
     }
 
+    fn sol_get_accumulator_root(&self, var_addr: *mut u8) -> u64 {
+        let invoke_context = get_invoke_context();
+        if invoke_context
+            .consume_checked(
+                invoke_context.get_compute_budget().sysvar_base_cost
+                    + size_of::<AccumulatorRoot>() as u64,
+            )
+            .is_err()
+        {
+            panic!("Exceeded compute budget");
+        }
+
+        match invoke_context.get_sysvar_cache().get_accumulator_root() {
+            Ok(accumulator_root) => unsafe {
+                *(var_addr as *mut _ as *mut AccumulatorRoot) = *accumulator_root;
+                SUCCESS
+            },
+            Err(_) => UNSUPPORTED_SYSVAR,
+        }
+    }
+
     fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
         let (program_id, data) = get_invoke_context().transaction_context.get_return_data();
         Some((*program_id, data.to_vec()))
@@ -576,6 +600,14 @@ impl ProgramTest {
         );
     }
 
+    /// Add the mapping, product and price accounts of a simulated Pyth oracle
+    /// to the test environment
+    pub fn add_pyth_oracle(&mut self, oracle: &PythOracle) {
+        for (address, account) in oracle.genesis_accounts() {
+            self.add_account(address, account);
+        }
+    }
+
     /// Add a SBF program to the test environment.
     ///
     /// `program_name` will also be used to locate the SBF shared object in the current or fixtures
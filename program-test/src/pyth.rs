@@ -0,0 +1,308 @@
+//! Simulated Pyth price feeds for testing oracle consumers.
+//!
+//! [`PythOracle`] describes a mapping account and, for every feed, a product
+//! and a price account with its publishers, which [`ProgramTest::add_pyth_oracle`]
+//! creates in genesis. Publisher prices are scripted per slot with a
+//! [`PriceScript`] and played back against a [`ProgramTestContext`]. As on
+//! Pythnet, the bank aggregates the prices written in a slot and commits them
+//! to the accumulator sysvar when the slot is frozen.
+//!
+//! [`ProgramTest::add_pyth_oracle`]: crate::ProgramTest::add_pyth_oracle
+
+use {
+    crate::{ProgramTestContext, ProgramTestError},
+    solana_sdk::{
+        account::{Account, ReadableAccount, WritableAccount},
+        clock::Slot,
+        pubkey::Pubkey,
+        pyth_oracle::{
+            self, AccountHeader, AccountType, MappingAccount, PriceAccount, PriceComponent,
+            PriceInfo, PriceStatus, ProductAccount, MAGIC, VERSION,
+        },
+        rent::Rent,
+    },
+    std::{collections::BTreeMap, mem::size_of},
+};
+
+/// Addresses of a simulated price feed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PriceFeed {
+    pub product: Pubkey,
+    pub price: Pubkey,
+    /// Publishers of the feed, in the order of their price components
+    pub publishers: Vec<Pubkey>,
+    symbol: String,
+    exponent: i32,
+}
+
+/// A set of simulated price feeds listed by a single mapping account.
+#[derive(Clone, Debug)]
+pub struct PythOracle {
+    mapping: Pubkey,
+    feeds: Vec<PriceFeed>,
+}
+
+impl Default for PythOracle {
+    fn default() -> Self {
+        Self {
+            mapping: Pubkey::new_unique(),
+            feeds: vec![],
+        }
+    }
+}
+
+impl PythOracle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a feed quoted by `num_publishers` new publishers. Its prices are
+    /// `price * 10^exponent`.
+    pub fn add_price_feed(
+        &mut self,
+        symbol: &str,
+        exponent: i32,
+        num_publishers: usize,
+    ) -> PriceFeed {
+        let feed = PriceFeed {
+            product: Pubkey::new_unique(),
+            price: Pubkey::new_unique(),
+            publishers: (0..num_publishers).map(|_| Pubkey::new_unique()).collect(),
+            symbol: symbol.to_string(),
+            exponent,
+        };
+        self.feeds.push(feed.clone());
+        feed
+    }
+
+    pub fn mapping(&self) -> &Pubkey {
+        &self.mapping
+    }
+
+    pub fn feeds(&self) -> &[PriceFeed] {
+        &self.feeds
+    }
+
+    /// The mapping, product and price accounts of the oracle, before any
+    /// price has been published.
+    pub fn genesis_accounts(&self) -> Vec<(Pubkey, Account)> {
+        let mut accounts = vec![(self.mapping, self.mapping_account())];
+        for feed in &self.feeds {
+            accounts.push((feed.product, product_account(feed)));
+            accounts.push((feed.price, price_account(feed)));
+        }
+        accounts
+    }
+
+    fn mapping_account(&self) -> Account {
+        let size = size_of::<MappingAccount>() + self.feeds.len() * size_of::<Pubkey>();
+        let mapping = MappingAccount {
+            header: account_header(AccountType::Mapping, size),
+            num_products: self.feeds.len() as u32,
+            ..MappingAccount::default()
+        };
+        let mut data = bytemuck::bytes_of(&mapping).to_vec();
+        for feed in &self.feeds {
+            data.extend_from_slice(feed.product.as_ref());
+        }
+        oracle_account(data)
+    }
+}
+
+fn account_header(account_type: AccountType, size: usize) -> AccountHeader {
+    AccountHeader {
+        magic: MAGIC,
+        version: VERSION,
+        account_type: account_type as u32,
+        size: size as u32,
+    }
+}
+
+fn oracle_account(data: Vec<u8>) -> Account {
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: pyth_oracle::id(),
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn product_account(feed: &PriceFeed) -> Account {
+    let mut attributes = vec![];
+    for string in ["symbol", feed.symbol.as_str()] {
+        attributes.push(string.len() as u8);
+        attributes.extend_from_slice(string.as_bytes());
+    }
+    let product = ProductAccount {
+        header: account_header(
+            AccountType::Product,
+            size_of::<ProductAccount>() + attributes.len(),
+        ),
+        price_account: feed.price,
+    };
+    let mut data = bytemuck::bytes_of(&product).to_vec();
+    data.extend_from_slice(&attributes);
+    oracle_account(data)
+}
+
+fn price_account(feed: &PriceFeed) -> Account {
+    let size = size_of::<PriceAccount>() + feed.publishers.len() * size_of::<PriceComponent>();
+    let price = PriceAccount {
+        header: account_header(AccountType::Price, size),
+        price_type: 1,
+        exponent: feed.exponent,
+        num_components: feed.publishers.len() as u32,
+        min_publishers: 1,
+        product_account: feed.product,
+        ..PriceAccount::default()
+    };
+    let mut data = bytemuck::bytes_of(&price).to_vec();
+    for publisher in &feed.publishers {
+        let component = PriceComponent {
+            publisher: *publisher,
+            ..PriceComponent::default()
+        };
+        data.extend_from_slice(bytemuck::bytes_of(&component));
+    }
+    oracle_account(data)
+}
+
+/// A price submitted by a publisher.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PriceUpdate {
+    pub price: i64,
+    pub conf: u64,
+    pub status: PriceStatus,
+    /// How many slots before the slot it is written in the price was
+    /// published. Prices older than `pyth_oracle::MAX_SEND_LATENCY` slots
+    /// are stale and left out of the aggregate.
+    pub slots_behind: u64,
+}
+
+impl PriceUpdate {
+    /// A current trading price. A wide confidence interval is simulated with
+    /// a large `conf`.
+    pub fn trading(price: i64, conf: u64) -> Self {
+        Self {
+            price,
+            conf,
+            status: PriceStatus::Trading,
+            slots_behind: 0,
+        }
+    }
+
+    /// A price of a halted market, which is left out of the aggregate.
+    pub fn halted(price: i64, conf: u64) -> Self {
+        Self {
+            status: PriceStatus::Halted,
+            ..Self::trading(price, conf)
+        }
+    }
+
+    /// The same price, published `slots_behind` slots before it is written.
+    pub fn stale(self, slots_behind: u64) -> Self {
+        Self {
+            slots_behind,
+            ..self
+        }
+    }
+}
+
+/// Publisher prices to write, by slot.
+#[derive(Clone, Debug, Default)]
+pub struct PriceScript {
+    updates: BTreeMap<Slot, Vec<(Pubkey, Pubkey, PriceUpdate)>>,
+}
+
+impl PriceScript {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Have `publisher` update the price of `feed` in `slot`.
+    pub fn update(
+        &mut self,
+        slot: Slot,
+        feed: &PriceFeed,
+        publisher: &Pubkey,
+        update: PriceUpdate,
+    ) -> &mut Self {
+        self.updates
+            .entry(slot)
+            .or_default()
+            .push((feed.price, *publisher, update));
+        self
+    }
+
+    /// Have the publishers of `feed` update its price in `slot`, the first
+    /// publisher with the first update and so on.
+    pub fn update_all(
+        &mut self,
+        slot: Slot,
+        feed: &PriceFeed,
+        updates: &[PriceUpdate],
+    ) -> &mut Self {
+        for (publisher, update) in feed.publishers.iter().zip(updates) {
+            self.update(slot, feed, publisher, *update);
+        }
+        self
+    }
+
+    /// Write the scripted prices, warping the working bank to each scripted
+    /// slot in turn, then warp one slot past the last one so that its
+    /// aggregates and accumulator are visible to the banks client.
+    pub fn play(&self, context: &mut ProgramTestContext) -> Result<(), ProgramTestError> {
+        let mut last_slot = None;
+        for (slot, updates) in &self.updates {
+            if *slot != context.working_slot() {
+                context.warp_to_slot(*slot)?;
+            }
+            for (price_account, publisher, update) in updates {
+                context.update_pyth_price(price_account, publisher, update);
+            }
+            last_slot = Some(*slot);
+        }
+        if let Some(last_slot) = last_slot {
+            context.warp_to_slot(last_slot + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl ProgramTestContext {
+    fn working_slot(&self) -> Slot {
+        self.bank_forks.read().unwrap().working_bank().slot()
+    }
+
+    /// Write `publisher`'s latest price to its component of `price_account`
+    /// in the working bank. The price is aggregated when the bank is frozen.
+    pub fn update_pyth_price(
+        &mut self,
+        price_account: &Pubkey,
+        publisher: &Pubkey,
+        update: &PriceUpdate,
+    ) {
+        let bank_forks = self.bank_forks.read().unwrap();
+        let bank = bank_forks.working_bank();
+        let mut account = bank
+            .get_account(price_account)
+            .unwrap_or_else(|| panic!("Price account {price_account} not found"));
+        let price = PriceAccount::from_account_data(account.data())
+            .unwrap_or_else(|| panic!("{price_account} is not a price account"));
+        let mut components = price.components(account.data());
+        let component = components
+            .iter_mut()
+            .find(|component| component.publisher == *publisher)
+            .unwrap_or_else(|| panic!("{publisher} does not publish to {price_account}"));
+        component.latest = PriceInfo {
+            price: update.price,
+            conf: update.conf,
+            status: update.status as u32,
+            corp_act: 0,
+            pub_slot: bank.slot().saturating_sub(update.slots_behind),
+        };
+        price.write_account_data(&components, account.data_as_mut_slice());
+        bank.store_account(price_account, &account);
+    }
+}
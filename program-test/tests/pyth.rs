@@ -0,0 +1,173 @@
+use {
+    solana_program_test::{
+        pyth::{PriceScript, PriceUpdate, PythOracle},
+        ProgramTest, ProgramTestContext,
+    },
+    solana_sdk::{
+        accumulator::{Accumulator, PriceFeedMessage},
+        pubkey::Pubkey,
+        pyth_oracle::{MappingAccount, PriceAccount, PriceStatus, ProductAccount},
+        sysvar,
+    },
+};
+
+async fn get_price_account(context: &mut ProgramTestContext, address: &Pubkey) -> PriceAccount {
+    let account = context
+        .banks_client
+        .get_account(*address)
+        .await
+        .unwrap()
+        .unwrap();
+    PriceAccount::from_account_data(&account.data).unwrap()
+}
+
+async fn get_accumulator(context: &mut ProgramTestContext) -> Accumulator {
+    let account = context
+        .banks_client
+        .get_account(sysvar::accumulator::id())
+        .await
+        .unwrap()
+        .unwrap();
+    bincode::deserialize(&account.data).unwrap()
+}
+
+#[tokio::test]
+async fn pyth_oracle_genesis_accounts() {
+    let mut oracle = PythOracle::new();
+    let btc = oracle.add_price_feed("Crypto.BTC/USD", -8, 3);
+    let eth = oracle.add_price_feed("Crypto.ETH/USD", -8, 2);
+    let mut program_test = ProgramTest::default();
+    program_test.add_pyth_oracle(&oracle);
+    let mut context = program_test.start_with_context().await;
+
+    let mapping = context
+        .banks_client
+        .get_account(*oracle.mapping())
+        .await
+        .unwrap()
+        .unwrap();
+    let products = MappingAccount::from_account_data(&mapping.data)
+        .unwrap()
+        .products(&mapping.data);
+    assert_eq!(products, vec![btc.product, eth.product]);
+
+    let product = context
+        .banks_client
+        .get_account(btc.product)
+        .await
+        .unwrap()
+        .unwrap();
+    let product_account = ProductAccount::from_account_data(&product.data).unwrap();
+    assert_eq!(product_account.price_account, btc.price);
+    assert_eq!(
+        product_account.attributes(&product.data),
+        vec![("symbol".to_string(), "Crypto.BTC/USD".to_string())]
+    );
+
+    let price = context
+        .banks_client
+        .get_account(btc.price)
+        .await
+        .unwrap()
+        .unwrap();
+    let price_account = PriceAccount::from_account_data(&price.data).unwrap();
+    assert_eq!(price_account.exponent, -8);
+    let publishers: Vec<_> = price_account
+        .components(&price.data)
+        .iter()
+        .map(|component| component.publisher)
+        .collect();
+    assert_eq!(publishers, btc.publishers);
+}
+
+#[tokio::test]
+async fn pyth_price_script() {
+    let mut oracle = PythOracle::new();
+    let feed = oracle.add_price_feed("Crypto.BTC/USD", -8, 3);
+    let mut program_test = ProgramTest::default();
+    program_test.add_pyth_oracle(&oracle);
+    let mut context = program_test.start_with_context().await;
+
+    // Trading publishers are aggregated at the median
+    PriceScript::new()
+        .update_all(
+            5,
+            &feed,
+            &[
+                PriceUpdate::trading(100, 1),
+                PriceUpdate::trading(101, 1),
+                PriceUpdate::trading(102, 1),
+            ],
+        )
+        .play(&mut context)
+        .unwrap();
+    let price = get_price_account(&mut context, &feed.price).await;
+    assert_eq!(price.status(), PriceStatus::Trading);
+    assert_eq!(
+        (price.agg.price, price.agg.conf, price.agg.pub_slot),
+        (101, 1, 5)
+    );
+
+    let accumulator = get_accumulator(&mut context).await;
+    assert_eq!(accumulator.slot, 5);
+    assert_eq!(accumulator.messages.len(), 1);
+    let message = PriceFeedMessage::from_bytes(&accumulator.messages[0]).unwrap();
+    assert_eq!(message.feed_id, feed.price);
+    assert_eq!(
+        (message.price, message.conf, message.exponent),
+        (101, 1, -8)
+    );
+
+    // Wide confidence intervals widen the aggregate confidence
+    PriceScript::new()
+        .update_all(10, &feed, &[PriceUpdate::trading(100, 50); 3])
+        .play(&mut context)
+        .unwrap();
+    let price = get_price_account(&mut context, &feed.price).await;
+    assert_eq!((price.agg.price, price.agg.conf), (100, 50));
+
+    // Without a current trading price the aggregate is unknown
+    PriceScript::new()
+        .update_all(20, &feed, &[PriceUpdate::halted(100, 1); 3])
+        .play(&mut context)
+        .unwrap();
+    let price = get_price_account(&mut context, &feed.price).await;
+    assert_eq!(price.status(), PriceStatus::Unknown);
+    assert_eq!(get_accumulator(&mut context).await.slot, 20);
+
+    // Stale and halted prices are left out of the aggregate
+    PriceScript::new()
+        .update_all(
+            40,
+            &feed,
+            &[
+                PriceUpdate::trading(90, 1).stale(30),
+                PriceUpdate::halted(95, 1),
+                PriceUpdate::trading(200, 2),
+            ],
+        )
+        .play(&mut context)
+        .unwrap();
+    let price = get_price_account(&mut context, &feed.price).await;
+    assert_eq!(price.status(), PriceStatus::Trading);
+    assert_eq!(
+        (price.agg.price, price.agg.conf, price.num_quoters),
+        (200, 2, 1)
+    );
+
+    // Slots are played back in order, so the aggregate at slot 55 includes
+    // the price published at slot 50, but not the halted publisher
+    let mut script = PriceScript::new();
+    script
+        .update(50, &feed, &feed.publishers[0], PriceUpdate::trading(300, 3))
+        .update(50, &feed, &feed.publishers[2], PriceUpdate::halted(200, 2))
+        .update(55, &feed, &feed.publishers[1], PriceUpdate::trading(310, 3));
+    script.play(&mut context).unwrap();
+    let price = get_price_account(&mut context, &feed.price).await;
+    // Values are 297 300 303 307 310 313, so the median is the mean of 303 and 307
+    assert_eq!(
+        (price.agg.price, price.agg.pub_slot, price.num_quoters),
+        (305, 55, 2)
+    );
+    assert!(script.play(&mut context).is_err());
+}
@@ -5,8 +5,8 @@ pub use self::{
     },
     mem_ops::{SyscallMemcmp, SyscallMemcpy, SyscallMemmove, SyscallMemset},
     sysvar::{
-        SyscallGetClockSysvar, SyscallGetEpochScheduleSysvar, SyscallGetFeesSysvar,
-        SyscallGetRentSysvar,
+        SyscallGetAccumulatorRoot, SyscallGetClockSysvar, SyscallGetEpochScheduleSysvar,
+        SyscallGetFeesSysvar, SyscallGetRentSysvar,
     },
 };
 #[allow(deprecated)]
@@ -24,6 +24,7 @@ use {
     solana_sdk::{
         account::{ReadableAccount, WritableAccount},
         account_info::AccountInfo,
+        accumulator::AccumulatorRoot,
         alt_bn128::prelude::{
             alt_bn128_addition, alt_bn128_multiplication, alt_bn128_pairing, AltBn128Error,
             ALT_BN128_ADDITION_OUTPUT_LEN, ALT_BN128_MULTIPLICATION_OUTPUT_LEN,
@@ -36,8 +37,8 @@ use {
         feature_set::{
             self, blake3_syscall_enabled, check_syscall_outputs_do_not_overlap,
             curve25519_syscall_enabled, disable_cpi_setting_executable_and_rent_epoch,
-            disable_fees_sysvar, enable_alt_bn128_syscall, enable_big_mod_exp_syscall,
-            enable_early_verification_of_account_modifications,
+            disable_fees_sysvar, enable_accumulator_root_syscall, enable_alt_bn128_syscall,
+            enable_big_mod_exp_syscall, enable_early_verification_of_account_modifications,
             error_on_syscall_bpf_function_hash_collisions, libsecp256k1_0_5_upgrade_enabled,
             limit_secp256k1_recovery_id, reject_callx_r10,
             stop_sibling_instruction_search_at_parent,
@@ -191,6 +192,8 @@ pub fn create_loader<'a>(
     let blake3_syscall_enabled = feature_set.is_active(&blake3_syscall_enabled::id());
     let curve25519_syscall_enabled = feature_set.is_active(&curve25519_syscall_enabled::id());
     let disable_fees_sysvar = feature_set.is_active(&disable_fees_sysvar::id());
+    let enable_accumulator_root_syscall =
+        feature_set.is_active(&enable_accumulator_root_syscall::id());
     let is_abi_v2 = false;
 
     let mut result = BuiltInProgram::new_loader(config);
@@ -267,6 +270,12 @@ pub fn create_loader<'a>(
         SyscallGetFeesSysvar::call,
     )?;
     result.register_function_by_name("sol_get_rent_sysvar", SyscallGetRentSysvar::call)?;
+    register_feature_gated_function!(
+        result,
+        enable_accumulator_root_syscall,
+        "sol_get_accumulator_root",
+        SyscallGetAccumulatorRoot::call,
+    )?;
 
     // Memory ops
     result.register_function_by_name("sol_memcpy_", SyscallMemcpy::call)?;
@@ -1820,7 +1829,7 @@ mod tests {
             account::AccountSharedData,
             bpf_loader,
             fee_calculator::FeeCalculator,
-            hash::hashv,
+            hash::{hashv, Hash},
             program::check_type_assumptions,
             sysvar::{clock::Clock, epoch_schedule::EpochSchedule, rent::Rent},
             transaction_context::TransactionContext,
@@ -3562,12 +3571,17 @@ mod tests {
         src_rent.lamports_per_byte_year = 1;
         src_rent.exemption_threshold = 2.0;
         src_rent.burn_percent = 3;
+        let src_accumulator_root = AccumulatorRoot {
+            slot: 6,
+            root: Hash::new_unique(),
+        };
 
         let mut sysvar_cache = SysvarCache::default();
         sysvar_cache.set_clock(src_clock.clone());
         sysvar_cache.set_epoch_schedule(src_epochschedule);
         sysvar_cache.set_fees(src_fees.clone());
         sysvar_cache.set_rent(src_rent);
+        sysvar_cache.set_accumulator_root(src_accumulator_root);
 
         prepare_mockup!(
             invoke_context,
@@ -3731,6 +3745,38 @@ mod tests {
             clean_rent.burn_percent = src_rent.burn_percent;
             assert!(are_bytes_equal(&got_rent, &clean_rent));
         }
+
+        // Test accumulator root
+        {
+            let got_accumulator_root = AccumulatorRoot::default();
+            let got_accumulator_root_va = 0x100000000;
+
+            let mut memory_mapping = MemoryMapping::new(
+                vec![MemoryRegion {
+                    host_addr: &got_accumulator_root as *const _ as u64,
+                    vm_addr: got_accumulator_root_va,
+                    len: size_of::<AccumulatorRoot>() as u64,
+                    vm_gap_shift: 63,
+                    is_writable: true,
+                }],
+                &config,
+            )
+            .unwrap();
+
+            let mut result = ProgramResult::Ok(0);
+            SyscallGetAccumulatorRoot::call(
+                &mut invoke_context,
+                got_accumulator_root_va,
+                0,
+                0,
+                0,
+                0,
+                &mut memory_mapping,
+                &mut result,
+            );
+            result.unwrap();
+            assert_eq!(got_accumulator_root, src_accumulator_root);
+        }
     }
 
     fn call_program_address_common<'a, 'b: 'a>(
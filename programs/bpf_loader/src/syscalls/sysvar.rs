@@ -112,3 +112,38 @@ declare_syscall!(
         )
     }
 );
+
+declare_syscall!(
+    /// Get the root of the accumulator built when the parent bank was frozen
+    SyscallGetAccumulatorRoot,
+    fn inner_call(
+        invoke_context: &mut InvokeContext,
+        var_addr: u64,
+        _arg2: u64,
+        _arg3: u64,
+        _arg4: u64,
+        _arg5: u64,
+        memory_mapping: &mut MemoryMapping,
+    ) -> Result<u64, EbpfError> {
+        consume_compute_meter(
+            invoke_context,
+            invoke_context
+                .get_compute_budget()
+                .sysvar_base_cost
+                .saturating_add(size_of::<AccumulatorRoot>() as u64),
+        )?;
+        let var = translate_type_mut::<AccumulatorRoot>(
+            memory_mapping,
+            var_addr,
+            invoke_context.get_check_aligned(),
+        )?;
+
+        let accumulator_root = invoke_context
+            .get_sysvar_cache()
+            .get_accumulator_root()
+            .map_err(SyscallError::InstructionError)?;
+        *var = *accumulator_root;
+
+        Ok(SUCCESS)
+    }
+);
@@ -64,6 +64,7 @@
 //!                 data_slice: None,
 //!                 commitment: Some(CommitmentConfig::confirmed()),
 //!                 min_context_slot: None,
+//!                 at_slot: None,
 //!             }),
 //!         )?;
 //!
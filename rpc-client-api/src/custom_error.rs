@@ -24,6 +24,9 @@ pub const JSON_RPC_SERVER_ERROR_TRANSACTION_SIGNATURE_LEN_MISMATCH: i64 = -32013
 pub const JSON_RPC_SERVER_ERROR_BLOCK_STATUS_NOT_AVAILABLE_YET: i64 = -32014;
 pub const JSON_RPC_SERVER_ERROR_UNSUPPORTED_TRANSACTION_VERSION: i64 = -32015;
 pub const JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED: i64 = -32016;
+pub const JSON_RPC_SERVER_ERROR_ACCOUNT_HISTORY_NOT_AVAILABLE: i64 = -32017;
+pub const JSON_RPC_SERVER_ERROR_RATE_LIMIT_EXCEEDED: i64 = -32018;
+pub const JSON_RPC_SERVER_ERROR_METHOD_CONCURRENCY_LIMIT_EXCEEDED: i64 = -32019;
 
 #[derive(Error, Debug)]
 pub enum RpcCustomError {
@@ -65,6 +68,12 @@ pub enum RpcCustomError {
     UnsupportedTransactionVersion(u8),
     #[error("MinContextSlotNotReached")]
     MinContextSlotNotReached { context_slot: Slot },
+    #[error("AccountHistoryNotAvailable")]
+    AccountHistoryNotAvailable,
+    #[error("RateLimitExceeded")]
+    RateLimitExceeded { method: String, retry_after_ms: u64 },
+    #[error("MethodConcurrencyLimitExceeded")]
+    MethodConcurrencyLimitExceeded { method: String },
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -79,6 +88,12 @@ pub struct MinContextSlotNotReachedErrorData {
     pub context_slot: Slot,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitExceededErrorData {
+    pub retry_after_ms: u64,
+}
+
 impl From<EncodeError> for RpcCustomError {
     fn from(err: EncodeError) -> Self {
         match err {
@@ -206,6 +221,26 @@ impl From<RpcCustomError> for Error {
                     context_slot,
                 })),
             },
+            RpcCustomError::AccountHistoryNotAvailable => Self {
+                code: ErrorCode::ServerError(JSON_RPC_SERVER_ERROR_ACCOUNT_HISTORY_NOT_AVAILABLE),
+                message: "Account history is not available from this node".to_string(),
+                data: None,
+            },
+            RpcCustomError::RateLimitExceeded {
+                method,
+                retry_after_ms,
+            } => Self {
+                code: ErrorCode::ServerError(JSON_RPC_SERVER_ERROR_RATE_LIMIT_EXCEEDED),
+                message: format!("Rate limit exceeded for method {method}"),
+                data: Some(serde_json::json!(RateLimitExceededErrorData { retry_after_ms })),
+            },
+            RpcCustomError::MethodConcurrencyLimitExceeded { method } => Self {
+                code: ErrorCode::ServerError(
+                    JSON_RPC_SERVER_ERROR_METHOD_CONCURRENCY_LIMIT_EXCEEDED,
+                ),
+                message: format!("Too many concurrent {method} requests, try again later"),
+                data: None,
+            },
         }
     }
 }
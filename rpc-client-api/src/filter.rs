@@ -10,6 +10,7 @@ use {
 const MAX_DATA_SIZE: usize = 128;
 const MAX_DATA_BASE58_SIZE: usize = 175;
 const MAX_DATA_BASE64_SIZE: usize = 172;
+const MAX_OR_FILTERS: usize = 8;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -17,6 +18,9 @@ pub enum RpcFilterType {
     DataSize(u64),
     Memcmp(Memcmp),
     TokenAccountState,
+    IntCmp(IntCmp),
+    /// Allows the accounts allowed by any of the filters
+    Or(Vec<RpcFilterType>),
 }
 
 impl RpcFilterType {
@@ -76,6 +80,19 @@ impl RpcFilterType {
                 }
             }
             RpcFilterType::TokenAccountState => Ok(()),
+            RpcFilterType::IntCmp(compare) => compare.verify(),
+            RpcFilterType::Or(filters) => {
+                if filters.is_empty() || filters.len() > MAX_OR_FILTERS {
+                    return Err(RpcFilterError::InvalidOrFilter);
+                }
+                for filter in filters {
+                    if let RpcFilterType::Or(_) = filter {
+                        return Err(RpcFilterError::InvalidOrFilter);
+                    }
+                    filter.verify()?;
+                }
+                Ok(())
+            }
         }
     }
 
@@ -84,6 +101,8 @@ impl RpcFilterType {
             RpcFilterType::DataSize(size) => account.data().len() as u64 == *size,
             RpcFilterType::Memcmp(compare) => compare.bytes_match(account.data()),
             RpcFilterType::TokenAccountState => Account::valid_account_data(account.data()),
+            RpcFilterType::IntCmp(compare) => compare.int_match(account.data()),
+            RpcFilterType::Or(filters) => filters.iter().any(|filter| filter.allows(account)),
         }
     }
 }
@@ -108,6 +127,10 @@ pub enum RpcFilterError {
     Base58DecodeError(#[from] bs58::decode::Error),
     #[error("base64 decode error")]
     Base64DecodeError(#[from] base64::DecodeError),
+    #[error("compared value is out of the range of the integer type")]
+    IntValueOutOfRange,
+    #[error("or filter should hold 1 to 8 filters, none of them an or filter")]
+    InvalidOrFilter,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -220,6 +243,120 @@ impl Memcmp {
     }
 }
 
+/// Type of a little-endian integer in account data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IntType {
+    U8,
+    U16,
+    U32,
+    U64,
+    I8,
+    I16,
+    I32,
+    I64,
+}
+
+impl IntType {
+    fn size(&self) -> usize {
+        match self {
+            IntType::U8 | IntType::I8 => 1,
+            IntType::U16 | IntType::I16 => 2,
+            IntType::U32 | IntType::I32 => 4,
+            IntType::U64 | IntType::I64 => 8,
+        }
+    }
+
+    fn range(&self) -> (i128, i128) {
+        match self {
+            IntType::U8 => (0, u8::MAX.into()),
+            IntType::U16 => (0, u16::MAX.into()),
+            IntType::U32 => (0, u32::MAX.into()),
+            IntType::U64 => (0, u64::MAX.into()),
+            IntType::I8 => (i8::MIN.into(), i8::MAX.into()),
+            IntType::I16 => (i16::MIN.into(), i16::MAX.into()),
+            IntType::I32 => (i32::MIN.into(), i32::MAX.into()),
+            IntType::I64 => (i64::MIN.into(), i64::MAX.into()),
+        }
+    }
+
+    /// Reads an integer of this type from the start of `bytes`, which must be
+    /// exactly `size()` bytes long
+    fn read(&self, bytes: &[u8]) -> i128 {
+        match self {
+            IntType::U8 => bytes[0].into(),
+            IntType::U16 => u16::from_le_bytes(bytes.try_into().unwrap()).into(),
+            IntType::U32 => u32::from_le_bytes(bytes.try_into().unwrap()).into(),
+            IntType::U64 => u64::from_le_bytes(bytes.try_into().unwrap()).into(),
+            IntType::I8 => (bytes[0] as i8).into(),
+            IntType::I16 => i16::from_le_bytes(bytes.try_into().unwrap()).into(),
+            IntType::I32 => i32::from_le_bytes(bytes.try_into().unwrap()).into(),
+            IntType::I64 => i64::from_le_bytes(bytes.try_into().unwrap()).into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IntCmpOperator {
+    Lt,
+    Gt,
+    Eq,
+    Ne,
+}
+
+/// Compares an integer in account data with a value
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntCmp {
+    /// Data offset of the integer
+    pub offset: usize,
+    /// Type of the integer, stored little-endian
+    #[serde(rename = "type")]
+    pub int_type: IntType,
+    /// How the integer compares with `value` in allowed accounts
+    pub op: IntCmpOperator,
+    pub value: i128,
+}
+
+impl IntCmp {
+    pub fn new(offset: usize, int_type: IntType, op: IntCmpOperator, value: i128) -> Self {
+        Self {
+            offset,
+            int_type,
+            op,
+            value,
+        }
+    }
+
+    pub fn verify(&self) -> Result<(), RpcFilterError> {
+        let (min, max) = self.int_type.range();
+        if self.value < min || self.value > max {
+            Err(RpcFilterError::IntValueOutOfRange)
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn int_match(&self, data: &[u8]) -> bool {
+        let bytes = match self
+            .offset
+            .checked_add(self.int_type.size())
+            .and_then(|end| data.get(self.offset..end))
+        {
+            Some(bytes) => bytes,
+            None => return false,
+        };
+        let int = self.int_type.read(bytes);
+        match self.op {
+            IntCmpOperator::Lt => int < self.value,
+            IntCmpOperator::Gt => int > self.value,
+            IntCmpOperator::Eq => int == self.value,
+            IntCmpOperator::Ne => int != self.value,
+        }
+    }
+}
+
 // Internal struct to hold Memcmp filter data as either encoded String or raw Bytes
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -303,20 +440,27 @@ pub fn maybe_map_filters(
         .map(|version| version_reqs.matches_any(&version))
         .unwrap_or(true);
     if needs_mapping {
-        for filter in filters.iter_mut() {
-            if let RpcFilterType::Memcmp(memcmp) = filter {
-                match &memcmp.bytes {
-                    MemcmpEncodedBytes::Base58(string) => {
-                        memcmp.bytes = MemcmpEncodedBytes::Binary(string.clone());
-                    }
-                    MemcmpEncodedBytes::Base64(_) => {
-                        return Err("RPC node on old version does not support base64 \
-                            encoding for memcmp filters"
-                            .to_string());
-                    }
-                    _ => {}
+        map_legacy_memcmp_filters(filters)?;
+    }
+    Ok(())
+}
+
+fn map_legacy_memcmp_filters(filters: &mut [RpcFilterType]) -> Result<(), String> {
+    for filter in filters.iter_mut() {
+        match filter {
+            RpcFilterType::Memcmp(memcmp) => match &memcmp.bytes {
+                MemcmpEncodedBytes::Base58(string) => {
+                    memcmp.bytes = MemcmpEncodedBytes::Binary(string.clone());
                 }
-            }
+                MemcmpEncodedBytes::Base64(_) => {
+                    return Err("RPC node on old version does not support base64 \
+                        encoding for memcmp filters"
+                        .to_string());
+                }
+                _ => {}
+            },
+            RpcFilterType::Or(filters) => map_legacy_memcmp_filters(filters)?,
+            _ => {}
         }
     }
     Ok(())
@@ -396,6 +540,94 @@ mod tests {
         .bytes_match(&data));
     }
 
+    #[test]
+    fn test_int_match() {
+        let mut data = vec![0xff];
+        data.extend_from_slice(&1_000u64.to_le_bytes());
+        data.extend_from_slice(&(-5i32).to_le_bytes());
+
+        let compare = |offset, int_type, op, value| IntCmp::new(offset, int_type, op, value);
+        assert!(compare(0, IntType::U8, IntCmpOperator::Eq, 255).int_match(&data));
+        assert!(compare(0, IntType::I8, IntCmpOperator::Eq, -1).int_match(&data));
+        assert!(compare(1, IntType::U64, IntCmpOperator::Gt, 999).int_match(&data));
+        assert!(!compare(1, IntType::U64, IntCmpOperator::Lt, 1_000).int_match(&data));
+        assert!(compare(1, IntType::U16, IntCmpOperator::Ne, 0).int_match(&data));
+        assert!(compare(9, IntType::I32, IntCmpOperator::Lt, 0).int_match(&data));
+        assert!(compare(9, IntType::U32, IntCmpOperator::Gt, i32::MAX.into()).int_match(&data));
+
+        // Integers overrunning data fail
+        assert!(!compare(10, IntType::I32, IntCmpOperator::Ne, 0).int_match(&data));
+        assert!(!compare(usize::MAX, IntType::U8, IntCmpOperator::Ne, 0).int_match(&data));
+    }
+
+    #[test]
+    fn test_verify_int_cmp_and_or() {
+        let int_cmp = |int_type, value| {
+            RpcFilterType::IntCmp(IntCmp::new(0, int_type, IntCmpOperator::Eq, value))
+        };
+        assert_eq!(int_cmp(IntType::U8, 255).verify(), Ok(()));
+        assert_eq!(
+            int_cmp(IntType::U8, 256).verify(),
+            Err(RpcFilterError::IntValueOutOfRange)
+        );
+        assert_eq!(int_cmp(IntType::I64, i64::MIN.into()).verify(), Ok(()));
+        assert_eq!(
+            int_cmp(IntType::U64, -1).verify(),
+            Err(RpcFilterError::IntValueOutOfRange)
+        );
+
+        let or = RpcFilterType::Or(vec![int_cmp(IntType::U8, 1), RpcFilterType::DataSize(8)]);
+        assert_eq!(or.verify(), Ok(()));
+        assert_eq!(
+            RpcFilterType::Or(vec![int_cmp(IntType::U8, 1), int_cmp(IntType::U8, 256)]).verify(),
+            Err(RpcFilterError::IntValueOutOfRange)
+        );
+        assert_eq!(
+            RpcFilterType::Or(vec![]).verify(),
+            Err(RpcFilterError::InvalidOrFilter)
+        );
+        assert_eq!(
+            RpcFilterType::Or(vec![or]).verify(),
+            Err(RpcFilterError::InvalidOrFilter)
+        );
+    }
+
+    #[test]
+    fn test_or_allows() {
+        let account = AccountSharedData::from(solana_sdk::account::Account {
+            data: vec![3, 0],
+            ..solana_sdk::account::Account::default()
+        });
+        let status =
+            |value| RpcFilterType::IntCmp(IntCmp::new(0, IntType::U16, IntCmpOperator::Eq, value));
+        assert!(RpcFilterType::Or(vec![status(1), status(3)]).allows(&account));
+        assert!(!RpcFilterType::Or(vec![status(1), status(2)]).allows(&account));
+        assert!(RpcFilterType::Or(vec![status(1), RpcFilterType::DataSize(2)]).allows(&account));
+    }
+
+    #[test]
+    fn test_int_cmp_serde() {
+        let filter: RpcFilterType = serde_json::from_value(serde_json::json!({
+            "intCmp": {"offset": 208, "type": "u64", "op": "lt", "value": 1000}
+        }))
+        .unwrap();
+        assert_eq!(
+            filter,
+            RpcFilterType::IntCmp(IntCmp::new(208, IntType::U64, IntCmpOperator::Lt, 1000))
+        );
+        let filter: RpcFilterType = serde_json::from_value(serde_json::json!({
+            "or": [{"dataSize": 8}, {"intCmp": {"offset": 0, "type": "i8", "op": "ne", "value": -1}}]
+        }))
+        .unwrap();
+        assert_eq!(
+            filter,
+            RpcFilterType::Or(vec![
+                RpcFilterType::DataSize(8),
+                RpcFilterType::IntCmp(IntCmp::new(0, IntType::I8, IntCmpOperator::Ne, -1)),
+            ])
+        );
+    }
+
     #[test]
     fn test_verify_memcmp() {
         let base58_bytes = "\
@@ -427,4 +659,39 @@ mod tests {
             Err(RpcFilterError::DataTooLarge)
         );
     }
+
+    #[test]
+    fn test_maybe_map_filters_or() {
+        let memcmp = |bytes| {
+            RpcFilterType::Memcmp(Memcmp {
+                offset: 0,
+                bytes,
+                encoding: None,
+            })
+        };
+        let base58 = || MemcmpEncodedBytes::Base58("2".to_string());
+        let old_version = Some(semver::Version::new(1, 10, 0));
+
+        let mut filters = vec![RpcFilterType::Or(vec![
+            RpcFilterType::DataSize(8),
+            memcmp(base58()),
+        ])];
+        maybe_map_filters(old_version.clone(), &mut filters).unwrap();
+        assert_eq!(
+            filters,
+            vec![RpcFilterType::Or(vec![
+                RpcFilterType::DataSize(8),
+                memcmp(MemcmpEncodedBytes::Binary("2".to_string())),
+            ])]
+        );
+
+        let mut filters = vec![RpcFilterType::Or(vec![memcmp(base58())])];
+        maybe_map_filters(Some(semver::Version::new(1, 14, 0)), &mut filters).unwrap();
+        assert_eq!(filters, vec![RpcFilterType::Or(vec![memcmp(base58())])]);
+
+        let mut filters = vec![RpcFilterType::Or(vec![memcmp(MemcmpEncodedBytes::Base64(
+            "Ag==".to_string(),
+        ))])];
+        assert!(maybe_map_filters(old_version, &mut filters).is_err());
+    }
 }
@@ -3,6 +3,7 @@ use {
     serde::{Deserialize, Deserializer, Serialize, Serializer},
     solana_account_decoder::{parse_token::UiTokenAmount, UiAccount},
     solana_sdk::{
+        accumulator::PriceFeedMessage,
         clock::{Epoch, Slot, UnixTimestamp},
         fee_calculator::{FeeCalculator, FeeRateGovernor},
         hash::Hash,
@@ -502,6 +503,52 @@ pub struct RpcInflationReward {
     pub commission: Option<u8>, // Vote account commission when the reward was credited
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAccumulatorProof {
+    pub slot: Slot,
+    pub root: String,    // base-58 encoded Merkle root
+    pub message: String, // base-64 encoded accumulator message
+    pub proof: String,   // base-64 encoded Merkle inclusion proof
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAccumulatorUpdate {
+    pub slot: Slot,
+    pub root: String, // base-58 encoded Merkle root
+    pub messages: Vec<RpcPriceFeedMessage>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcPriceFeedMessage {
+    /// Price account address, as base-58 encoded string
+    pub feed_id: String,
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: UnixTimestamp,
+    pub prev_publish_time: UnixTimestamp,
+    pub ema_price: i64,
+    pub ema_conf: u64,
+}
+
+impl From<PriceFeedMessage> for RpcPriceFeedMessage {
+    fn from(message: PriceFeedMessage) -> Self {
+        Self {
+            feed_id: message.feed_id.to_string(),
+            price: message.price,
+            conf: message.conf,
+            exponent: message.exponent,
+            publish_time: message.publish_time,
+            prev_publish_time: message.prev_publish_time,
+            ema_price: message.ema_price,
+            ema_conf: message.ema_conf,
+        }
+    }
+}
+
 #[derive(Clone, Deserialize, Serialize, Debug, Error, Eq, PartialEq)]
 pub enum RpcBlockUpdateError {
     #[error("block store error")]
@@ -3883,6 +3883,7 @@ impl RpcClient {
             commitment: Some(self.maybe_map_commitment(commitment_config).await?),
             data_slice: None,
             min_context_slot: None,
+            at_slot: None,
         };
 
         self.get_account_with_config(pubkey, config).await
@@ -4110,6 +4111,7 @@ impl RpcClient {
                 commitment: Some(self.maybe_map_commitment(commitment_config).await?),
                 data_slice: None,
                 min_context_slot: None,
+                at_slot: None,
             },
         )
         .await
@@ -4438,6 +4440,7 @@ impl RpcClient {
     ///         }),
     ///         commitment: Some(CommitmentConfig::processed()),
     ///         min_context_slot: Some(1234),
+    ///         at_slot: None,
     ///     },
     ///     with_context: Some(false),
     /// };
@@ -4811,6 +4814,7 @@ impl RpcClient {
             commitment: Some(self.maybe_map_commitment(commitment_config).await?),
             data_slice: None,
             min_context_slot: None,
+            at_slot: None,
         };
         let response = self
             .send(
@@ -4912,6 +4916,7 @@ impl RpcClient {
             commitment: Some(self.maybe_map_commitment(commitment_config).await?),
             data_slice: None,
             min_context_slot: None,
+            at_slot: None,
         };
 
         self.send(
@@ -4954,6 +4959,7 @@ impl RpcClient {
             commitment: Some(self.maybe_map_commitment(commitment_config).await?),
             data_slice: None,
             min_context_slot: None,
+            at_slot: None,
         };
 
         self.send(
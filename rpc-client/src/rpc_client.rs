@@ -3617,6 +3617,7 @@ impl RpcClient {
     ///         }),
     ///         commitment: Some(CommitmentConfig::processed()),
     ///         min_context_slot: Some(1234),
+    ///         at_slot: None,
     ///     },
     ///     with_context: Some(false),
     /// };
@@ -4363,6 +4364,7 @@ mod tests {
                             data_slice: None,
                             commitment: None,
                             min_context_slot: None,
+                            at_slot: None,
                         },
                         with_context: None,
                     },
@@ -4397,6 +4399,7 @@ mod tests {
                             data_slice: None,
                             commitment: None,
                             min_context_slot: None,
+                            at_slot: None,
                         },
                         with_context: Some(true),
                     },
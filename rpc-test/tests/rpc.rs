@@ -129,6 +129,7 @@ fn test_rpc_send_tx() {
         commitment: None,
         data_slice: None,
         min_context_slot: None,
+        at_slot: None,
     };
     let req = json_req!(
         "getAccountInfo",
@@ -2,8 +2,12 @@
 
 use {
     crate::{
-        max_slots::MaxSlots, optimistically_confirmed_bank_tracker::OptimisticallyConfirmedBank,
-        parsed_token_accounts::*, rpc_cache::LargestAccountsCache, rpc_health::*,
+        max_slots::MaxSlots,
+        optimistically_confirmed_bank_tracker::OptimisticallyConfirmedBank,
+        parsed_token_accounts::*,
+        rpc_cache::LargestAccountsCache,
+        rpc_health::*,
+        rpc_rate_limiter::{RpcClientId, RpcRateLimitConfig},
     },
     bincode::{config::Options, serialize},
     crossbeam_channel::{unbounded, Receiver, Sender},
@@ -26,6 +30,7 @@ use {
         get_tmp_ledger_path,
         leader_schedule_cache::LeaderScheduleCache,
     },
+    solana_merkle_tree::{MerkleAccumulator, Sha256},
     solana_metrics::inc_new_counter_info,
     solana_perf::packet::PACKET_DATA_SIZE,
     solana_rpc_client_api::{
@@ -58,6 +63,7 @@ use {
     solana_sdk::{
         account::{AccountSharedData, ReadableAccount},
         account_utils::StateMut,
+        accumulator::{Accumulator, PriceFeedMessage},
         clock::{Slot, UnixTimestamp, MAX_RECENT_BLOCKHASHES},
         commitment_config::{CommitmentConfig, CommitmentLevel},
         epoch_info::EpochInfo,
@@ -83,7 +89,7 @@ use {
         tpu_info::NullTpuInfo,
     },
     solana_stake_program,
-    solana_storage_bigtable::Error as StorageError,
+    solana_storage_bigtable::{Error as StorageError, LedgerStorageAdapter},
     solana_streamer::socket::SocketAddrSpace,
     solana_transaction_status::{
         BlockEncodingOptions, ConfirmedBlock, ConfirmedTransactionStatusWithSignature,
@@ -103,6 +109,7 @@ use {
         collections::{HashMap, HashSet},
         convert::TryFrom,
         net::SocketAddr,
+        path::PathBuf,
         str::FromStr,
         sync::{
             atomic::{AtomicBool, AtomicU64, Ordering},
@@ -124,6 +131,13 @@ fn new_response<T>(bank: &Bank, value: T) -> RpcResponse<T> {
     }
 }
 
+fn new_response_at_slot<T>(bank: &Bank, at_slot: Option<Slot>, value: T) -> RpcResponse<T> {
+    RpcResponse {
+        context: RpcResponseContext::new(at_slot.unwrap_or_else(|| bank.slot())),
+        value,
+    }
+}
+
 fn is_finalized(
     block_commitment_cache: &BlockCommitmentCache,
     bank: &Bank,
@@ -149,6 +163,8 @@ pub struct JsonRpcConfig {
     pub obsolete_v1_7_api: bool,
     pub rpc_scan_and_fix_roots: bool,
     pub max_request_body_size: Option<usize>,
+    pub account_history_owners: HashSet<Pubkey>,
+    pub rate_limit_config: Option<RpcRateLimitConfig>,
 }
 
 impl JsonRpcConfig {
@@ -166,6 +182,8 @@ pub struct RpcBigtableConfig {
     pub bigtable_instance_name: String,
     pub bigtable_app_profile_id: String,
     pub timeout: Option<Duration>,
+    /// Keep the long-term ledger storage in a local database at this path instead of BigTable
+    pub local_ledger_storage_path: Option<PathBuf>,
 }
 
 impl Default for RpcBigtableConfig {
@@ -177,6 +195,7 @@ impl Default for RpcBigtableConfig {
             bigtable_instance_name,
             bigtable_app_profile_id,
             timeout: None,
+            local_ledger_storage_path: None,
         }
     }
 }
@@ -194,13 +213,15 @@ pub struct JsonRpcRequestProcessor {
     cluster_info: Arc<ClusterInfo>,
     genesis_hash: Hash,
     transaction_sender: Arc<Mutex<Sender<TransactionInfo>>>,
-    bigtable_ledger_storage: Option<solana_storage_bigtable::LedgerStorage>,
+    bigtable_ledger_storage: Option<Arc<dyn LedgerStorageAdapter>>,
     optimistically_confirmed_bank: Arc<RwLock<OptimisticallyConfirmedBank>>,
     largest_accounts_cache: Arc<RwLock<LargestAccountsCache>>,
     max_slots: Arc<MaxSlots>,
     leader_schedule_cache: Arc<LeaderScheduleCache>,
     max_complete_transaction_status_slot: Arc<AtomicU64>,
     prioritization_fee_cache: Arc<PrioritizationFeeCache>,
+    /// Client of the request being processed, as identified by the rate limiter
+    client: Option<RpcClientId>,
 }
 impl Metadata for JsonRpcRequestProcessor {}
 
@@ -300,7 +321,7 @@ impl JsonRpcRequestProcessor {
         health: Arc<RpcHealth>,
         cluster_info: Arc<ClusterInfo>,
         genesis_hash: Hash,
-        bigtable_ledger_storage: Option<solana_storage_bigtable::LedgerStorage>,
+        bigtable_ledger_storage: Option<Arc<dyn LedgerStorageAdapter>>,
         optimistically_confirmed_bank: Arc<RwLock<OptimisticallyConfirmedBank>>,
         largest_accounts_cache: Arc<RwLock<LargestAccountsCache>>,
         max_slots: Arc<MaxSlots>,
@@ -328,11 +349,24 @@ impl JsonRpcRequestProcessor {
                 leader_schedule_cache,
                 max_complete_transaction_status_slot,
                 prioritization_fee_cache,
+                client: None,
             },
             receiver,
         )
     }
 
+    /// This processor, handling a request of `client`
+    pub(crate) fn for_client(&self, client: RpcClientId) -> Self {
+        Self {
+            client: Some(client),
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn client(&self) -> Option<&RpcClientId> {
+        self.client.as_ref()
+    }
+
     // Useful for unit testing
     pub fn new_from_bank(
         bank: &Arc<Bank>,
@@ -396,6 +430,7 @@ impl JsonRpcRequestProcessor {
             leader_schedule_cache: Arc::new(LeaderScheduleCache::new_from_bank(bank)),
             max_complete_transaction_status_slot: Arc::new(AtomicU64::default()),
             prioritization_fee_cache: Arc::new(PrioritizationFeeCache::default()),
+            client: None,
         }
     }
 
@@ -409,6 +444,7 @@ impl JsonRpcRequestProcessor {
             data_slice,
             commitment,
             min_context_slot,
+            at_slot,
         } = config.unwrap_or_default();
         let bank = self.get_bank_with_config(RpcContextConfig {
             commitment,
@@ -416,8 +452,9 @@ impl JsonRpcRequestProcessor {
         })?;
         let encoding = encoding.unwrap_or(UiAccountEncoding::Binary);
 
-        let response = get_encoded_account(&bank, pubkey, encoding, data_slice)?;
-        Ok(new_response(&bank, response))
+        let account = self.get_account_at_slot(&bank, pubkey, at_slot)?;
+        let response = get_encoded_account(&bank, pubkey, account, encoding, data_slice)?;
+        Ok(new_response_at_slot(&bank, at_slot, response))
     }
 
     pub fn get_multiple_accounts(
@@ -430,6 +467,7 @@ impl JsonRpcRequestProcessor {
             data_slice,
             commitment,
             min_context_slot,
+            at_slot,
         } = config.unwrap_or_default();
         let bank = self.get_bank_with_config(RpcContextConfig {
             commitment,
@@ -439,9 +477,79 @@ impl JsonRpcRequestProcessor {
 
         let accounts = pubkeys
             .into_iter()
-            .map(|pubkey| get_encoded_account(&bank, &pubkey, encoding, data_slice))
+            .map(|pubkey| {
+                let account = self.get_account_at_slot(&bank, &pubkey, at_slot)?;
+                get_encoded_account(&bank, &pubkey, account, encoding, data_slice)
+            })
             .collect::<Result<Vec<_>>>()?;
-        Ok(new_response(&bank, accounts))
+        Ok(new_response_at_slot(&bank, at_slot, accounts))
+    }
+
+    /// Returns the state of `pubkey` in `bank`, or the state recorded in the account history
+    /// for `at_slot` if it is set. Fails if `at_slot` is before the account history starts, or
+    /// if `pubkey` is owned by a program whose accounts are not recorded.
+    fn get_account_at_slot(
+        &self,
+        bank: &Bank,
+        pubkey: &Pubkey,
+        at_slot: Option<Slot>,
+    ) -> Result<Option<AccountSharedData>> {
+        let slot = match at_slot {
+            Some(slot) => slot,
+            None => return Ok(bank.get_account(pubkey)),
+        };
+        if self.config.account_history_owners.is_empty() {
+            return Err(RpcCustomError::AccountHistoryNotAvailable.into());
+        }
+        if slot
+            > self
+                .block_commitment_cache
+                .read()
+                .unwrap()
+                .highest_confirmed_root()
+        {
+            return Err(RpcCustomError::BlockStatusNotAvailableYet { slot }.into());
+        }
+        let lowest_history_slot = self
+            .blockstore
+            .lowest_account_history_slot()
+            .map_err(|_| Error::internal_error())?
+            .ok_or(RpcCustomError::AccountHistoryNotAvailable)?;
+        if slot < lowest_history_slot {
+            return Err(RpcCustomError::BlockCleanedUp {
+                slot,
+                first_available_block: lowest_history_slot,
+            }
+            .into());
+        }
+        let result = self.blockstore.get_account_at_slot(pubkey, slot);
+        self.check_blockstore_root(&result, slot)?;
+        self.check_slot_cleaned_up(&result, slot)?;
+        match result.map_err(|_| Error::internal_error())? {
+            // closed accounts are recorded without lamports
+            Some((_, account)) => {
+                Ok(Some(AccountSharedData::from(account)).filter(|account| account.lamports() > 0))
+            }
+            // states are only recorded when accounts are written, so the account is only known
+            // not to have existed if it was created later, or if it neither exists nor has any
+            // recorded state, e.g. because it existed before the history started or its states
+            // were purged
+            None => {
+                let created_later = self
+                    .blockstore
+                    .is_account_created_after(pubkey, slot)
+                    .map_err(|_| Error::internal_error())?;
+                let has_history = self
+                    .blockstore
+                    .has_account_history(pubkey)
+                    .map_err(|_| Error::internal_error())?;
+                if created_later || (!has_history && bank.get_account(pubkey).is_none()) {
+                    Ok(None)
+                } else {
+                    Err(RpcCustomError::AccountHistoryNotAvailable.into())
+                }
+            }
+        }
     }
 
     pub fn get_minimum_balance_for_rent_exemption(
@@ -465,7 +573,9 @@ impl JsonRpcRequestProcessor {
             data_slice: data_slice_config,
             commitment,
             min_context_slot,
+            at_slot,
         } = config.unwrap_or_default();
+        verify_no_at_slot(at_slot)?;
         let bank = self.get_bank_with_config(RpcContextConfig {
             commitment,
             min_context_slot,
@@ -1333,6 +1443,54 @@ impl JsonRpcRequestProcessor {
         }
     }
 
+    pub async fn get_accumulator_proof(
+        &self,
+        slot: Slot,
+        price_account: &Pubkey,
+    ) -> Result<Option<RpcAccumulatorProof>> {
+        let bank = self
+            .bank_forks
+            .read()
+            .unwrap()
+            .get(slot)
+            .filter(|bank| bank.is_frozen());
+        if let Some(bank) = bank {
+            return Ok(bank
+                .get_accumulator()
+                .filter(|accumulator| accumulator.slot == slot)
+                .and_then(|accumulator| accumulator_proof(&accumulator, price_account)));
+        }
+
+        // Banks are only kept for recent slots, older accumulators are read back from the
+        // blockstore or long-term storage.
+        if slot
+            <= self
+                .block_commitment_cache
+                .read()
+                .unwrap()
+                .highest_confirmed_root()
+        {
+            let result = self.blockstore.get_rooted_accumulator(slot);
+            self.check_blockstore_root(&result, slot)?;
+            if result.is_err() || matches!(result, Ok(None)) {
+                if let Some(bigtable_ledger_storage) = &self.bigtable_ledger_storage {
+                    let bigtable_result = bigtable_ledger_storage.get_accumulator(slot).await;
+                    self.check_bigtable_result(&bigtable_result)?;
+                    return Ok(bigtable_result
+                        .ok()
+                        .flatten()
+                        .and_then(|accumulator| accumulator_proof(&accumulator, price_account)));
+                }
+            }
+            self.check_slot_cleaned_up(&result, slot)?;
+            return Ok(result
+                .ok()
+                .flatten()
+                .and_then(|accumulator| accumulator_proof(&accumulator, price_account)));
+        }
+        Err(RpcCustomError::BlockNotAvailable { slot }.into())
+    }
+
     pub fn get_signature_confirmation_status(
         &self,
         signature: Signature,
@@ -1881,7 +2039,9 @@ impl JsonRpcRequestProcessor {
             data_slice: data_slice_config,
             commitment,
             min_context_slot,
+            at_slot,
         } = config.unwrap_or_default();
+        verify_no_at_slot(at_slot)?;
         let bank = self.get_bank_with_config(RpcContextConfig {
             commitment,
             min_context_slot,
@@ -1931,7 +2091,9 @@ impl JsonRpcRequestProcessor {
             data_slice: data_slice_config,
             commitment,
             min_context_slot,
+            at_slot,
         } = config.unwrap_or_default();
+        verify_no_at_slot(at_slot)?;
         let bank = self.get_bank_with_config(RpcContextConfig {
             commitment,
             min_context_slot,
@@ -2172,14 +2334,18 @@ impl JsonRpcRequestProcessor {
 }
 
 fn optimize_filters(filters: &mut [RpcFilterType]) {
-    filters.iter_mut().for_each(|filter_type| {
-        if let RpcFilterType::Memcmp(compare) = filter_type {
-            if let Err(err) = compare.convert_to_raw_bytes() {
-                // All filters should have been previously verified
-                warn!("Invalid filter: bytes could not be decoded, {err}");
+    filters
+        .iter_mut()
+        .for_each(|filter_type| match filter_type {
+            RpcFilterType::Memcmp(compare) => {
+                if let Err(err) = compare.convert_to_raw_bytes() {
+                    // All filters should have been previously verified
+                    warn!("Invalid filter: bytes could not be decoded, {err}");
+                }
             }
-        }
-    })
+            RpcFilterType::Or(filters) => optimize_filters(filters),
+            _ => {}
+        })
 }
 
 fn verify_transaction(
@@ -2210,6 +2376,15 @@ pub fn verify_pubkey(input: &str) -> Result<Pubkey> {
         .map_err(|e| Error::invalid_params(format!("Invalid param: {e:?}")))
 }
 
+pub(crate) fn verify_no_at_slot(at_slot: Option<Slot>) -> Result<()> {
+    if at_slot.is_some() {
+        return Err(Error::invalid_params(
+            "atSlot is only supported by getAccountInfo and getMultipleAccounts",
+        ));
+    }
+    Ok(())
+}
+
 fn verify_hash(input: &str) -> Result<Hash> {
     input
         .parse()
@@ -2270,10 +2445,11 @@ pub(crate) fn check_is_at_least_confirmed(commitment: CommitmentConfig) -> Resul
 fn get_encoded_account(
     bank: &Arc<Bank>,
     pubkey: &Pubkey,
+    account: Option<AccountSharedData>,
     encoding: UiAccountEncoding,
     data_slice: Option<UiDataSliceConfig>,
 ) -> Result<Option<UiAccount>> {
-    match bank.get_account(pubkey) {
+    match account {
         Some(account) => {
             let response = if is_known_spl_token_id(account.owner())
                 && encoding == UiAccountEncoding::JsonParsed
@@ -2454,6 +2630,24 @@ fn get_token_program_id_and_mint(
     }
 }
 
+/// Builds the Merkle inclusion proof of the message derived from `price_account`, if the
+/// accumulator commits to one.
+pub(crate) fn accumulator_proof(
+    accumulator: &Accumulator,
+    price_account: &Pubkey,
+) -> Option<RpcAccumulatorProof> {
+    let index = accumulator.messages.iter().position(|message| {
+        PriceFeedMessage::from_bytes(message).map(|message| message.feed_id) == Some(*price_account)
+    })?;
+    let proof = MerkleAccumulator::<Sha256>::new(&accumulator.messages).prove(index)?;
+    Some(RpcAccumulatorProof {
+        slot: accumulator.slot,
+        root: accumulator.root.to_string(),
+        message: base64::encode(&accumulator.messages[index]),
+        proof: base64::encode(proof.to_bytes()),
+    })
+}
+
 fn _send_transaction(
     meta: JsonRpcRequestProcessor,
     signature: Signature,
@@ -3333,6 +3527,14 @@ pub mod rpc_full {
             slot: Slot,
         ) -> BoxFuture<Result<Option<UnixTimestamp>>>;
 
+        #[rpc(meta, name = "getAccumulatorProof")]
+        fn get_accumulator_proof(
+            &self,
+            meta: Self::Metadata,
+            slot: Slot,
+            price_account_str: String,
+        ) -> BoxFuture<Result<Option<RpcAccumulatorProof>>>;
+
         #[rpc(meta, name = "getBlocks")]
         fn get_blocks(
             &self,
@@ -3853,6 +4055,23 @@ pub mod rpc_full {
             Box::pin(async move { meta.get_block_time(slot).await })
         }
 
+        fn get_accumulator_proof(
+            &self,
+            meta: Self::Metadata,
+            slot: Slot,
+            price_account_str: String,
+        ) -> BoxFuture<Result<Option<RpcAccumulatorProof>>> {
+            debug!(
+                "get_accumulator_proof rpc request received: {:?} {:?}",
+                slot, price_account_str
+            );
+            let price_account = match verify_pubkey(&price_account_str) {
+                Ok(price_account) => price_account,
+                Err(err) => return Box::pin(future::err(err)),
+            };
+            Box::pin(async move { meta.get_accumulator_proof(slot, &price_account).await })
+        }
+
         fn get_transaction(
             &self,
             meta: Self::Metadata,
@@ -4626,9 +4845,13 @@ pub mod tests {
             blockstore_processor::fill_blockstore_slot_with_ticks,
             genesis_utils::{create_genesis_config, GenesisConfigInfo},
         },
+        solana_merkle_tree::MerkleProof,
         solana_rpc_client_api::{
             custom_error::{
-                JSON_RPC_SERVER_ERROR_BLOCK_NOT_AVAILABLE,
+                JSON_RPC_SERVER_ERROR_ACCOUNT_HISTORY_NOT_AVAILABLE,
+                JSON_RPC_SERVER_ERROR_BLOCK_CLEANED_UP, JSON_RPC_SERVER_ERROR_BLOCK_NOT_AVAILABLE,
+                JSON_RPC_SERVER_ERROR_BLOCK_STATUS_NOT_AVAILABLE_YET,
+                JSON_RPC_SERVER_ERROR_SLOT_SKIPPED,
                 JSON_RPC_SERVER_ERROR_TRANSACTION_HISTORY_NOT_AVAILABLE,
                 JSON_RPC_SERVER_ERROR_UNSUPPORTED_TRANSACTION_VERSION,
             },
@@ -4651,6 +4874,7 @@ pub mod tests {
                 Message, MessageHeader, VersionedMessage,
             },
             nonce::{self, state::DurableNonce},
+            pyth_oracle::{self, AccountHeader, AccountType, PriceAccount, PriceInfo},
             rpc_port,
             signature::{Keypair, Signer},
             slot_hashes::SlotHashes,
@@ -5543,6 +5767,109 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_rpc_get_account_info_at_slot() {
+        let owner = Pubkey::new_unique();
+        let rpc = RpcHandler::start_with_config(JsonRpcConfig {
+            enable_rpc_transaction_history: true,
+            account_history_owners: [owner].into_iter().collect(),
+            ..JsonRpcConfig::default()
+        });
+        let pubkey = Pubkey::new_unique();
+        let address = pubkey.to_string();
+        let account = |lamports| Account {
+            lamports,
+            owner,
+            ..Account::default()
+        };
+        let created_later = Pubkey::new_unique();
+        rpc.blockstore
+            .write_account_history(1, &[(pubkey, account(42))], &[pubkey])
+            .unwrap();
+        // the account is closed in slot 3, where another one is created
+        rpc.blockstore
+            .write_account_history(
+                3,
+                &[(pubkey, Account::default()), (created_later, account(7))],
+                &[created_later],
+            )
+            .unwrap();
+        rpc.blockstore.set_roots([1, 3].iter()).unwrap();
+        rpc.block_commitment_cache
+            .write()
+            .unwrap()
+            .set_highest_confirmed_root(3);
+
+        let request = create_test_request(
+            "getAccountInfo",
+            Some(json!([address, {"encoding": "base64", "atSlot": 2}])),
+        );
+        let result: Value = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result["context"]["slot"], 2);
+        assert_eq!(result["value"]["lamports"], 42);
+        assert_eq!(result["value"]["owner"], owner.to_string());
+
+        let request = create_test_request(
+            "getMultipleAccounts",
+            Some(json!([[address, Pubkey::new_unique().to_string()], {"atSlot": 3}])),
+        );
+        let result: Value = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result["context"]["slot"], 3);
+        assert_eq!(result["value"], json!([null, null]));
+
+        // the account did not exist before the slot it was created in
+        let request = create_test_request(
+            "getAccountInfo",
+            Some(json!([created_later.to_string(), {"atSlot": 2}])),
+        );
+        let result: Value = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result["value"], Value::Null);
+
+        // an account that was not written since the history started has no recorded state
+        let untouched = Pubkey::new_unique();
+        rpc.working_bank()
+            .store_account(&untouched, &AccountSharedData::new(1, 0, &owner));
+        let request = create_test_request(
+            "getAccountInfo",
+            Some(json!([untouched.to_string(), {"atSlot": 2}])),
+        );
+        let (code, _) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, JSON_RPC_SERVER_ERROR_ACCOUNT_HISTORY_NOT_AVAILABLE);
+
+        let request = create_test_request("getAccountInfo", Some(json!([address, {"atSlot": 4}])));
+        let (code, _) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, JSON_RPC_SERVER_ERROR_BLOCK_STATUS_NOT_AVAILABLE_YET);
+
+        // the account history starts in slot 1
+        let request = create_test_request("getAccountInfo", Some(json!([address, {"atSlot": 0}])));
+        let (code, _) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, JSON_RPC_SERVER_ERROR_BLOCK_CLEANED_UP);
+
+        // the mint is owned by the system program, whose accounts are not recorded
+        let request = create_test_request(
+            "getAccountInfo",
+            Some(json!([rpc.mint_keypair.pubkey().to_string(), {"atSlot": 2}])),
+        );
+        let (code, _) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, JSON_RPC_SERVER_ERROR_ACCOUNT_HISTORY_NOT_AVAILABLE);
+
+        let request = create_test_request(
+            "getProgramAccounts",
+            Some(json!([owner.to_string(), {"atSlot": 2}])),
+        );
+        let (code, _) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidParams.code());
+
+        let rpc = RpcHandler::start();
+        let request = create_test_request("getAccountInfo", Some(json!([address, {"atSlot": 2}])));
+        let response = parse_failure_response(rpc.handle_request_sync(request));
+        let expected = (
+            JSON_RPC_SERVER_ERROR_ACCOUNT_HISTORY_NOT_AVAILABLE,
+            String::from("Account history is not available from this node"),
+        );
+        assert_eq!(response, expected);
+    }
+
     #[test]
     fn test_rpc_get_multiple_accounts() {
         let rpc = RpcHandler::start();
@@ -5809,6 +6136,77 @@ pub mod tests {
         );
         let result: Vec<RpcKeyedAccount> = parse_success_result(rpc.handle_request_sync(request));
         assert_eq!(result.len(), 0);
+
+        // Test intCmp filter; filter on the lamports per signature of the nonce
+        let request = create_test_request(
+            "getProgramAccounts",
+            Some(json!([
+                system_program::id().to_string(),
+                {"filters": [{
+                    "intCmp": {"offset": 72, "type": "u64", "op": "eq", "value": 1000},
+                }]},
+            ])),
+        );
+        let result: Vec<RpcKeyedAccount> = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.len(), 2);
+
+        let request = create_test_request(
+            "getProgramAccounts",
+            Some(json!([
+                system_program::id().to_string(),
+                {"filters": [{
+                    "intCmp": {"offset": 72, "type": "u64", "op": "gt", "value": 1000},
+                }]},
+            ])),
+        );
+        let result: Vec<RpcKeyedAccount> = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.len(), 0);
+
+        // Test or filter
+        let request = create_test_request(
+            "getProgramAccounts",
+            Some(json!([
+                system_program::id().to_string(),
+                {"filters": [{"or": [{
+                    "memcmp": {
+                        "offset": 8,
+                        "bytes": nonce_authorities[0].to_string(),
+                    },
+                }, {
+                    "memcmp": {
+                        "offset": 8,
+                        "bytes": nonce_authorities[1].to_string(),
+                    },
+                }]}]}, // Filter on either Nonce authority
+            ])),
+        );
+        let result: Vec<RpcKeyedAccount> = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.len(), 2);
+
+        let request = create_test_request(
+            "getProgramAccounts",
+            Some(json!([
+                system_program::id().to_string(),
+                {"filters": [{"or": [{
+                    "intCmp": {"offset": 4, "type": "u32", "op": "eq", "value": 0},
+                }, {
+                    "dataSize": 1,
+                }]}]}, // Filter on Uninitialized or non-matching data size
+            ])),
+        );
+        let result: Vec<RpcKeyedAccount> = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.len(), 0);
+
+        // Invalid or filter
+        let request = create_test_request(
+            "getProgramAccounts",
+            Some(json!([
+                system_program::id().to_string(),
+                {"filters": [{"or": []}]},
+            ])),
+        );
+        let (code, _) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidParams.code());
     }
 
     #[test]
@@ -7128,6 +7526,141 @@ pub mod tests {
         assert_eq!(response, expected);
     }
 
+    #[test]
+    fn test_get_accumulator_proof() {
+        let rpc = RpcHandler::start();
+        let parent_bank = rpc.working_bank();
+        let bank = Bank::new_from_parent(&parent_bank, parent_bank.collector_id(), 1);
+
+        let price_accounts = [Pubkey::new_unique(), Pubkey::new_unique()];
+        for (i, price_account) in price_accounts.iter().enumerate() {
+            let price = PriceAccount {
+                header: AccountHeader {
+                    magic: pyth_oracle::MAGIC,
+                    version: pyth_oracle::VERSION,
+                    account_type: AccountType::Price as u32,
+                    size: std::mem::size_of::<PriceAccount>() as u32,
+                },
+                agg: PriceInfo {
+                    price: i as i64,
+                    ..PriceInfo::default()
+                },
+                ..PriceAccount::default()
+            };
+            let mut account = AccountSharedData::new(TEST_MINT_LAMPORTS, 0, &pyth_oracle::id());
+            account.set_data(bytemuck::bytes_of(&price).to_vec());
+            bank.store_account(price_account, &account);
+        }
+        bank.freeze();
+        let accumulator = bank.get_accumulator().unwrap();
+        rpc.bank_forks.write().unwrap().insert(bank);
+
+        for price_account in price_accounts {
+            let request = create_test_request(
+                "getAccumulatorProof",
+                Some(json!([1u64, price_account.to_string()])),
+            );
+            let result: Option<RpcAccumulatorProof> =
+                parse_success_result(rpc.handle_request_sync(request));
+            let result = result.unwrap();
+            assert_eq!(result.slot, 1);
+            assert_eq!(result.root, accumulator.root.to_string());
+
+            let message = base64::decode(result.message).unwrap();
+            assert_eq!(
+                PriceFeedMessage::from_bytes(&message).unwrap().feed_id,
+                price_account
+            );
+            let proof =
+                MerkleProof::<Sha256>::from_bytes(&base64::decode(result.proof).unwrap()).unwrap();
+            assert!(proof.verify(
+                &accumulator.root.to_bytes(),
+                accumulator.messages.len(),
+                &message
+            ));
+        }
+
+        let request = create_test_request(
+            "getAccumulatorProof",
+            Some(json!([1u64, Pubkey::new_unique().to_string()])),
+        );
+        let result: Option<RpcAccumulatorProof> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, None);
+
+        let request = create_test_request(
+            "getAccumulatorProof",
+            Some(json!([12345u64, Pubkey::new_unique().to_string()])),
+        );
+        let response = parse_failure_response(rpc.handle_request_sync(request));
+        let expected = (
+            JSON_RPC_SERVER_ERROR_BLOCK_NOT_AVAILABLE,
+            String::from("Block not available for slot 12345"),
+        );
+        assert_eq!(response, expected);
+    }
+
+    #[test]
+    fn test_get_accumulator_proof_from_blockstore() {
+        let rpc = RpcHandler::start();
+        let price_account = Pubkey::new_unique();
+        let accumulator = |slot: Slot| {
+            let messages = vec![PriceFeedMessage {
+                feed_id: price_account,
+                price: slot as i64,
+                ..PriceFeedMessage::default()
+            }
+            .to_bytes()];
+            Accumulator {
+                slot,
+                root: Hash::new_from_array(
+                    *MerkleAccumulator::<Sha256>::new(&messages).root().unwrap(),
+                ),
+                messages,
+            }
+        };
+
+        // Slots past the banks of the handler, so that only the blockstore has them. Slot 12 was
+        // frozen on a fork that was abandoned.
+        rpc.blockstore.set_roots([10, 11, 13].iter()).unwrap();
+        for slot in [10, 12] {
+            rpc.blockstore
+                .cache_accumulator(slot, &accumulator(slot))
+                .unwrap();
+        }
+        rpc.block_commitment_cache
+            .write()
+            .unwrap()
+            .set_highest_confirmed_root(13);
+        let get_accumulator_proof = |slot: Slot| {
+            let request = create_test_request(
+                "getAccumulatorProof",
+                Some(json!([slot, price_account.to_string()])),
+            );
+            rpc.handle_request_sync(request)
+        };
+
+        let result: Option<RpcAccumulatorProof> = parse_success_result(get_accumulator_proof(10));
+        let result = result.unwrap();
+        assert_eq!(result.slot, 10);
+        assert_eq!(result.root, accumulator(10).root.to_string());
+
+        // Rooted, but no accumulator was cached
+        let result: Option<RpcAccumulatorProof> = parse_success_result(get_accumulator_proof(11));
+        assert_eq!(result, None);
+
+        let response = parse_failure_response(get_accumulator_proof(12));
+        let expected = (
+            JSON_RPC_SERVER_ERROR_SLOT_SKIPPED,
+            String::from("Slot 12 was skipped, or missing due to ledger jump to recent snapshot"),
+        );
+        assert_eq!(response, expected);
+
+        *rpc.blockstore.lowest_cleanup_slot.write().unwrap() = 10;
+        let (code, _message) = parse_failure_response(get_accumulator_proof(10));
+        assert_eq!(code, JSON_RPC_SERVER_ERROR_BLOCK_CLEANED_UP);
+    }
+
     #[test]
     fn test_get_vote_accounts() {
         let rpc = RpcHandler::start();
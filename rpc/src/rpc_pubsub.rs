@@ -3,13 +3,13 @@
 use crate::{rpc_pubsub_service, rpc_subscriptions::RpcSubscriptions};
 use {
     crate::{
-        rpc::check_is_at_least_confirmed,
+        rpc::{check_is_at_least_confirmed, verify_no_at_slot},
         rpc_pubsub_service::PubSubConfig,
         rpc_subscription_tracker::{
-            AccountSubscriptionParams, BlockSubscriptionKind, BlockSubscriptionParams,
-            LogsSubscriptionKind, LogsSubscriptionParams, ProgramSubscriptionParams,
-            SignatureSubscriptionParams, SubscriptionControl, SubscriptionId, SubscriptionParams,
-            SubscriptionToken,
+            AccountSubscriptionParams, AccumulatorSubscriptionParams, BlockSubscriptionKind,
+            BlockSubscriptionParams, LogsSubscriptionKind, LogsSubscriptionParams,
+            ProgramSubscriptionParams, SignatureSubscriptionParams, SubscriptionControl,
+            SubscriptionId, SubscriptionParams, SubscriptionToken,
         },
     },
     dashmap::DashMap,
@@ -19,13 +19,13 @@ use {
     solana_account_decoder::{UiAccount, UiAccountEncoding},
     solana_rpc_client_api::{
         config::{
-            RpcAccountInfoConfig, RpcBlockSubscribeConfig, RpcBlockSubscribeFilter,
-            RpcProgramAccountsConfig, RpcSignatureSubscribeConfig, RpcTransactionLogsConfig,
-            RpcTransactionLogsFilter,
+            RpcAccountInfoConfig, RpcAccumulatorSubscribeConfig, RpcBlockSubscribeConfig,
+            RpcBlockSubscribeFilter, RpcProgramAccountsConfig, RpcSignatureSubscribeConfig,
+            RpcTransactionLogsConfig, RpcTransactionLogsFilter,
         },
         response::{
-            Response as RpcResponse, RpcBlockUpdate, RpcKeyedAccount, RpcLogsResponse,
-            RpcSignatureResult, RpcVersionInfo, RpcVote, SlotInfo, SlotUpdate,
+            Response as RpcResponse, RpcAccumulatorUpdate, RpcBlockUpdate, RpcKeyedAccount,
+            RpcLogsResponse, RpcSignatureResult, RpcVersionInfo, RpcVote, SlotInfo, SlotUpdate,
         },
     },
     solana_sdk::{clock::Slot, pubkey::Pubkey, signature::Signature},
@@ -245,6 +245,31 @@ pub trait RpcSolPubSub {
         meta: Option<Self::Metadata>,
         id: PubSubSubscriptionId,
     ) -> Result<bool>;
+
+    // Get notification when a new accumulator root is committed
+    #[pubsub(
+        subscription = "accumulatorNotification",
+        subscribe,
+        name = "accumulatorSubscribe"
+    )]
+    fn accumulator_subscribe(
+        &self,
+        meta: Self::Metadata,
+        subscriber: Subscriber<RpcResponse<RpcAccumulatorUpdate>>,
+        config: Option<RpcAccumulatorSubscribeConfig>,
+    );
+
+    // Unsubscribe from accumulator notification subscription.
+    #[pubsub(
+        subscription = "accumulatorNotification",
+        unsubscribe,
+        name = "accumulatorUnsubscribe"
+    )]
+    fn accumulator_unsubscribe(
+        &self,
+        meta: Option<Self::Metadata>,
+        id: PubSubSubscriptionId,
+    ) -> Result<bool>;
 }
 
 pub use internal::RpcSolPubSubInternal;
@@ -350,6 +375,17 @@ mod internal {
         #[rpc(name = "rootUnsubscribe")]
         fn root_unsubscribe(&self, id: SubscriptionId) -> Result<bool>;
 
+        // Get notification when a new accumulator root is committed
+        #[rpc(name = "accumulatorSubscribe")]
+        fn accumulator_subscribe(
+            &self,
+            config: Option<RpcAccumulatorSubscribeConfig>,
+        ) -> Result<SubscriptionId>;
+
+        // Unsubscribe from accumulator notification subscription.
+        #[rpc(name = "accumulatorUnsubscribe")]
+        fn accumulator_unsubscribe(&self, id: SubscriptionId) -> Result<bool>;
+
         // Get the current solana version running on the node
         #[rpc(name = "getVersion")]
         fn get_version(&self) -> Result<RpcVersionInfo>;
@@ -430,7 +466,9 @@ impl RpcSolPubSubInternal for RpcSolPubSubImpl {
             data_slice,
             commitment,
             min_context_slot: _, // ignored
+            at_slot,
         } = config.unwrap_or_default();
+        verify_no_at_slot(at_slot)?;
         let params = AccountSubscriptionParams {
             pubkey: param::<Pubkey>(&pubkey_str, "pubkey")?,
             commitment: commitment.unwrap_or_default(),
@@ -450,6 +488,7 @@ impl RpcSolPubSubInternal for RpcSolPubSubImpl {
         config: Option<RpcProgramAccountsConfig>,
     ) -> Result<SubscriptionId> {
         let config = config.unwrap_or_default();
+        verify_no_at_slot(config.account_config.at_slot)?;
         let params = ProgramSubscriptionParams {
             pubkey: param::<Pubkey>(&pubkey_str, "pubkey")?,
             filters: config.filters.unwrap_or_default(),
@@ -590,6 +629,20 @@ impl RpcSolPubSubInternal for RpcSolPubSubImpl {
         self.unsubscribe(id)
     }
 
+    fn accumulator_subscribe(
+        &self,
+        config: Option<RpcAccumulatorSubscribeConfig>,
+    ) -> Result<SubscriptionId> {
+        let params = AccumulatorSubscriptionParams {
+            commitment: config.and_then(|c| c.commitment).unwrap_or_default(),
+        };
+        self.subscribe(SubscriptionParams::Accumulator(params))
+    }
+
+    fn accumulator_unsubscribe(&self, id: SubscriptionId) -> Result<bool> {
+        self.unsubscribe(id)
+    }
+
     fn get_version(&self) -> Result<RpcVersionInfo> {
         let version = solana_version::Version::default();
         Ok(RpcVersionInfo {
@@ -892,6 +945,7 @@ mod tests {
                 encoding: Some(encoding),
                 data_slice: None,
                 min_context_slot: None,
+                at_slot: None,
             }),
         )
         .unwrap();
@@ -1016,6 +1070,7 @@ mod tests {
                 encoding: Some(UiAccountEncoding::JsonParsed),
                 data_slice: None,
                 min_context_slot: None,
+                at_slot: None,
             }),
         )
         .unwrap();
@@ -1117,6 +1172,44 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    #[serial]
+    fn test_account_subscribe_at_slot() {
+        let bob_pubkey = solana_sdk::pubkey::new_rand();
+
+        let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(10_000);
+        let bank_forks = Arc::new(RwLock::new(BankForks::new(Bank::new_for_tests(
+            &genesis_config,
+        ))));
+
+        let mut io = IoHandler::<()>::default();
+        let max_complete_transaction_status_slot = Arc::new(AtomicU64::default());
+        let subscriptions = Arc::new(RpcSubscriptions::default_with_bank_forks(
+            max_complete_transaction_status_slot,
+            bank_forks,
+        ));
+        let (rpc, _receiver) = rpc_pubsub_service::test_connection(&subscriptions);
+
+        io.extend_with(rpc.to_delegate());
+
+        let expected = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"atSlot is only supported by getAccountInfo and getMultipleAccounts"},"id":1}"#;
+        let expected: Response = serde_json::from_str(expected).unwrap();
+
+        let req = format!(
+            r#"{{"jsonrpc":"2.0","id":1,"method":"accountSubscribe","params":["{bob_pubkey}",{{"atSlot":1}}]}}"#
+        );
+        let res = io.handle_request_sync(&req);
+        let result: Response = serde_json::from_str(&res.unwrap()).unwrap();
+        assert_eq!(result, expected);
+
+        let req = format!(
+            r#"{{"jsonrpc":"2.0","id":1,"method":"programSubscribe","params":["{bob_pubkey}",{{"atSlot":1}}]}}"#
+        );
+        let res = io.handle_request_sync(&req);
+        let result: Response = serde_json::from_str(&res.unwrap()).unwrap();
+        assert_eq!(result, expected);
+    }
+
     #[test]
     #[should_panic]
     fn test_account_commitment_not_fulfilled() {
@@ -1149,6 +1242,7 @@ mod tests {
                 encoding: None,
                 data_slice: None,
                 min_context_slot: None,
+                at_slot: None,
             }),
         )
         .unwrap();
@@ -1202,6 +1296,7 @@ mod tests {
                 encoding: None,
                 data_slice: None,
                 min_context_slot: None,
+                at_slot: None,
             }),
         )
         .unwrap();
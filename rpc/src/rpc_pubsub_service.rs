@@ -124,6 +124,9 @@ fn count_final(params: &SubscriptionParams) {
         SubscriptionParams::Account(_) => {
             inc_new_counter_info!("rpc-pubsub-final-accounts", 1);
         }
+        SubscriptionParams::Accumulator(_) => {
+            inc_new_counter_info!("rpc-pubsub-final-accumulators", 1);
+        }
         SubscriptionParams::Logs(_) => {
             inc_new_counter_info!("rpc-pubsub-final-logs", 1);
         }
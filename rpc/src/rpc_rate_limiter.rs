@@ -0,0 +1,580 @@
+//! Per-client rate limiting and method quotas of the JSON RPC service.
+//!
+//! Every call spends the weight of its method from a token bucket of the
+//! calling client, which refills at a configured rate. Clients presenting a
+//! configured API key spend from the bucket of their key, all others from the
+//! bucket of their IP address. The address of a client is the peer address of
+//! its connection, or the address forwarded by the reverse proxy in front of
+//! the node if that proxy is trusted.
+//!
+//! Independently of the caller, each method may have a limited number of calls
+//! running at once, so that a few expensive account scans cannot occupy every
+//! RPC thread.
+
+use {
+    crate::rpc::JsonRpcRequestProcessor,
+    jsonrpc_core::{
+        futures::future,
+        futures_util::future::Either,
+        middleware::{Middleware, NoopCallFuture, NoopFuture},
+        Call, Failure, Output,
+    },
+    jsonrpc_http_server::hyper::HeaderMap,
+    solana_rpc_client_api::custom_error::RpcCustomError,
+    std::{
+        collections::{HashMap, HashSet},
+        future::Future,
+        net::IpAddr,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+        time::{Duration, Instant},
+    },
+};
+
+pub const DEFAULT_CLIENT_IP_HEADER: &str = "x-forwarded-for";
+pub const DEFAULT_API_KEY_HEADER: &str = "x-api-key";
+
+/// Number of tracked clients above which the buckets of idle clients are
+/// dropped
+const MAX_TRACKED_CLIENTS: usize = 100_000;
+
+/// Share of the tracked clients dropped, least recently seen first, when
+/// dropping the idle ones is not enough to stay below `MAX_TRACKED_CLIENTS`
+const EVICTED_CLIENTS: usize = MAX_TRACKED_CLIENTS / 4;
+
+/// Default quotas of the methods that are expensive to serve, as
+/// (method, weight, max concurrent calls). Other methods weigh 1.
+const DEFAULT_METHOD_QUOTAS: &[(&str, u64, Option<usize>)] = &[
+    ("getProgramAccounts", 100, Some(8)),
+    ("getLargestAccounts", 100, Some(2)),
+    ("getSupply", 50, Some(2)),
+    ("getTokenLargestAccounts", 50, Some(4)),
+    ("getTokenAccountsByOwner", 20, Some(16)),
+    ("getTokenAccountsByDelegate", 20, Some(16)),
+    ("getSignaturesForAddress", 10, None),
+    ("getConfirmedSignaturesForAddress2", 10, None),
+    ("getBlock", 10, None),
+    ("getConfirmedBlock", 10, None),
+    ("getBlocks", 5, None),
+    ("getBlocksWithLimit", 5, None),
+    ("getTransaction", 5, None),
+    ("getConfirmedTransaction", 5, None),
+    ("getMultipleAccounts", 5, None),
+    ("simulateTransaction", 5, None),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Weight units added to the bucket every second
+    pub units_per_second: u64,
+    /// Weight units the bucket holds when full, i.e. the largest burst of
+    /// calls allowed
+    pub burst: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MethodQuota {
+    /// Weight units spent by every call of the method
+    pub weight: u64,
+    /// Calls of the method allowed to run at once, across all clients
+    pub max_concurrent: Option<usize>,
+}
+
+impl Default for MethodQuota {
+    fn default() -> Self {
+        Self {
+            weight: 1,
+            max_concurrent: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RpcRateLimitConfig {
+    /// Limit of every client IP address, if any
+    pub ip_rate_limit: Option<RateLimit>,
+    /// Limits of the known API keys. Clients presenting one of these keys
+    /// are limited by the key rather than by their IP address.
+    pub api_key_rate_limits: HashMap<String, RateLimit>,
+    /// Addresses of the reverse proxies in front of the node. Only requests
+    /// coming from one of them are identified by `client_ip_header`, all
+    /// others by their peer address.
+    pub trusted_proxies: HashSet<IpAddr>,
+    /// Header holding the client IP address, as appended by the trusted
+    /// reverse proxies
+    pub client_ip_header: String,
+    /// Header holding the API key of the client
+    pub api_key_header: String,
+    /// Quotas of the methods, overriding the defaults
+    pub method_quotas: HashMap<String, MethodQuota>,
+}
+
+impl Default for RpcRateLimitConfig {
+    fn default() -> Self {
+        Self {
+            ip_rate_limit: None,
+            api_key_rate_limits: HashMap::new(),
+            trusted_proxies: HashSet::new(),
+            client_ip_header: DEFAULT_CLIENT_IP_HEADER.to_string(),
+            api_key_header: DEFAULT_API_KEY_HEADER.to_string(),
+            method_quotas: HashMap::new(),
+        }
+    }
+}
+
+/// Client of a request, as far as rate limiting is concerned.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RpcClientId {
+    ApiKey(String),
+    Ip(IpAddr),
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+    last_take: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: &RateLimit, now: Instant) -> Self {
+        Self {
+            tokens: limit.burst as f64,
+            last_refill: now,
+            last_take: now,
+        }
+    }
+
+    fn refill(&mut self, limit: &RateLimit, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * limit.units_per_second as f64)
+            .min(limit.burst as f64);
+        self.last_refill = now;
+    }
+
+    /// Take `weight` units from the bucket, or return how long it takes until
+    /// they are available. Calls weighing more than a full bucket only need a
+    /// full bucket.
+    fn take(&mut self, limit: &RateLimit, weight: u64, now: Instant) -> Result<(), Duration> {
+        self.refill(limit, now);
+        self.last_take = now;
+        let weight = weight.min(limit.burst) as f64;
+        if self.tokens >= weight {
+            self.tokens -= weight;
+            Ok(())
+        } else if limit.units_per_second == 0 {
+            Err(Duration::MAX)
+        } else {
+            Err(Duration::from_secs_f64(
+                (weight - self.tokens) / limit.units_per_second as f64,
+            ))
+        }
+    }
+
+    fn is_full(&mut self, limit: &RateLimit, now: Instant) -> bool {
+        self.refill(limit, now);
+        self.tokens >= limit.burst as f64
+    }
+}
+
+/// A call counted against the concurrency limit of its method until dropped.
+#[derive(Debug)]
+pub struct RunningCall(Arc<AtomicUsize>);
+
+impl Drop for RunningCall {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[derive(Debug)]
+pub struct RpcRateLimiter {
+    config: RpcRateLimitConfig,
+    method_quotas: HashMap<String, MethodQuota>,
+    running_calls: HashMap<String, Arc<AtomicUsize>>,
+    buckets: Mutex<HashMap<RpcClientId, TokenBucket>>,
+}
+
+impl RpcRateLimiter {
+    pub fn new(config: RpcRateLimitConfig) -> Self {
+        let mut method_quotas: HashMap<_, _> = DEFAULT_METHOD_QUOTAS
+            .iter()
+            .map(|(method, weight, max_concurrent)| {
+                (
+                    method.to_string(),
+                    MethodQuota {
+                        weight: *weight,
+                        max_concurrent: *max_concurrent,
+                    },
+                )
+            })
+            .collect();
+        method_quotas.extend(config.method_quotas.clone());
+        let running_calls = method_quotas
+            .iter()
+            .filter(|(_, quota)| quota.max_concurrent.is_some())
+            .map(|(method, _)| (method.clone(), Arc::default()))
+            .collect();
+        Self {
+            config,
+            method_quotas,
+            running_calls,
+            buckets: Mutex::default(),
+        }
+    }
+
+    /// Identify the client of a request from its headers and the address of
+    /// its peer.
+    pub fn client_id(&self, headers: &HeaderMap, peer_addr: IpAddr) -> RpcClientId {
+        let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+        if let Some(api_key) = header(&self.config.api_key_header) {
+            if self.config.api_key_rate_limits.contains_key(api_key) {
+                return RpcClientId::ApiKey(api_key.to_string());
+            }
+        }
+        // every trusted proxy appends the address of its own peer, so the client is the last
+        // address that is not one of them
+        let mut client_addr = peer_addr;
+        if self.config.trusted_proxies.contains(&peer_addr) {
+            let forwarded_addrs = header(&self.config.client_ip_header)
+                .into_iter()
+                .flat_map(|addresses| addresses.rsplit(','))
+                .map_while(|address| address.trim().parse().ok());
+            for forwarded_addr in forwarded_addrs {
+                client_addr = forwarded_addr;
+                if !self.config.trusted_proxies.contains(&forwarded_addr) {
+                    break;
+                }
+            }
+        }
+        RpcClientId::Ip(client_addr)
+    }
+
+    pub fn method_quota(&self, method: &str) -> MethodQuota {
+        self.method_quotas.get(method).copied().unwrap_or_default()
+    }
+
+    fn rate_limit(&self, client: &RpcClientId) -> Option<&RateLimit> {
+        match client {
+            RpcClientId::ApiKey(api_key) => self.config.api_key_rate_limits.get(api_key),
+            RpcClientId::Ip(_) => self.config.ip_rate_limit.as_ref(),
+        }
+    }
+
+    fn take(&self, client: &RpcClientId, weight: u64, now: Instant) -> Result<(), Duration> {
+        let Some(limit) = self.rate_limit(client) else {
+            return Ok(());
+        };
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() >= MAX_TRACKED_CLIENTS && !buckets.contains_key(client) {
+            buckets.retain(|client, bucket| {
+                self.rate_limit(client)
+                    .map(|limit| !bucket.is_full(limit, now))
+                    .unwrap_or(false)
+            });
+        }
+        if buckets.len() >= MAX_TRACKED_CLIENTS && !buckets.contains_key(client) {
+            let mut clients: Vec<_> = buckets
+                .iter()
+                .map(|(client, bucket)| (bucket.last_take, client.clone()))
+                .collect();
+            clients.select_nth_unstable_by_key(EVICTED_CLIENTS, |(last_take, _)| *last_take);
+            for (_, client) in &clients[..EVICTED_CLIENTS] {
+                buckets.remove(client);
+            }
+        }
+        buckets
+            .entry(client.clone())
+            .or_insert_with(|| TokenBucket::new(limit, now))
+            .take(limit, weight, now)
+    }
+
+    /// Admit a call of `method` by `client`, which holds its place among the
+    /// running calls of the method until the returned guard is dropped.
+    pub fn check_call(
+        &self,
+        client: Option<&RpcClientId>,
+        method: &str,
+    ) -> Result<Option<RunningCall>, RpcCustomError> {
+        self.check_call_at(client, method, Instant::now())
+    }
+
+    fn check_call_at(
+        &self,
+        client: Option<&RpcClientId>,
+        method: &str,
+        now: Instant,
+    ) -> Result<Option<RunningCall>, RpcCustomError> {
+        let quota = self.method_quota(method);
+        let running_call = match (quota.max_concurrent, self.running_calls.get(method)) {
+            (Some(max_concurrent), Some(running_calls)) => {
+                let running_call = RunningCall(running_calls.clone());
+                if running_calls.fetch_add(1, Ordering::Relaxed) >= max_concurrent {
+                    inc_new_counter_info!("rpc-method-concurrency-limited", 1);
+                    return Err(RpcCustomError::MethodConcurrencyLimitExceeded {
+                        method: method.to_string(),
+                    });
+                }
+                Some(running_call)
+            }
+            _ => None,
+        };
+        if let Some(client) = client {
+            self.take(client, quota.weight, now)
+                .map_err(|retry_after| {
+                    inc_new_counter_info!("rpc-rate-limited", 1);
+                    RpcCustomError::RateLimitExceeded {
+                        method: method.to_string(),
+                        retry_after_ms: (retry_after.as_secs_f64() * 1000.0).ceil() as u64,
+                    }
+                })?;
+        }
+        Ok(running_call)
+    }
+}
+
+/// Applies the rate limiter, if any, to every call handled by the JSON RPC
+/// service.
+#[derive(Debug, Clone, Default)]
+pub struct RpcRateLimitMiddleware(pub Option<Arc<RpcRateLimiter>>);
+
+impl Middleware<JsonRpcRequestProcessor> for RpcRateLimitMiddleware {
+    type Future = NoopFuture;
+    type CallFuture = NoopCallFuture;
+
+    fn on_call<F, X>(
+        &self,
+        call: Call,
+        meta: JsonRpcRequestProcessor,
+        next: F,
+    ) -> Either<Self::CallFuture, X>
+    where
+        F: Fn(Call, JsonRpcRequestProcessor) -> X + Send + Sync,
+        X: Future<Output = Option<Output>> + Send + 'static,
+    {
+        let Some(rate_limiter) = &self.0 else {
+            return Either::Right(next(call, meta));
+        };
+        let (method, jsonrpc, id) = match &call {
+            Call::MethodCall(method_call) => (
+                &method_call.method,
+                method_call.jsonrpc,
+                Some(method_call.id.clone()),
+            ),
+            Call::Notification(notification) => (&notification.method, notification.jsonrpc, None),
+            Call::Invalid { .. } => return Either::Right(next(call, meta)),
+        };
+        match rate_limiter.check_call(meta.client(), method) {
+            Ok(None) => Either::Right(next(call, meta)),
+            Ok(Some(running_call)) => {
+                let output = next(call, meta);
+                Either::Left(Box::pin(async move {
+                    let output = output.await;
+                    drop(running_call);
+                    output
+                }))
+            }
+            Err(err) => {
+                debug!("rejected {} call from {:?}: {}", method, meta.client(), err);
+                let output = id.map(|id| {
+                    Output::Failure(Failure {
+                        jsonrpc,
+                        error: err.into(),
+                        id,
+                    })
+                });
+                Either::Left(Box::pin(future::ready(output)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, jsonrpc_http_server::hyper::header::HeaderValue, std::net::Ipv4Addr};
+
+    fn rate_limiter() -> RpcRateLimiter {
+        RpcRateLimiter::new(RpcRateLimitConfig {
+            ip_rate_limit: Some(RateLimit {
+                units_per_second: 10,
+                burst: 100,
+            }),
+            api_key_rate_limits: HashMap::from([(
+                "key".to_string(),
+                RateLimit {
+                    units_per_second: 1_000,
+                    burst: 1_000,
+                },
+            )]),
+            trusted_proxies: HashSet::from(["10.0.0.1".parse().unwrap()]),
+            method_quotas: HashMap::from([(
+                "getBalance".to_string(),
+                MethodQuota {
+                    weight: 2,
+                    max_concurrent: Some(1),
+                },
+            )]),
+            ..RpcRateLimitConfig::default()
+        })
+    }
+
+    #[test]
+    fn test_client_id() {
+        let rate_limiter = rate_limiter();
+        let peer: IpAddr = "1.2.3.4".parse().unwrap();
+        let proxy: IpAddr = "10.0.0.1".parse().unwrap();
+        let client: IpAddr = "2.2.2.2".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        assert_eq!(
+            rate_limiter.client_id(&headers, peer),
+            RpcClientId::Ip(peer)
+        );
+        assert_eq!(
+            rate_limiter.client_id(&headers, proxy),
+            RpcClientId::Ip(proxy)
+        );
+
+        // the forwarded address is only used for requests of a trusted proxy, and stops at
+        // the first address that is not a trusted proxy
+        headers.insert(
+            DEFAULT_CLIENT_IP_HEADER,
+            HeaderValue::from_static("1.1.1.1, 2.2.2.2, 10.0.0.1"),
+        );
+        assert_eq!(
+            rate_limiter.client_id(&headers, peer),
+            RpcClientId::Ip(peer)
+        );
+        assert_eq!(
+            rate_limiter.client_id(&headers, proxy),
+            RpcClientId::Ip(client)
+        );
+
+        // unknown keys are limited by IP address
+        headers.insert(DEFAULT_API_KEY_HEADER, HeaderValue::from_static("other"));
+        assert_eq!(
+            rate_limiter.client_id(&headers, proxy),
+            RpcClientId::Ip(client)
+        );
+        headers.insert(DEFAULT_API_KEY_HEADER, HeaderValue::from_static("key"));
+        assert_eq!(
+            rate_limiter.client_id(&headers, peer),
+            RpcClientId::ApiKey("key".to_string())
+        );
+    }
+
+    #[test]
+    fn test_token_bucket() {
+        let rate_limiter = rate_limiter();
+        let now = Instant::now();
+        let client = RpcClientId::Ip("10.0.0.1".parse().unwrap());
+        let other_client = RpcClientId::Ip("10.0.0.2".parse().unwrap());
+
+        // getProgramAccounts takes the whole burst of 100
+        assert!(rate_limiter
+            .check_call_at(Some(&client), "getProgramAccounts", now)
+            .is_ok());
+        match rate_limiter.check_call_at(Some(&client), "getSlot", now) {
+            Err(RpcCustomError::RateLimitExceeded {
+                method,
+                retry_after_ms,
+            }) => {
+                assert_eq!(method, "getSlot");
+                assert_eq!(retry_after_ms, 100);
+            }
+            result => panic!("unexpected result {result:?}"),
+        }
+        assert!(rate_limiter
+            .check_call_at(Some(&other_client), "getSlot", now)
+            .is_ok());
+
+        // one unit is refilled after 100ms
+        let later = now + Duration::from_millis(100);
+        assert!(rate_limiter
+            .check_call_at(Some(&client), "getSlot", later)
+            .is_ok());
+        assert!(rate_limiter
+            .check_call_at(Some(&client), "getSlot", later)
+            .is_err());
+
+        // api keys have their own bucket, and unidentified calls are not limited
+        let api_key = RpcClientId::ApiKey("key".to_string());
+        for _ in 0..10 {
+            assert!(rate_limiter
+                .check_call_at(Some(&api_key), "getProgramAccounts", now)
+                .is_ok());
+            assert!(rate_limiter
+                .check_call_at(None, "getProgramAccounts", now)
+                .is_ok());
+        }
+        assert!(rate_limiter
+            .check_call_at(Some(&api_key), "getProgramAccounts", now)
+            .is_err());
+    }
+
+    #[test]
+    fn test_tracked_clients() {
+        let rate_limiter = rate_limiter();
+        let now = Instant::now();
+        let client = |index: usize| RpcClientId::Ip(Ipv4Addr::from(index as u32).into());
+        let tracked = |index| {
+            rate_limiter
+                .buckets
+                .lock()
+                .unwrap()
+                .contains_key(&client(index))
+        };
+
+        // every client keeps a partly spent bucket, so none of them is idle
+        for index in 0..MAX_TRACKED_CLIENTS {
+            let now = now + Duration::from_nanos(index as u64);
+            assert!(rate_limiter
+                .check_call_at(Some(&client(index)), "getSlot", now)
+                .is_ok());
+        }
+        assert_eq!(
+            rate_limiter.buckets.lock().unwrap().len(),
+            MAX_TRACKED_CLIENTS
+        );
+
+        // the least recently seen clients make room for new ones
+        let later = now + Duration::from_millis(1);
+        assert!(rate_limiter
+            .check_call_at(Some(&client(MAX_TRACKED_CLIENTS)), "getSlot", later)
+            .is_ok());
+        assert_eq!(
+            rate_limiter.buckets.lock().unwrap().len(),
+            MAX_TRACKED_CLIENTS - EVICTED_CLIENTS + 1
+        );
+        assert!(!tracked(EVICTED_CLIENTS - 1));
+        assert!(tracked(EVICTED_CLIENTS));
+        assert!(tracked(MAX_TRACKED_CLIENTS));
+    }
+
+    #[test]
+    fn test_method_concurrency() {
+        let rate_limiter = rate_limiter();
+        assert_eq!(
+            rate_limiter.method_quota("getProgramAccounts"),
+            MethodQuota {
+                weight: 100,
+                max_concurrent: Some(8),
+            }
+        );
+        assert_eq!(rate_limiter.method_quota("getSlot"), MethodQuota::default());
+
+        let running_call = rate_limiter.check_call(None, "getBalance").unwrap();
+        assert!(running_call.is_some());
+        assert!(matches!(
+            rate_limiter.check_call(None, "getBalance"),
+            Err(RpcCustomError::MethodConcurrencyLimitExceeded { .. })
+        ));
+        assert!(rate_limiter.check_call(None, "getSlot").unwrap().is_none());
+        drop(running_call);
+        assert!(rate_limiter
+            .check_call(None, "getBalance")
+            .unwrap()
+            .is_some());
+    }
+}
@@ -11,12 +11,15 @@ use {
         },
         rpc_cache::LargestAccountsCache,
         rpc_health::*,
+        rpc_rate_limiter::{RpcRateLimitMiddleware, RpcRateLimiter},
     },
     crossbeam_channel::unbounded,
     jsonrpc_core::{futures::prelude::*, MetaIoHandler},
     jsonrpc_http_server::{
-        hyper, AccessControlAllowOrigin, CloseHandle, DomainsValidation, RequestMiddleware,
-        RequestMiddlewareAction, ServerBuilder,
+        cors::AccessControlAllowHeaders,
+        hyper::{self, server::conn::AddrStream, service::Service},
+        AccessControlAllowOrigin, RequestMiddleware, RequestMiddlewareAction, RestApi, Rpc,
+        ServerHandler,
     },
     regex::Regex,
     solana_client::connection_cache::ConnectionCache,
@@ -40,9 +43,10 @@ use {
         native_token::lamports_to_sol, pubkey::Pubkey,
     },
     solana_send_transaction_service::send_transaction_service::{self, SendTransactionService},
-    solana_storage_bigtable::CredentialType,
+    solana_storage_bigtable::{CredentialType, LedgerStorageAdapter, LocalLedgerStorage},
     std::{
         collections::HashSet,
+        convert::Infallible,
         net::SocketAddr,
         path::{Path, PathBuf},
         sync::{
@@ -51,6 +55,7 @@ use {
         },
         thread::{self, Builder, JoinHandle},
     },
+    tokio::{runtime::Runtime, sync::Notify},
     tokio_util::codec::{BytesCodec, FramedRead},
 };
 
@@ -64,7 +69,7 @@ pub struct JsonRpcService {
     #[cfg(test)]
     pub request_processor: JsonRpcRequestProcessor, // Used only by test_rpc_new()...
 
-    close_handle: Option<CloseHandle>,
+    close_handle: Option<Arc<Notify>>,
 }
 
 struct RpcRequestMiddleware {
@@ -335,6 +340,47 @@ fn process_rest(bank_forks: &Arc<RwLock<BankForks>>, path: &str) -> Option<Strin
     }
 }
 
+/// Serve `rpc` over HTTP on `rpc_addr` until `close_handle` is notified. `ServerBuilder` doesn't
+/// expose the peer address of a request, so the connections are accepted here instead and the
+/// peer address is added to the extensions of every request for the metadata extractor.
+fn start_http_server(
+    runtime: &Runtime,
+    rpc: Rpc<JsonRpcRequestProcessor, RpcRateLimitMiddleware>,
+    request_middleware: Arc<dyn RequestMiddleware>,
+    max_request_body_size: usize,
+    rpc_addr: &SocketAddr,
+    close_handle: Arc<Notify>,
+) -> hyper::Result<impl Future<Output = hyper::Result<()>>> {
+    let _guard = runtime.enter();
+    let make_service = hyper::service::make_service_fn(move |connection: &AddrStream| {
+        let peer_addr = connection.remote_addr();
+        let mut handler = ServerHandler::new(
+            rpc.downgrade(),
+            Some(vec![AccessControlAllowOrigin::Any]),
+            Some(86400),
+            AccessControlAllowHeaders::Any,
+            None,
+            request_middleware.clone(),
+            RestApi::Disabled,
+            None,
+            max_request_body_size,
+            true,
+        );
+        let service =
+            hyper::service::service_fn(move |mut request: hyper::Request<hyper::Body>| {
+                request.extensions_mut().insert(peer_addr);
+                handler.call(request)
+            });
+        future::ready(Ok::<_, Infallible>(service))
+    });
+    Ok(hyper::Server::try_bind(rpc_addr)?
+        .http1_keepalive(true)
+        .tcp_nodelay(true)
+        .tcp_sleep_on_accept_errors(true)
+        .serve(make_service)
+        .with_graceful_shutdown(async move { close_handle.notified().await }))
+}
+
 impl JsonRpcService {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -382,9 +428,8 @@ impl JsonRpcService {
         // sadly, some parts of our current rpc implemention block the jsonrpc's
         // _socket-listening_ event loop for too long, due to (blocking) long IO or intesive CPU,
         // causing no further processing of incoming requests and ultimatily innocent clients timing-out.
-        // So create a (shared) multi-threaded event_loop for jsonrpc and set its .threads() to 1,
-        // so that we avoid the single-threaded event loops from being created automatically by
-        // jsonrpc for threads when .threads(N > 1) is given.
+        // So serve jsonrpc on a (shared) multi-threaded event_loop rather than on the
+        // single-threaded event loops jsonrpc creates by itself.
         let runtime = Arc::new(
             tokio::runtime::Builder::new_multi_thread()
                 .worker_threads(rpc_threads)
@@ -403,21 +448,29 @@ impl JsonRpcService {
                 ref bigtable_instance_name,
                 ref bigtable_app_profile_id,
                 timeout,
+                ref local_ledger_storage_path,
             }) = config.rpc_bigtable_config
             {
-                let bigtable_config = solana_storage_bigtable::LedgerStorageConfig {
-                    read_only: !enable_bigtable_ledger_upload,
-                    timeout,
-                    credential_type: CredentialType::Filepath(None),
-                    instance_name: bigtable_instance_name.clone(),
-                    app_profile_id: bigtable_app_profile_id.clone(),
+                let ledger_storage = if let Some(path) = local_ledger_storage_path {
+                    LocalLedgerStorage::open(path, !enable_bigtable_ledger_upload)
+                        .map(|storage| Arc::new(storage) as Arc<dyn LedgerStorageAdapter>)
+                } else {
+                    let bigtable_config = solana_storage_bigtable::LedgerStorageConfig {
+                        read_only: !enable_bigtable_ledger_upload,
+                        timeout,
+                        credential_type: CredentialType::Filepath(None),
+                        instance_name: bigtable_instance_name.clone(),
+                        app_profile_id: bigtable_app_profile_id.clone(),
+                    };
+                    runtime
+                        .block_on(solana_storage_bigtable::LedgerStorage::new_with_config(
+                            bigtable_config,
+                        ))
+                        .map(|storage| Arc::new(storage) as Arc<dyn LedgerStorageAdapter>)
                 };
-                runtime
-                    .block_on(solana_storage_bigtable::LedgerStorage::new_with_config(
-                        bigtable_config,
-                    ))
+                ledger_storage
                     .map(|bigtable_ledger_storage| {
-                        info!("BigTable ledger storage initialized");
+                        info!("Long-term ledger storage initialized");
 
                         let bigtable_ledger_upload_service = if enable_bigtable_ledger_upload {
                             Some(Arc::new(BigTableUploadService::new_with_config(
@@ -439,13 +492,17 @@ impl JsonRpcService {
                         )
                     })
                     .unwrap_or_else(|err| {
-                        error!("Failed to initialize BigTable ledger storage: {:?}", err);
+                        error!("Failed to initialize long-term ledger storage: {:?}", err);
                         (None, None)
                     })
             } else {
                 (None, None)
             };
 
+        let rate_limiter = config
+            .rate_limit_config
+            .clone()
+            .map(|rate_limit_config| Arc::new(RpcRateLimiter::new(rate_limit_config)));
         let full_api = config.full_api;
         let obsolete_v1_7_api = config.obsolete_v1_7_api;
         let max_request_body_size = config
@@ -486,13 +543,16 @@ impl JsonRpcService {
 
         let ledger_path = ledger_path.to_path_buf();
 
-        let (close_handle_sender, close_handle_receiver) = unbounded();
+        let close_handle = Arc::new(Notify::new());
+        let server_close_handle = close_handle.clone();
+        let (start_sender, start_receiver) = unbounded();
         let thread_hdl = Builder::new()
             .name("solJsonRpcSvc".to_string())
             .spawn(move || {
                 renice_this_thread(rpc_niceness_adj).unwrap();
 
-                let mut io = MetaIoHandler::default();
+                let mut io =
+                    MetaIoHandler::with_middleware(RpcRateLimitMiddleware(rate_limiter.clone()));
 
                 io.extend_with(rpc_minimal::MinimalImpl.to_delegate());
                 if full_api {
@@ -513,19 +573,24 @@ impl JsonRpcService {
                     bank_forks.clone(),
                     health.clone(),
                 );
-                let server = ServerBuilder::with_meta_extractor(
-                    io,
-                    move |_req: &hyper::Request<hyper::Body>| request_processor.clone(),
-                )
-                .event_loop_executor(runtime.handle().clone())
-                .threads(1)
-                .cors(DomainsValidation::AllowOnly(vec![
-                    AccessControlAllowOrigin::Any,
-                ]))
-                .cors_max_age(86400)
-                .request_middleware(request_middleware)
-                .max_request_body_size(max_request_body_size)
-                .start_http(&rpc_addr);
+                let rpc = Rpc {
+                    handler: Arc::new(io),
+                    extractor: Arc::new(move |req: &hyper::Request<hyper::Body>| {
+                        match (&rate_limiter, req.extensions().get::<SocketAddr>()) {
+                            (Some(rate_limiter), Some(peer_addr)) => request_processor
+                                .for_client(rate_limiter.client_id(req.headers(), peer_addr.ip())),
+                            _ => request_processor.clone(),
+                        }
+                    }),
+                };
+                let server = start_http_server(
+                    &runtime,
+                    rpc,
+                    Arc::new(request_middleware),
+                    max_request_body_size,
+                    &rpc_addr,
+                    server_close_handle,
+                );
 
                 if let Err(e) = server {
                     warn!(
@@ -534,23 +599,24 @@ impl JsonRpcService {
                         e,
                         rpc_addr.port()
                     );
-                    close_handle_sender.send(Err(e.to_string())).unwrap();
+                    start_sender.send(Err(e.to_string())).unwrap();
                     return;
                 }
 
-                let server = server.unwrap();
-                close_handle_sender.send(Ok(server.close_handle())).unwrap();
-                server.wait();
+                start_sender.send(Ok(())).unwrap();
+                if let Err(e) = runtime.block_on(server.unwrap()) {
+                    error!("JSON RPC service error: {:?}", e);
+                }
                 exit_bigtable_ledger_upload_service.store(true, Ordering::Relaxed);
             })
             .unwrap();
 
-        let close_handle = close_handle_receiver.recv().unwrap()?;
+        start_receiver.recv().unwrap()?;
         let close_handle_ = close_handle.clone();
         validator_exit
             .write()
             .unwrap()
-            .register_exit(Box::new(move || close_handle_.close()));
+            .register_exit(Box::new(move || close_handle_.notify_one()));
         Ok(Self {
             thread_hdl,
             #[cfg(test)]
@@ -561,7 +627,7 @@ impl JsonRpcService {
 
     pub fn exit(&mut self) {
         if let Some(c) = self.close_handle.take() {
-            c.close()
+            c.notify_one()
         }
     }
 
@@ -593,7 +659,6 @@ mod tests {
             io::Write,
             net::{IpAddr, Ipv4Addr},
         },
-        tokio::runtime::Runtime,
     };
 
     #[test]
@@ -42,6 +42,7 @@ impl From<SubscriptionId> for u64 {
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum SubscriptionParams {
     Account(AccountSubscriptionParams),
+    Accumulator(AccumulatorSubscriptionParams),
     Block(BlockSubscriptionParams),
     Logs(LogsSubscriptionParams),
     Program(ProgramSubscriptionParams),
@@ -56,6 +57,7 @@ impl SubscriptionParams {
     fn method(&self) -> &'static str {
         match self {
             SubscriptionParams::Account(_) => "accountNotification",
+            SubscriptionParams::Accumulator(_) => "accumulatorNotification",
             SubscriptionParams::Logs(_) => "logsNotification",
             SubscriptionParams::Program(_) => "programNotification",
             SubscriptionParams::Signature(_) => "signatureNotification",
@@ -70,6 +72,7 @@ impl SubscriptionParams {
     fn commitment(&self) -> Option<CommitmentConfig> {
         match self {
             SubscriptionParams::Account(params) => Some(params.commitment),
+            SubscriptionParams::Accumulator(params) => Some(params.commitment),
             SubscriptionParams::Logs(params) => Some(params.commitment),
             SubscriptionParams::Program(params) => Some(params.commitment),
             SubscriptionParams::Signature(params) => Some(params.commitment),
@@ -84,6 +87,7 @@ impl SubscriptionParams {
     fn is_commitment_watcher(&self) -> bool {
         let commitment = match self {
             SubscriptionParams::Account(params) => &params.commitment,
+            SubscriptionParams::Accumulator(params) => &params.commitment,
             SubscriptionParams::Block(params) => &params.commitment,
             SubscriptionParams::Logs(params) => &params.commitment,
             SubscriptionParams::Program(params) => &params.commitment,
@@ -99,6 +103,7 @@ impl SubscriptionParams {
     fn is_gossip_watcher(&self) -> bool {
         let commitment = match self {
             SubscriptionParams::Account(params) => &params.commitment,
+            SubscriptionParams::Accumulator(params) => &params.commitment,
             SubscriptionParams::Block(params) => &params.commitment,
             SubscriptionParams::Logs(params) => &params.commitment,
             SubscriptionParams::Program(params) => &params.commitment,
@@ -130,6 +135,11 @@ pub struct AccountSubscriptionParams {
     pub commitment: CommitmentConfig,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AccumulatorSubscriptionParams {
+    pub commitment: CommitmentConfig,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct BlockSubscriptionParams {
     pub commitment: CommitmentConfig,
@@ -6,10 +6,10 @@ use {
         parsed_token_accounts::{get_parsed_token_account, get_parsed_token_accounts},
         rpc_pubsub_service::PubSubConfig,
         rpc_subscription_tracker::{
-            AccountSubscriptionParams, BlockSubscriptionKind, BlockSubscriptionParams,
-            LogsSubscriptionKind, LogsSubscriptionParams, ProgramSubscriptionParams,
-            SignatureSubscriptionParams, SubscriptionControl, SubscriptionId, SubscriptionInfo,
-            SubscriptionParams, SubscriptionsTracker,
+            AccountSubscriptionParams, AccumulatorSubscriptionParams, BlockSubscriptionKind,
+            BlockSubscriptionParams, LogsSubscriptionKind, LogsSubscriptionParams,
+            ProgramSubscriptionParams, SignatureSubscriptionParams, SubscriptionControl,
+            SubscriptionId, SubscriptionInfo, SubscriptionParams, SubscriptionsTracker,
         },
     },
     crossbeam_channel::{Receiver, RecvTimeoutError, SendError, Sender},
@@ -21,9 +21,9 @@ use {
     solana_measure::measure::Measure,
     solana_rayon_threadlimit::get_thread_count,
     solana_rpc_client_api::response::{
-        ProcessedSignatureResult, ReceivedSignatureResult, Response as RpcResponse, RpcBlockUpdate,
-        RpcBlockUpdateError, RpcKeyedAccount, RpcLogsResponse, RpcResponseContext,
-        RpcSignatureResult, RpcVote, SlotInfo, SlotUpdate,
+        ProcessedSignatureResult, ReceivedSignatureResult, Response as RpcResponse,
+        RpcAccumulatorUpdate, RpcBlockUpdate, RpcBlockUpdateError, RpcKeyedAccount,
+        RpcLogsResponse, RpcResponseContext, RpcSignatureResult, RpcVote, SlotInfo, SlotUpdate,
     },
     solana_runtime::{
         bank::{Bank, TransactionLogInfo},
@@ -33,7 +33,9 @@ use {
     },
     solana_sdk::{
         account::{AccountSharedData, ReadableAccount},
+        accumulator::{Accumulator, PriceFeedMessage},
         clock::Slot,
+        commitment_config::CommitmentConfig,
         pubkey::Pubkey,
         signature::Signature,
         timing::timestamp,
@@ -391,6 +393,32 @@ fn filter_account_result(
     (account, last_modified_slot)
 }
 
+fn filter_accumulator_result(
+    result: Option<Accumulator>,
+    _params: &AccumulatorSubscriptionParams,
+    last_notified_slot: Slot,
+    _bank: Arc<Bank>,
+) -> (Option<RpcAccumulatorUpdate>, Slot) {
+    // The sysvar holds the last accumulator committed on the bank's fork, so a slot differing
+    // from the last notified one is either a newer accumulator or one from a different fork.
+    match result {
+        Some(accumulator) if accumulator.slot != last_notified_slot => (
+            Some(RpcAccumulatorUpdate {
+                slot: accumulator.slot,
+                root: accumulator.root.to_string(),
+                messages: accumulator
+                    .messages
+                    .iter()
+                    .filter_map(|message| PriceFeedMessage::from_bytes(message))
+                    .map(Into::into)
+                    .collect(),
+            }),
+            accumulator.slot,
+        ),
+        _ => (None, last_notified_slot),
+    }
+}
+
 fn filter_signature_result(
     result: Option<transaction::Result<()>>,
     _params: &SignatureSubscriptionParams,
@@ -455,22 +483,29 @@ fn initial_last_notified_slot(
     block_commitment_cache: &RwLock<BlockCommitmentCache>,
     optimistically_confirmed_bank: &RwLock<OptimisticallyConfirmedBank>,
 ) -> Option<Slot> {
+    let commitment_bank = |commitment: &CommitmentConfig| {
+        let slot = if commitment.is_finalized() {
+            block_commitment_cache
+                .read()
+                .unwrap()
+                .highest_confirmed_root()
+        } else if commitment.is_confirmed() {
+            optimistically_confirmed_bank.read().unwrap().bank.slot()
+        } else {
+            block_commitment_cache.read().unwrap().slot()
+        };
+        bank_forks.read().unwrap().get(slot)
+    };
+
     match params {
         SubscriptionParams::Account(params) => {
-            let slot = if params.commitment.is_finalized() {
-                block_commitment_cache
-                    .read()
-                    .unwrap()
-                    .highest_confirmed_root()
-            } else if params.commitment.is_confirmed() {
-                optimistically_confirmed_bank.read().unwrap().bank.slot()
-            } else {
-                block_commitment_cache.read().unwrap().slot()
-            };
-
-            let bank = bank_forks.read().unwrap().get(slot)?;
+            let bank = commitment_bank(&params.commitment)?;
             Some(bank.get_account_modified_slot(&params.pubkey)?.1)
         }
+        SubscriptionParams::Accumulator(params) => {
+            let bank = commitment_bank(&params.commitment)?;
+            Some(bank.get_accumulator()?.slot)
+        }
         _ => None,
     }
 }
@@ -929,6 +964,9 @@ impl RpcSubscriptions {
         let num_accounts_found = AtomicUsize::new(0);
         let num_accounts_notified = AtomicUsize::new(0);
 
+        let num_accumulators_found = AtomicUsize::new(0);
+        let num_accumulators_notified = AtomicUsize::new(0);
+
         let num_blocks_found = AtomicUsize::new(0);
         let num_blocks_notified = AtomicUsize::new(0);
 
@@ -975,6 +1013,62 @@ impl RpcSubscriptions {
                         }
                     }
                 }
+                SubscriptionParams::Accumulator(params) => {
+                    num_accumulators_found.fetch_add(1, Ordering::Relaxed);
+                    if let Some(slot) = slot {
+                        let bank = bank_forks.read().unwrap().get(slot);
+                        if let Some(bank) = bank {
+                            // Every slot commits its own accumulator, so when the commitment
+                            // moves several slots at once, the accumulators of the ancestors
+                            // in between are notified first, in slot order. Ancestors that
+                            // were pruned from bank forks are read from the blockstore.
+                            let mut w_last_notified_slot =
+                                subscription.last_notified_slot.write().unwrap();
+                            let mut skipped_slots: Vec<_> = if *w_last_notified_slot == 0 {
+                                vec![]
+                            } else {
+                                (*w_last_notified_slot + 1..slot).collect()
+                            };
+                            let ancestors = bank.proper_ancestors_set();
+                            skipped_slots.retain(|slot| ancestors.contains(slot));
+                            let mut accumulators: Vec<_> = {
+                                let r_bank_forks = bank_forks.read().unwrap();
+                                skipped_slots
+                                    .into_iter()
+                                    .filter_map(|s| {
+                                        match r_bank_forks.get(s) {
+                                            Some(bank) => bank.get_accumulator(),
+                                            None => blockstore.get_accumulator(s).ok().flatten(),
+                                        }
+                                        .filter(|accumulator| accumulator.slot == s)
+                                    })
+                                    .collect()
+                            };
+                            accumulators.extend(bank.get_accumulator());
+
+                            for accumulator in accumulators {
+                                let (update, result_slot) = filter_accumulator_result(
+                                    Some(accumulator),
+                                    params,
+                                    *w_last_notified_slot,
+                                    bank.clone(),
+                                );
+                                if let Some(update) = update {
+                                    notifier.notify(
+                                        RpcResponse::from(RpcNotificationResponse {
+                                            context: RpcNotificationContext { slot },
+                                            value: update,
+                                        }),
+                                        subscription,
+                                        false,
+                                    );
+                                    num_accumulators_notified.fetch_add(1, Ordering::Relaxed);
+                                    *w_last_notified_slot = result_slot;
+                                }
+                            }
+                        }
+                    }
+                }
                 SubscriptionParams::Block(params) => {
                     num_blocks_found.fetch_add(1, Ordering::Relaxed);
                     if let Some(slot) = slot {
@@ -1128,6 +1222,7 @@ impl RpcSubscriptions {
         total_time.stop();
 
         let total_notified = num_accounts_notified.load(Ordering::Relaxed)
+            + num_accumulators_notified.load(Ordering::Relaxed)
             + num_logs_notified.load(Ordering::Relaxed)
             + num_programs_notified.load(Ordering::Relaxed)
             + num_signatures_notified.load(Ordering::Relaxed);
@@ -1159,6 +1254,16 @@ impl RpcSubscriptions {
                     num_accounts_notified.load(Ordering::Relaxed),
                     i64
                 ),
+                (
+                    "num_accumulator_subscriptions",
+                    num_accumulators_found.load(Ordering::Relaxed),
+                    i64
+                ),
+                (
+                    "num_accumulators_notified",
+                    num_accumulators_notified.load(Ordering::Relaxed),
+                    i64
+                ),
                 (
                     "num_logs_subscriptions",
                     num_logs_found.load(Ordering::Relaxed),
@@ -1242,18 +1347,23 @@ pub(crate) mod tests {
             rpc_pubsub_service,
         },
         serial_test::serial,
-        solana_rpc_client_api::config::{
-            RpcAccountInfoConfig, RpcBlockSubscribeConfig, RpcBlockSubscribeFilter,
-            RpcProgramAccountsConfig, RpcSignatureSubscribeConfig, RpcTransactionLogsConfig,
-            RpcTransactionLogsFilter,
+        solana_rpc_client_api::{
+            config::{
+                RpcAccountInfoConfig, RpcAccumulatorSubscribeConfig, RpcBlockSubscribeConfig,
+                RpcBlockSubscribeFilter, RpcProgramAccountsConfig, RpcSignatureSubscribeConfig,
+                RpcTransactionLogsConfig, RpcTransactionLogsFilter,
+            },
+            response::RpcPriceFeedMessage,
         },
         solana_runtime::{
             commitment::BlockCommitment,
             genesis_utils::{create_genesis_config, GenesisConfigInfo},
         },
         solana_sdk::{
+            account::WritableAccount,
             commitment_config::CommitmentConfig,
             message::Message,
+            pyth_oracle::{self, AccountHeader, AccountType, PriceAccount, PriceInfo},
             signature::{Keypair, Signer},
             stake, system_instruction, system_program, system_transaction,
             transaction::Transaction,
@@ -1393,6 +1503,7 @@ pub(crate) mod tests {
                         encoding: None,
                         data_slice: None,
                         min_context_slot: None,
+                        at_slot: None,
                     }),
                 )
                 .unwrap();
@@ -1439,6 +1550,161 @@ pub(crate) mod tests {
         }
     }
 
+    #[test]
+    #[serial]
+    fn test_check_accumulator_subscribe() {
+        let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(100);
+        let bank = Bank::new_for_tests(&genesis_config);
+        let bank_forks = Arc::new(RwLock::new(BankForks::new(bank)));
+        let bank0 = bank_forks.read().unwrap().get(0).unwrap();
+        let bank1 = Bank::new_from_parent(&bank0, &Pubkey::default(), 1);
+        bank_forks.write().unwrap().insert(bank1);
+
+        let exit = Arc::new(AtomicBool::new(false));
+        let max_complete_transaction_status_slot = Arc::new(AtomicU64::default());
+        let subscriptions = Arc::new(RpcSubscriptions::new_for_tests(
+            &exit,
+            max_complete_transaction_status_slot,
+            bank_forks.clone(),
+            Arc::new(RwLock::new(BlockCommitmentCache::new_for_tests_with_slots(
+                1, 1,
+            ))),
+            OptimisticallyConfirmedBank::locked_from_bank_forks_root(&bank_forks),
+        ));
+        let (rpc, mut receiver) = rpc_pubsub_service::test_connection(&subscriptions);
+        let sub_id = rpc
+            .accumulator_subscribe(Some(RpcAccumulatorSubscribeConfig {
+                commitment: Some(CommitmentConfig::processed()),
+            }))
+            .unwrap();
+        subscriptions
+            .control
+            .assert_subscribed(&SubscriptionParams::Accumulator(
+                AccumulatorSubscriptionParams {
+                    commitment: CommitmentConfig::processed(),
+                },
+            ));
+        rpc.block_until_processed(&subscriptions);
+
+        let price_account = Pubkey::new_unique();
+        let price = PriceAccount {
+            header: AccountHeader {
+                magic: pyth_oracle::MAGIC,
+                version: pyth_oracle::VERSION,
+                account_type: AccountType::Price as u32,
+                size: std::mem::size_of::<PriceAccount>() as u32,
+            },
+            agg: PriceInfo {
+                price: 42,
+                conf: 7,
+                ..PriceInfo::default()
+            },
+            ..PriceAccount::default()
+        };
+        let mut account = AccountSharedData::new(1, 0, &pyth_oracle::id());
+        account.set_data(bytemuck::bytes_of(&price).to_vec());
+        let bank1 = bank_forks.read().unwrap().get(1).unwrap();
+        bank1.store_account(&price_account, &account);
+        bank1.freeze();
+        let root = bank1.get_accumulator().unwrap().root;
+
+        let commitment_slots = CommitmentSlots {
+            slot: 1,
+            ..CommitmentSlots::default()
+        };
+        subscriptions.notify_subscribers(commitment_slots);
+        let response = receiver.recv();
+        let expected = json!({
+           "jsonrpc": "2.0",
+           "method": "accumulatorNotification",
+           "params": {
+               "result": {
+                   "context": { "slot": 1 },
+                   "value": {
+                       "slot": 1,
+                       "root": root.to_string(),
+                       "messages": [
+                           RpcPriceFeedMessage::from(price.to_price_feed_message(&price_account)),
+                       ],
+                   },
+               },
+               "subscription": 0,
+           }
+        });
+        assert_eq!(
+            expected,
+            serde_json::from_str::<serde_json::Value>(&response).unwrap(),
+        );
+
+        // The accumulator is only notified once, however often the slot is.
+        subscriptions.notify_subscribers(commitment_slots);
+        assert!(receiver
+            .recv_timeout(Duration::from_millis(RECEIVE_DELAY_MILLIS * 5))
+            .is_err());
+
+        // The accumulators of the slots skipped by the commitment are notified too.
+        let bank2 = bank_forks.write().unwrap().insert(Bank::new_from_parent(
+            &bank1,
+            &Pubkey::default(),
+            2,
+        ));
+        bank2.freeze();
+        let bank3 = bank_forks.write().unwrap().insert(Bank::new_from_parent(
+            &bank2,
+            &Pubkey::default(),
+            3,
+        ));
+        bank3.store_account(&price_account, &account);
+        bank3.freeze();
+        let root2 = bank2.get_accumulator().unwrap().root;
+        let root3 = bank3.get_accumulator().unwrap().root;
+
+        subscriptions.notify_subscribers(CommitmentSlots {
+            slot: 3,
+            ..CommitmentSlots::default()
+        });
+        for (slot, root, messages) in [
+            (2, root2, vec![]),
+            (
+                3,
+                root3,
+                vec![RpcPriceFeedMessage::from(
+                    price.to_price_feed_message(&price_account),
+                )],
+            ),
+        ] {
+            let response = receiver.recv();
+            let expected = json!({
+               "jsonrpc": "2.0",
+               "method": "accumulatorNotification",
+               "params": {
+                   "result": {
+                       "context": { "slot": 3 },
+                       "value": {
+                           "slot": slot,
+                           "root": root.to_string(),
+                           "messages": messages,
+                       },
+                   },
+                   "subscription": 0,
+               }
+            });
+            assert_eq!(
+                expected,
+                serde_json::from_str::<serde_json::Value>(&response).unwrap(),
+            );
+        }
+
+        rpc.accumulator_unsubscribe(sub_id).unwrap();
+        subscriptions
+            .control
+            .assert_unsubscribed(&SubscriptionParams::Accumulator(
+                AccumulatorSubscriptionParams {
+                    commitment: CommitmentConfig::processed(),
+                },
+            ));
+    }
+
     #[test]
     #[serial]
     fn test_check_confirmed_block_subscribe() {
@@ -2737,6 +3003,7 @@ pub(crate) mod tests {
                     encoding: None,
                     data_slice: None,
                     min_context_slot: None,
+                    at_slot: None,
                 }),
             )
             .unwrap();
@@ -2828,6 +3095,7 @@ pub(crate) mod tests {
                     encoding: None,
                     data_slice: None,
                     min_context_slot: None,
+                    at_slot: None,
                 }),
             )
             .unwrap();
@@ -192,6 +192,10 @@ pub struct VerifyBankHash {
 mod address_lookup_table;
 mod builtin_programs;
 mod metrics;
+mod pyth_accumulator;
+mod pyth_aggregation;
+#[cfg(test)]
+mod pyth_test_utils;
 mod sysvar_cache;
 mod transaction_account_state_info;
 
@@ -3139,6 +3143,12 @@ impl Bank {
         // committed before this write lock can be obtained here.
         let mut hash = self.hash.write().unwrap();
         if *hash == Hash::default() {
+            // build the accumulator before rent collection rewrites accounts
+            // in this slot that no transaction touched, but after the native
+            // stages that rewrite the accounts it commits to
+            self.run_builtin_freeze_stages();
+            self.update_accumulator_sysvar();
+
             // finish up any deferred changes to account state
             self.collect_rent_eagerly();
             self.collect_fees();
@@ -3153,6 +3163,14 @@ impl Bank {
         }
     }
 
+    fn run_builtin_freeze_stages(&self) {
+        for stage in builtins::freeze_stages() {
+            if self.feature_set.is_active(&stage.feature_id) {
+                (stage.process_bank)(self);
+            }
+        }
+    }
+
     // dangerous; don't use this; this is only needed for ledger-tool's special command
     pub fn unfreeze_for_ledger_tool(&self) {
         self.freeze_started.store(false, Relaxed);
@@ -6216,7 +6234,7 @@ impl Bank {
 
     /// Technically this issues (or even burns!) new lamports,
     /// so be extra careful for its usage
-    fn store_account_and_update_capitalization(
+    pub fn store_account_and_update_capitalization(
         &self,
         pubkey: &Pubkey,
         new_account: &AccountSharedData,
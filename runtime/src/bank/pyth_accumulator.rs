@@ -0,0 +1,305 @@
+use {
+    super::Bank,
+    lazy_static::lazy_static,
+    solana_merkle_tree::MerkleTree,
+    solana_sdk::{
+        account::{
+            create_account_shared_data_with_fields as create_account, AccountSharedData,
+            ReadableAccount,
+        },
+        accumulator::{emitter, Accumulator, EmitMessage, EMIT_MESSAGE_RING_SIZE},
+        feature_set,
+        pubkey::Pubkey,
+        pyth_oracle::{self, PriceAccount},
+        sysvar,
+    },
+};
+
+lazy_static! {
+    /// The emit message accounts, indexed by position in the ring. Deriving
+    /// them is too costly to repeat on every freeze.
+    static ref EMIT_MESSAGE_ADDRESSES: Vec<Pubkey> = (0..u64::from(EMIT_MESSAGE_RING_SIZE))
+        .map(EmitMessage::address)
+        .collect();
+    static ref SEQUENCE_TRACKER_ADDRESS: Pubkey = EmitMessage::sequence_tracker_address();
+}
+
+impl Bank {
+    /// Commits the Pyth price accounts written in this slot to the accumulator
+    /// sysvar.
+    ///
+    /// One message is produced per price account, ordered by address so that
+    /// every validator derives the same root regardless of the order in which
+    /// transactions were committed.
+    pub(crate) fn update_accumulator_sysvar(&self) {
+        if !self
+            .feature_set
+            .is_active(&feature_set::enable_accumulator_sysvar::id())
+        {
+            return;
+        }
+
+        let accumulator = self.compute_accumulator();
+        self.update_sysvar_account(&sysvar::accumulator::id(), |account| {
+            create_account(
+                &accumulator,
+                self.inherit_specially_retained_account_fields(account),
+            )
+        });
+
+        if !accumulator.messages.is_empty()
+            && self
+                .feature_set
+                .is_active(&feature_set::enable_accumulator_emit_message::id())
+        {
+            self.post_accumulator_emit_message(&accumulator);
+        }
+    }
+
+    /// Writes the [`EmitMessage`] attesting `accumulator` to the next account
+    /// of the ring, and advances the sequence tracker.
+    ///
+    /// The sequence number only depends on the number of messages written on
+    /// this fork, so every validator replaying it writes identical accounts.
+    fn post_accumulator_emit_message(&self, accumulator: &Accumulator) {
+        let tracker_address = *SEQUENCE_TRACKER_ADDRESS;
+        let sequence = self
+            .get_account_with_fixed_root(&tracker_address)
+            .and_then(|account| account.data().try_into().ok())
+            .map(u64::from_be_bytes)
+            .unwrap_or_default();
+        let message = EmitMessage {
+            sequence,
+            timestamp: self.clock().unix_timestamp,
+            slot: accumulator.slot,
+            ring_size: EMIT_MESSAGE_RING_SIZE,
+            root: accumulator.root,
+        };
+
+        let emitter_account = |data: Vec<u8>| {
+            move |account: &Option<AccountSharedData>| {
+                let (lamports, rent_epoch) =
+                    self.inherit_specially_retained_account_fields(account);
+                AccountSharedData::create(lamports, data.clone(), emitter::id(), false, rent_epoch)
+            }
+        };
+        self.update_sysvar_account(
+            &EMIT_MESSAGE_ADDRESSES[(sequence % u64::from(EMIT_MESSAGE_RING_SIZE)) as usize],
+            emitter_account(message.to_bytes()),
+        );
+        self.update_sysvar_account(
+            &tracker_address,
+            emitter_account((sequence + 1).to_be_bytes().to_vec()),
+        );
+    }
+
+    /// Builds the accumulator of the Pyth price accounts stored in this slot.
+    ///
+    /// Once the bank is frozen this recomputes the accumulator committed to
+    /// the sysvar, as long as no price account was stored after it was built.
+    pub fn compute_accumulator(&self) -> Accumulator {
+        let messages = self.accumulator_messages();
+        let root = MerkleTree::new(&messages)
+            .get_root()
+            .copied()
+            .unwrap_or_default();
+        Accumulator {
+            slot: self.slot(),
+            root,
+            messages,
+        }
+    }
+
+    /// Encodes a price feed message for every price account owned by the
+    /// oracle program that was stored in this slot.
+    fn accumulator_messages(&self) -> Vec<Vec<u8>> {
+        let mut price_accounts = self
+            .rc
+            .accounts
+            .load_by_program_slot(self.slot(), Some(&pyth_oracle::id()));
+        price_accounts.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        price_accounts
+            .iter()
+            .filter_map(|(pubkey, account)| {
+                PriceAccount::from_account_data(account.data())
+                    .map(|price| price.to_price_feed_message(pubkey).to_bytes())
+            })
+            .collect()
+    }
+
+    /// Returns the accumulator committed by this bank, if the sysvar exists.
+    pub fn get_accumulator(&self) -> Option<Accumulator> {
+        self.get_account(&sysvar::accumulator::id())
+            .and_then(|account| bincode::deserialize(account.data()).ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::bank::{
+            pyth_test_utils::{create_price_account, new_bank},
+            test_utils::goto_end_of_slot,
+        },
+        solana_sdk::accumulator::{AccumulatorRoot, PriceFeedMessage},
+        std::sync::Arc,
+    };
+
+    #[test]
+    fn test_update_accumulator_sysvar() {
+        let mut bank = new_bank();
+        bank.activate_feature(&feature_set::enable_accumulator_sysvar::id());
+        let bank = Arc::new(bank);
+        let mut bank = Bank::new_from_parent(&bank, &Pubkey::default(), 1);
+
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+        bank.store_account(&second, &create_price_account(2));
+        bank.store_account(&first, &create_price_account(1));
+        // Accounts owned by other programs, or oracle accounts that are not
+        // price accounts, are not committed to.
+        bank.store_account(
+            &Pubkey::new_unique(),
+            &AccountSharedData::new(1, 240, &Pubkey::new_unique()),
+        );
+        bank.store_account(
+            &Pubkey::new_unique(),
+            &AccountSharedData::new(1, 240, &pyth_oracle::id()),
+        );
+        goto_end_of_slot(&mut bank);
+
+        let accumulator = bank.get_accumulator().unwrap();
+        assert_eq!(accumulator.slot, 1);
+        let (lower, higher) = if first < second {
+            (first, second)
+        } else {
+            (second, first)
+        };
+        let feed_ids: Vec<_> = accumulator
+            .messages
+            .iter()
+            .map(|message| PriceFeedMessage::from_bytes(message).unwrap().feed_id)
+            .collect();
+        assert_eq!(feed_ids, vec![lower, higher]);
+        assert_eq!(
+            accumulator.root,
+            *MerkleTree::new(&accumulator.messages).get_root().unwrap()
+        );
+        // The frozen bank recomputes the same accumulator.
+        assert_eq!(bank.compute_accumulator(), accumulator);
+
+        // A slot without price updates commits to an empty message set.
+        let bank = Arc::new(bank);
+        let mut bank = Bank::new_from_parent(&bank, &Pubkey::default(), 2);
+        goto_end_of_slot(&mut bank);
+        let accumulator = bank.get_accumulator().unwrap();
+        assert_eq!(accumulator.slot, 2);
+        assert!(accumulator.messages.is_empty());
+        assert_eq!(accumulator.root, Default::default());
+    }
+
+    #[test]
+    fn test_accumulator_root_in_sysvar_cache() {
+        let mut bank = new_bank();
+        bank.activate_feature(&feature_set::enable_accumulator_sysvar::id());
+        let bank = Arc::new(bank);
+        let mut bank = Bank::new_from_parent(&bank, &Pubkey::default(), 1);
+        bank.store_account(&Pubkey::new_unique(), &create_price_account(1));
+        goto_end_of_slot(&mut bank);
+        let accumulator = bank.get_accumulator().unwrap();
+
+        // Programs executed in a child bank see the root of the parent.
+        let bank = Bank::new_from_parent(&Arc::new(bank), &Pubkey::default(), 2);
+        assert_eq!(
+            *bank
+                .get_sysvar_cache_for_tests()
+                .get_accumulator_root()
+                .unwrap(),
+            AccumulatorRoot::from(&accumulator)
+        );
+    }
+
+    #[test]
+    fn test_update_accumulator_sysvar_inactive() {
+        let mut bank = new_bank();
+        bank.deactivate_feature(&feature_set::enable_accumulator_sysvar::id());
+        bank.store_account(&Pubkey::new_unique(), &create_price_account(1));
+        goto_end_of_slot(&mut bank);
+        assert_eq!(bank.get_accumulator(), None);
+    }
+
+    #[test]
+    fn test_emit_message_addresses() {
+        assert_eq!(
+            EMIT_MESSAGE_ADDRESSES.len(),
+            EMIT_MESSAGE_RING_SIZE as usize
+        );
+        for sequence in [0, 1, u64::from(EMIT_MESSAGE_RING_SIZE) - 1] {
+            assert_eq!(
+                EMIT_MESSAGE_ADDRESSES[sequence as usize],
+                EmitMessage::address(sequence)
+            );
+        }
+        assert_eq!(
+            *SEQUENCE_TRACKER_ADDRESS,
+            EmitMessage::sequence_tracker_address()
+        );
+    }
+
+    fn emit_message(bank: &Bank, sequence: u64) -> Option<EmitMessage> {
+        let account = bank.get_account(&EmitMessage::address(sequence))?;
+        assert_eq!(account.owner(), &emitter::id());
+        assert!(bank
+            .rent_collector()
+            .rent
+            .is_exempt(account.lamports(), EmitMessage::LEN));
+        EmitMessage::from_bytes(account.data())
+    }
+
+    #[test]
+    fn test_post_accumulator_emit_message() {
+        let mut bank = new_bank();
+        bank.activate_feature(&feature_set::enable_accumulator_sysvar::id());
+        bank.activate_feature(&feature_set::enable_accumulator_emit_message::id());
+        let mut bank = Arc::new(bank);
+
+        let price_account = Pubkey::new_unique();
+        let mut roots = vec![];
+        for (slot, price) in [(1, Some(1)), (2, None), (3, Some(3))] {
+            let mut child = Bank::new_from_parent(&bank, &Pubkey::default(), slot);
+            if let Some(price) = price {
+                child.store_account(&price_account, &create_price_account(price));
+            }
+            goto_end_of_slot(&mut child);
+            roots.push(child.get_accumulator().unwrap().root);
+            bank = Arc::new(child);
+        }
+
+        // The slot without price updates does not take a sequence number.
+        let first = emit_message(&bank, 0).unwrap();
+        assert_eq!((first.slot, first.root), (1, roots[0]));
+        let second = emit_message(&bank, 1).unwrap();
+        assert_eq!(second.sequence, 1);
+        assert_eq!((second.slot, second.root), (3, roots[2]));
+        assert_eq!(second.ring_size, EMIT_MESSAGE_RING_SIZE);
+        assert_eq!(second.timestamp, bank.clock().unix_timestamp);
+        assert_eq!(emit_message(&bank, 2), None);
+        assert_eq!(
+            bank.get_account(&EmitMessage::sequence_tracker_address())
+                .unwrap()
+                .data(),
+            &2u64.to_be_bytes()
+        );
+    }
+
+    #[test]
+    fn test_post_accumulator_emit_message_inactive() {
+        let mut bank = new_bank();
+        bank.activate_feature(&feature_set::enable_accumulator_sysvar::id());
+        bank.deactivate_feature(&feature_set::enable_accumulator_emit_message::id());
+        bank.store_account(&Pubkey::new_unique(), &create_price_account(1));
+        goto_end_of_slot(&mut bank);
+        assert!(!bank.get_accumulator().unwrap().messages.is_empty());
+        assert_eq!(emit_message(&bank, 0), None);
+    }
+}
@@ -0,0 +1,100 @@
+use {
+    super::Bank,
+    solana_sdk::{
+        account::{ReadableAccount, WritableAccount},
+        pyth_oracle::{self, PriceAccount},
+    },
+};
+
+impl Bank {
+    /// Recomputes the aggregate price of every Pyth price account stored in
+    /// this slot.
+    ///
+    /// Accounts are aggregated once each, in address order, from the latest
+    /// price of every publisher, so the result does not depend on the order
+    /// in which publishers' transactions were committed.
+    pub(crate) fn aggregate_pyth_prices(&self) {
+        let mut price_accounts = self
+            .rc
+            .accounts
+            .load_by_program_slot(self.slot(), Some(&pyth_oracle::id()));
+        price_accounts.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+        let timestamp = self.clock().unix_timestamp;
+        for (pubkey, mut account) in price_accounts {
+            if let Some(mut price) = PriceAccount::from_account_data(account.data()) {
+                let mut components = price.components(account.data());
+                price.aggregate(&mut components, self.slot(), timestamp);
+                price.write_account_data(&components, account.data_as_mut_slice());
+                self.store_account(&pubkey, &account);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::bank::{
+            pyth_test_utils::{create_published_price_account, new_bank},
+            test_utils::goto_end_of_slot,
+        },
+        solana_sdk::{
+            accumulator::PriceFeedMessage,
+            feature_set,
+            pubkey::Pubkey,
+            pyth_oracle::{PriceInfo, PriceStatus},
+        },
+        std::sync::Arc,
+    };
+
+    fn new_child_bank(active: bool) -> Bank {
+        let mut bank = new_bank();
+        bank.activate_feature(&feature_set::enable_accumulator_sysvar::id());
+        if active {
+            bank.activate_feature(&feature_set::enable_native_price_aggregation::id());
+        } else {
+            bank.deactivate_feature(&feature_set::enable_native_price_aggregation::id());
+        }
+        Bank::new_from_parent(&Arc::new(bank), &Pubkey::default(), 1)
+    }
+
+    fn aggregate(bank: &Bank, pubkey: &Pubkey) -> PriceInfo {
+        let account = bank.get_account(pubkey).unwrap();
+        PriceAccount::from_account_data(account.data()).unwrap().agg
+    }
+
+    #[test]
+    fn test_aggregate_pyth_prices() {
+        let mut bank = new_child_bank(true);
+        let price_address = Pubkey::new_unique();
+        bank.store_account(
+            &price_address,
+            &create_published_price_account(1, &[3, 1, 2]),
+        );
+        goto_end_of_slot(&mut bank);
+
+        let agg = aggregate(&bank, &price_address);
+        assert_eq!(agg.price, 2);
+        assert_eq!(agg.pub_slot, 1);
+        assert_eq!(PriceStatus::from_u32(agg.status), PriceStatus::Trading);
+
+        // The accumulator commits to the aggregated price.
+        let accumulator = bank.get_accumulator().unwrap();
+        let message = PriceFeedMessage::from_bytes(&accumulator.messages[0]).unwrap();
+        assert_eq!(message.price, 2);
+    }
+
+    #[test]
+    fn test_aggregate_pyth_prices_inactive() {
+        let mut bank = new_child_bank(false);
+        let price_address = Pubkey::new_unique();
+        bank.store_account(
+            &price_address,
+            &create_published_price_account(1, &[3, 1, 2]),
+        );
+        goto_end_of_slot(&mut bank);
+        assert_eq!(aggregate(&bank, &price_address), PriceInfo::default());
+    }
+}
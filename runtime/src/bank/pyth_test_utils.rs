@@ -0,0 +1,70 @@
+//! Fixtures shared by the tests of the Pyth price aggregation and accumulator.
+
+use {
+    super::Bank,
+    crate::genesis_utils::{create_genesis_config_with_leader, GenesisConfigInfo},
+    solana_sdk::{
+        account::{AccountSharedData, WritableAccount},
+        clock::Slot,
+        pubkey::Pubkey,
+        pyth_oracle::{
+            self, AccountHeader, AccountType, PriceAccount, PriceComponent, PriceInfo, PriceStatus,
+            MAGIC, VERSION,
+        },
+    },
+    std::mem::size_of,
+};
+
+/// Creates a price account whose aggregate price is `price`, without any
+/// publisher.
+pub(super) fn create_price_account(price: i64) -> AccountSharedData {
+    price_account(price, 0, &[])
+}
+
+/// Creates a price account with one trading publisher for every price in
+/// `prices`, all published in `pub_slot`.
+pub(super) fn create_published_price_account(pub_slot: Slot, prices: &[i64]) -> AccountSharedData {
+    price_account(0, pub_slot, prices)
+}
+
+fn price_account(price: i64, pub_slot: Slot, prices: &[i64]) -> AccountSharedData {
+    let price_account = PriceAccount {
+        header: AccountHeader {
+            magic: MAGIC,
+            version: VERSION,
+            account_type: AccountType::Price as u32,
+            size: (size_of::<PriceAccount>() + prices.len() * size_of::<PriceComponent>()) as u32,
+        },
+        num_components: prices.len() as u32,
+        min_publishers: 1,
+        agg: PriceInfo {
+            price,
+            ..PriceInfo::default()
+        },
+        ..PriceAccount::default()
+    };
+    let mut data = bytemuck::bytes_of(&price_account).to_vec();
+    for price in prices {
+        let component = PriceComponent {
+            publisher: Pubkey::new_unique(),
+            latest: PriceInfo {
+                price: *price,
+                conf: 0,
+                status: PriceStatus::Trading as u32,
+                corp_act: 0,
+                pub_slot,
+            },
+            ..PriceComponent::default()
+        };
+        data.extend_from_slice(bytemuck::bytes_of(&component));
+    }
+    let mut account = AccountSharedData::new(1_000_000_000, 0, &pyth_oracle::id());
+    account.set_data(data);
+    account
+}
+
+pub(super) fn new_bank() -> Bank {
+    let GenesisConfigInfo { genesis_config, .. } =
+        create_genesis_config_with_leader(1_000_000_000, &Pubkey::new_unique(), 42);
+    Bank::new_for_tests(&genesis_config)
+}
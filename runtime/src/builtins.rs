@@ -1,7 +1,7 @@
 #[cfg(RUSTC_WITH_SPECIALIZATION)]
 use solana_frozen_abi::abi_example::AbiExample;
 use {
-    crate::system_instruction_processor,
+    crate::{bank::Bank, system_instruction_processor},
     solana_program_runtime::invoke_context::ProcessInstructionWithContext,
     solana_sdk::{feature_set, pubkey::Pubkey, stake, system_program},
     std::fmt,
@@ -169,6 +169,45 @@ fn builtin_feature_transitions() -> Vec<BuiltinFeatureTransition> {
     ]
 }
 
+/// A step run natively by a bank when it is frozen, once `feature_id` is
+/// active.
+#[derive(Clone)]
+pub struct BuiltinFreezeStage {
+    pub name: String,
+    pub feature_id: Pubkey,
+    pub process_bank: fn(&Bank),
+}
+
+impl BuiltinFreezeStage {
+    pub fn new(name: &str, feature_id: Pubkey, process_bank: fn(&Bank)) -> Self {
+        Self {
+            name: name.to_string(),
+            feature_id,
+            process_bank,
+        }
+    }
+}
+
+impl fmt::Debug for BuiltinFreezeStage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "BuiltinFreezeStage [name={}, feature_id={}]",
+            self.name, self.feature_id
+        )
+    }
+}
+
+/// Steps run by a bank when it is frozen, in order, before the accumulator
+/// commits to the accounts stored in the slot
+pub(crate) fn freeze_stages() -> Vec<BuiltinFreezeStage> {
+    vec![BuiltinFreezeStage::new(
+        "pyth_price_aggregation",
+        feature_set::enable_native_price_aggregation::id(),
+        Bank::aggregate_pyth_prices,
+    )]
+}
+
 pub(crate) fn get() -> Builtins {
     Builtins {
         genesis_builtins: genesis_builtins(),
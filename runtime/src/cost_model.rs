@@ -38,6 +38,7 @@ pub struct TransactionCost {
     pub bpf_execution_cost: u64,
     pub account_data_size: u64,
     pub is_simple_vote: bool,
+    pub is_publisher: bool,
 }
 
 impl Default for TransactionCost {
@@ -51,6 +52,7 @@ impl Default for TransactionCost {
             bpf_execution_cost: 0u64,
             account_data_size: 0u64,
             is_simple_vote: false,
+            is_publisher: false,
         }
     }
 }
@@ -71,6 +73,7 @@ impl TransactionCost {
         self.builtins_execution_cost = 0;
         self.bpf_execution_cost = 0;
         self.is_simple_vote = false;
+        self.is_publisher = false;
     }
 
     pub fn sum(&self) -> u64 {
@@ -40,6 +40,11 @@ pub struct CostTracker {
     transaction_count: u64,
     account_data_size: u64,
 
+    /// Block units that only publisher transactions may use, until
+    /// `publisher_cost` has consumed them.
+    publisher_reserved_cost: u64,
+    publisher_cost: u64,
+
     /// The amount of total account data size remaining.  If `Some`, then do not add transactions
     /// that would cause `account_data_size` to exceed this limit.
     account_data_size_limit: Option<u64>,
@@ -63,6 +68,8 @@ impl Default for CostTracker {
             transaction_count: 0,
             account_data_size: 0,
             account_data_size_limit: None,
+            publisher_reserved_cost: 0,
+            publisher_cost: 0,
         }
     }
 }
@@ -89,6 +96,13 @@ impl CostTracker {
         self.vote_cost_limit = vote_cost_limit;
     }
 
+    /// Reserve `publisher_reserved_cost` units of the block for transactions
+    /// signed by an allow-listed publisher. Other transactions are not
+    /// admitted into the part of the reservation publishers have not used.
+    pub fn set_publisher_reserved_cost(&mut self, publisher_reserved_cost: u64) {
+        self.publisher_reserved_cost = publisher_reserved_cost;
+    }
+
     pub fn try_add(&mut self, tx_cost: &TransactionCost) -> Result<u64, CostTrackerError> {
         self.would_fit(tx_cost)?;
         self.add_transaction_cost(tx_cost);
@@ -126,6 +140,10 @@ impl CostTracker {
         self.block_cost
     }
 
+    pub fn publisher_cost(&self) -> u64 {
+        self.publisher_cost
+    }
+
     pub fn transaction_count(&self) -> u64 {
         self.transaction_count
     }
@@ -143,6 +161,7 @@ impl CostTracker {
             ("bank_slot", bank_slot as i64, i64),
             ("block_cost", self.block_cost as i64, i64),
             ("vote_cost", self.vote_cost as i64, i64),
+            ("publisher_cost", self.publisher_cost as i64, i64),
             ("transaction_count", self.transaction_count as i64, i64),
             ("number_of_accounts", self.number_of_accounts() as i64, i64),
             ("costliest_account", costliest_account.to_string(), String),
@@ -168,6 +187,21 @@ impl CostTracker {
             return Err(CostTrackerError::WouldExceedBlockMaxLimit);
         }
 
+        // if not a publisher transaction, check it leaves the unused publisher reservation free
+        if !tx_cost.is_publisher {
+            let unused_publisher_reservation = self
+                .publisher_reserved_cost
+                .saturating_sub(self.publisher_cost);
+            if self
+                .block_cost
+                .saturating_add(cost)
+                .saturating_add(unused_publisher_reservation)
+                > self.block_cost_limit
+            {
+                return Err(CostTrackerError::WouldExceedBlockMaxLimit);
+            }
+        }
+
         // if vote transaction, check if it exceeds vote_transaction_limit
         if self.vote_cost.saturating_add(vote_cost) > self.vote_cost_limit {
             return Err(CostTrackerError::WouldExceedVoteMaxLimit);
@@ -238,6 +272,9 @@ impl CostTracker {
         if tx_cost.is_simple_vote {
             self.vote_cost = self.vote_cost.saturating_add(adjustment);
         }
+        if tx_cost.is_publisher {
+            self.publisher_cost = self.publisher_cost.saturating_add(adjustment);
+        }
     }
 
     /// Subtract extra execution units from cost_tracker
@@ -253,6 +290,9 @@ impl CostTracker {
         if tx_cost.is_simple_vote {
             self.vote_cost = self.vote_cost.saturating_sub(adjustment);
         }
+        if tx_cost.is_publisher {
+            self.publisher_cost = self.publisher_cost.saturating_sub(adjustment);
+        }
     }
 
     /// count number of none-zero CU accounts
@@ -402,6 +442,36 @@ mod tests {
         assert_eq!(cost, costliest_account_cost);
     }
 
+    #[test]
+    fn test_cost_tracker_publisher_reservation() {
+        let (mint_keypair, start_hash) = test_setup();
+        let (_tx, tx_cost) = build_simple_transaction(&mint_keypair, &start_hash);
+        let (_tx, mut publisher_tx_cost) = build_simple_transaction(&mint_keypair, &start_hash);
+        publisher_tx_cost.is_publisher = true;
+        let cost = tx_cost.sum();
+
+        // build testee to have capacity for two simple transactions, one of them reserved
+        let mut testee = CostTracker::new(cost * 2, cost * 2, cost * 2, None);
+        testee.set_publisher_reserved_cost(cost);
+        assert!(testee.would_fit(&tx_cost).is_ok());
+        testee.add_transaction_cost(&tx_cost);
+
+        // only a publisher transaction may use the reservation
+        assert_eq!(
+            testee.would_fit(&tx_cost),
+            Err(CostTrackerError::WouldExceedBlockMaxLimit)
+        );
+        assert!(testee.would_fit(&publisher_tx_cost).is_ok());
+        testee.add_transaction_cost(&publisher_tx_cost);
+        assert_eq!(cost, testee.publisher_cost);
+
+        // a used reservation does not hold back other transactions
+        testee.remove(&tx_cost);
+        assert!(testee.would_fit(&tx_cost).is_ok());
+        testee.remove(&publisher_tx_cost);
+        assert_eq!(0, testee.publisher_cost);
+    }
+
     #[test]
     fn test_cost_tracker_add_data() {
         let (mint_keypair, start_hash) = test_setup();
@@ -0,0 +1,324 @@
+//! A type to hold data for the [`Accumulator` sysvar][sv].
+//!
+//! The accumulator commits to a set of messages, one for every Pyth price
+//! account written in a slot. Each message starts with a [`MessageType`] byte
+//! followed by a big-endian payload, so that it can be decoded cheaply on
+//! chains other than Pythnet.
+//!
+//! [sv]: crate::sysvar::accumulator
+//!
+//! The sysvar ID is declared in [`sysvar::accumulator`].
+//!
+//! [`sysvar::accumulator`]: crate::sysvar::accumulator
+//!
+//! Once the `enable_accumulator_emit_message` feature is active, the bank also
+//! writes an [`EmitMessage`] for every non-empty accumulator, so that bridge
+//! observers can attest to the root without decoding the sysvar.
+
+pub use crate::clock::Slot;
+use crate::{
+    clock::UnixTimestamp,
+    hash::{Hash, HASH_BYTES},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// The program owning the emit message accounts and the sequence tracker.
+///
+/// No program is deployed at this address; the accounts are only ever
+/// written by the bank.
+pub mod emitter {
+    crate::declare_id!("F7Qk64NN7hBcMCqcWCjLbk5WhfRX9WSAMzBRuFB34F9u");
+}
+
+/// Number of emit message accounts the bank cycles through. The message with
+/// sequence number `n` overwrites the one with sequence number
+/// `n - EMIT_MESSAGE_RING_SIZE`, which keeps the state bounded while leaving
+/// observers ample time to read each message.
+pub const EMIT_MESSAGE_RING_SIZE: u32 = 10_000;
+
+#[repr(C)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Accumulator {
+    /// The slot whose price updates are committed to.
+    pub slot: Slot,
+    /// Root of the Merkle tree built over `messages`, or the default hash if
+    /// no price account was written in `slot`.
+    pub root: Hash,
+    /// The encoded messages, ordered by the address of the account they were
+    /// derived from.
+    pub messages: Vec<Vec<u8>>,
+}
+
+/// The root and slot of an [`Accumulator`], as returned by
+/// [`get_accumulator_root`].
+///
+/// Its encoding is a prefix of the encoding of an [`Accumulator`], so it can
+/// be read from the sysvar account without decoding the messages.
+#[repr(C)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AccumulatorRoot {
+    pub slot: Slot,
+    pub root: Hash,
+}
+
+impl From<&Accumulator> for AccumulatorRoot {
+    fn from(accumulator: &Accumulator) -> Self {
+        Self {
+            slot: accumulator.slot,
+            root: accumulator.root,
+        }
+    }
+}
+
+/// Get the root of the accumulator built when the parent bank was frozen.
+///
+/// This reads the same data as the [accumulator sysvar][sv] without
+/// requiring the account to be passed to the program. The syscall is only
+/// available once the `enable_accumulator_root_syscall` feature is active.
+///
+/// [sv]: crate::sysvar::accumulator
+pub fn get_accumulator_root() -> Result<AccumulatorRoot, ProgramError> {
+    let mut var = AccumulatorRoot::default();
+    let var_addr = &mut var as *mut _ as *mut u8;
+
+    #[cfg(target_os = "solana")]
+    let result = unsafe { crate::syscalls::sol_get_accumulator_root(var_addr) };
+
+    #[cfg(not(target_os = "solana"))]
+    let result = crate::program_stubs::sol_get_accumulator_root(var_addr);
+
+    match result {
+        crate::entrypoint::SUCCESS => Ok(var),
+        e => Err(e.into()),
+    }
+}
+
+/// Identifies the layout of an accumulator message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum MessageType {
+    PriceFeed = 0,
+}
+
+impl MessageType {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::PriceFeed),
+            _ => None,
+        }
+    }
+}
+
+/// The latest aggregate price of a single Pyth price feed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PriceFeedMessage {
+    /// Address of the price account the message was derived from.
+    pub feed_id: Pubkey,
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    /// Unix timestamp of the current aggregate price.
+    pub publish_time: i64,
+    /// Unix timestamp of the previous aggregate price.
+    pub prev_publish_time: i64,
+    pub ema_price: i64,
+    pub ema_conf: u64,
+}
+
+impl PriceFeedMessage {
+    /// Size of the encoded message, including the leading message type byte.
+    pub const LEN: usize = 1 + 32 + 8 + 8 + 4 + 8 + 8 + 8 + 8;
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::LEN);
+        bytes.push(MessageType::PriceFeed as u8);
+        bytes.extend_from_slice(self.feed_id.as_ref());
+        bytes.extend_from_slice(&self.price.to_be_bytes());
+        bytes.extend_from_slice(&self.conf.to_be_bytes());
+        bytes.extend_from_slice(&self.exponent.to_be_bytes());
+        bytes.extend_from_slice(&self.publish_time.to_be_bytes());
+        bytes.extend_from_slice(&self.prev_publish_time.to_be_bytes());
+        bytes.extend_from_slice(&self.ema_price.to_be_bytes());
+        bytes.extend_from_slice(&self.ema_conf.to_be_bytes());
+        bytes
+    }
+
+    /// Decodes a message produced by [`PriceFeedMessage::to_bytes`].
+    ///
+    /// Returns `None` if `bytes` is not a price feed message of the expected
+    /// length.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != Self::LEN || bytes[0] != MessageType::PriceFeed as u8 {
+            return None;
+        }
+        let (feed_id, rest) = bytes[1..].split_at(32);
+        let (price, rest) = rest.split_at(8);
+        let (conf, rest) = rest.split_at(8);
+        let (exponent, rest) = rest.split_at(4);
+        let (publish_time, rest) = rest.split_at(8);
+        let (prev_publish_time, rest) = rest.split_at(8);
+        let (ema_price, ema_conf) = rest.split_at(8);
+        Some(Self {
+            feed_id: Pubkey::try_from(feed_id).ok()?,
+            price: i64::from_be_bytes(price.try_into().ok()?),
+            conf: u64::from_be_bytes(conf.try_into().ok()?),
+            exponent: i32::from_be_bytes(exponent.try_into().ok()?),
+            publish_time: i64::from_be_bytes(publish_time.try_into().ok()?),
+            prev_publish_time: i64::from_be_bytes(prev_publish_time.try_into().ok()?),
+            ema_price: i64::from_be_bytes(ema_price.try_into().ok()?),
+            ema_conf: u64::from_be_bytes(ema_conf.try_into().ok()?),
+        })
+    }
+}
+
+/// The attestation of an accumulator root written by the bank.
+///
+/// The account data is exactly the encoding produced by
+/// [`EmitMessage::to_bytes`], with every integer in big-endian order.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EmitMessage {
+    /// One more than the sequence number of the previous message, starting
+    /// at zero.
+    pub sequence: u64,
+    /// Unix timestamp of the bank that wrote the message.
+    pub timestamp: UnixTimestamp,
+    /// The slot whose accumulator is attested.
+    pub slot: Slot,
+    /// The value of [`EMIT_MESSAGE_RING_SIZE`] when the message was written.
+    pub ring_size: u32,
+    pub root: Hash,
+}
+
+impl EmitMessage {
+    /// Leading bytes identifying an emit message.
+    pub const MAGIC: [u8; 4] = *b"PAEM";
+    pub const VERSION: u8 = 1;
+    /// Size of the encoded message.
+    pub const LEN: usize = 4 + 1 + 8 + 8 + 8 + 4 + HASH_BYTES;
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::LEN);
+        bytes.extend_from_slice(&Self::MAGIC);
+        bytes.push(Self::VERSION);
+        bytes.extend_from_slice(&self.sequence.to_be_bytes());
+        bytes.extend_from_slice(&self.timestamp.to_be_bytes());
+        bytes.extend_from_slice(&self.slot.to_be_bytes());
+        bytes.extend_from_slice(&self.ring_size.to_be_bytes());
+        bytes.extend_from_slice(self.root.as_ref());
+        bytes
+    }
+
+    /// Decodes a message produced by [`EmitMessage::to_bytes`].
+    ///
+    /// Returns `None` if `bytes` is not an emit message of the expected
+    /// version and length.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != Self::LEN || bytes[..4] != Self::MAGIC || bytes[4] != Self::VERSION {
+            return None;
+        }
+        let (sequence, rest) = bytes[5..].split_at(8);
+        let (timestamp, rest) = rest.split_at(8);
+        let (slot, rest) = rest.split_at(8);
+        let (ring_size, root) = rest.split_at(4);
+        Some(Self {
+            sequence: u64::from_be_bytes(sequence.try_into().ok()?),
+            timestamp: i64::from_be_bytes(timestamp.try_into().ok()?),
+            slot: u64::from_be_bytes(slot.try_into().ok()?),
+            ring_size: u32::from_be_bytes(ring_size.try_into().ok()?),
+            root: Hash::new(root),
+        })
+    }
+
+    /// Address of the account holding the message with sequence number
+    /// `sequence`.
+    pub fn address(sequence: u64) -> Pubkey {
+        let index = (sequence % u64::from(EMIT_MESSAGE_RING_SIZE)) as u32;
+        Pubkey::find_program_address(
+            &[b"AccumulatorMessage", &index.to_be_bytes()],
+            &emitter::id(),
+        )
+        .0
+    }
+
+    /// Address of the account holding the sequence number of the next
+    /// message, as a big-endian `u64`.
+    pub fn sequence_tracker_address() -> Pubkey {
+        Pubkey::find_program_address(&[b"AccumulatorSequenceTracker"], &emitter::id()).0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_price_feed_message_roundtrip() {
+        let message = PriceFeedMessage {
+            feed_id: Pubkey::new_unique(),
+            price: -42,
+            conf: 7,
+            exponent: -8,
+            publish_time: 1_680_000_000,
+            prev_publish_time: 1_679_999_999,
+            ema_price: 40,
+            ema_conf: 9,
+        };
+        let bytes = message.to_bytes();
+        assert_eq!(bytes.len(), PriceFeedMessage::LEN);
+        assert_eq!(MessageType::from_u8(bytes[0]), Some(MessageType::PriceFeed));
+        assert_eq!(PriceFeedMessage::from_bytes(&bytes), Some(message));
+        assert_eq!(PriceFeedMessage::from_bytes(&bytes[1..]), None);
+
+        let mut bytes = bytes;
+        bytes[0] = 1;
+        assert_eq!(PriceFeedMessage::from_bytes(&bytes), None);
+    }
+
+    #[test]
+    fn test_accumulator_root_from_sysvar_data() {
+        let accumulator = Accumulator {
+            slot: 42,
+            root: Hash::new_unique(),
+            messages: vec![
+                vec![1; PriceFeedMessage::LEN],
+                vec![2; PriceFeedMessage::LEN],
+            ],
+        };
+        let data = bincode::serialize(&accumulator).unwrap();
+        assert_eq!(
+            bincode::deserialize::<AccumulatorRoot>(&data).unwrap(),
+            AccumulatorRoot::from(&accumulator)
+        );
+    }
+
+    #[test]
+    fn test_emit_message_roundtrip() {
+        let message = EmitMessage {
+            sequence: 3,
+            timestamp: 1_680_000_000,
+            slot: 42,
+            ring_size: EMIT_MESSAGE_RING_SIZE,
+            root: Hash::new_unique(),
+        };
+        let bytes = message.to_bytes();
+        assert_eq!(bytes.len(), EmitMessage::LEN);
+        assert_eq!(EmitMessage::from_bytes(&bytes), Some(message));
+        assert_eq!(EmitMessage::from_bytes(&bytes[1..]), None);
+
+        let mut bytes = bytes;
+        bytes[4] = EmitMessage::VERSION + 1;
+        assert_eq!(EmitMessage::from_bytes(&bytes), None);
+    }
+
+    #[test]
+    fn test_emit_message_address() {
+        let ring_size = u64::from(EMIT_MESSAGE_RING_SIZE);
+        assert_eq!(EmitMessage::address(1), EmitMessage::address(ring_size + 1));
+        assert_ne!(EmitMessage::address(1), EmitMessage::address(2));
+        assert_ne!(
+            EmitMessage::address(0),
+            EmitMessage::sequence_tracker_address()
+        );
+    }
+}
@@ -0,0 +1,44 @@
+//! A commitment to the Pyth price updates made in the most recent slot.
+//!
+//! The _accumulator sysvar_ provides access to the [`Accumulator`] type. It is
+//! only written once the `enable_accumulator_sysvar` feature is active, at
+//! which point the bank rebuilds it when each slot is frozen.
+//!
+//! The account grows with the number of price accounts written in a slot, so
+//! [`Sysvar::get`] always returns [`ProgramError::UnsupportedSysvar`].
+//!
+//! Unlike the other sysvars, the ID is not part of [`ALL_IDS`], so
+//! [`is_sysvar_id`] doesn't know about it: adding it would change which
+//! accounts have their write locks demoted on every node at once, regardless
+//! of the feature.
+//!
+//! [`ProgramError::UnsupportedSysvar`]: crate::program_error::ProgramError::UnsupportedSysvar
+//! [`ALL_IDS`]: crate::sysvar::ALL_IDS
+//! [`is_sysvar_id`]: crate::sysvar::is_sysvar_id
+
+pub use crate::accumulator::Accumulator;
+use crate::sysvar::{Sysvar, SysvarId};
+
+crate::declare_id!("SysvarAccumu1ator11111111111111111111111111");
+
+impl SysvarId for Accumulator {
+    fn id() -> crate::pubkey::Pubkey {
+        id()
+    }
+
+    fn check_id(pubkey: &crate::pubkey::Pubkey) -> bool {
+        check_id(pubkey)
+    }
+}
+
+impl Sysvar for Accumulator {}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::sysvar::is_sysvar_id};
+
+    #[test]
+    fn test_accumulator_not_reserved() {
+        assert!(!is_sysvar_id(&id()));
+    }
+}
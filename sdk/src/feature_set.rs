@@ -598,6 +598,18 @@ pub mod enable_accumulator_sysvar {
     solana_sdk::declare_id!("BawYFA2oeA4CacxgQgLn6ZwRWDq1ZPXruUuEbko8oPT5");
 }
 
+pub mod enable_accumulator_emit_message {
+    solana_sdk::declare_id!("GwQNNiNc35tMM5BQzmUe4We1adg2FwM5uydGe2PHFMbi");
+}
+
+pub mod enable_accumulator_root_syscall {
+    solana_sdk::declare_id!("F4gnk1pBCb2RUKZZXzf7jQZewLQto22mv65ZgMD2fMjJ");
+}
+
+pub mod enable_native_price_aggregation {
+    solana_sdk::declare_id!("SDGWPFyfcTnWfj6byGC4JQ4veSYiHfNg755zRZXwHZs");
+}
+
 lazy_static! {
     /// Map of feature identifiers to user-visible description
     pub static ref FEATURE_NAMES: HashMap<Pubkey, &'static str> = [
@@ -742,6 +754,9 @@ lazy_static! {
         (enable_big_mod_exp_syscall::id(), "add big_mod_exp syscall #28503"),
         (disable_builtin_loader_ownership_chains::id(), "disable builtin loader ownership chains #29956"),
         (enable_accumulator_sysvar::id(), "enable accumulator sysvar"),
+        (enable_accumulator_emit_message::id(), "emit a message attesting each accumulator root"),
+        (enable_accumulator_root_syscall::id(), "add sol_get_accumulator_root syscall"),
+        (enable_native_price_aggregation::id(), "aggregate pyth prices natively when a bank is frozen"),
         /*************** ADD NEW FEATURES HERE ***************/
     ]
     .iter()
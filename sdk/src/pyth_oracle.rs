@@ -0,0 +1,802 @@
+//! Account and instruction layouts of the Pyth oracle program.
+//!
+//! Pyth price accounts make up almost all of Pythnet's state. The layouts here
+//! mirror the C structures of the on-chain oracle so that the runtime and RPC
+//! can read prices without depending on the oracle crate itself.
+
+use {
+    crate::{
+        accumulator::PriceFeedMessage,
+        clock::{Slot, UnixTimestamp},
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        sysvar,
+    },
+    bytemuck::{Pod, Zeroable},
+    std::mem::size_of,
+};
+
+crate::declare_id!("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH");
+
+/// Magic number at the start of every oracle account.
+pub const MAGIC: u32 = 0xa1b2c3d4;
+
+/// The oracle account layout version described by this module.
+pub const VERSION: u32 = 2;
+
+/// Publisher prices submitted more than this many slots before the aggregate
+/// is computed are left out of it.
+pub const MAX_SEND_LATENCY: u64 = 25;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum AccountType {
+    Mapping = 1,
+    Product = 2,
+    Price = 3,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum PriceStatus {
+    Unknown = 0,
+    Trading = 1,
+    Halted = 2,
+    Auction = 3,
+    Ignored = 4,
+}
+
+impl PriceStatus {
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            1 => Self::Trading,
+            2 => Self::Halted,
+            3 => Self::Auction,
+            4 => Self::Ignored,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Pod, Zeroable)]
+pub struct AccountHeader {
+    pub magic: u32,
+    pub version: u32,
+    pub account_type: u32,
+    /// Number of bytes of the account actually in use.
+    pub size: u32,
+}
+
+impl AccountHeader {
+    /// Reads the header of an oracle account, returning `None` if `data` is
+    /// not an oracle account of a supported version.
+    pub fn from_account_data(data: &[u8]) -> Option<Self> {
+        let header: Self = bytemuck::try_pod_read_unaligned(data.get(..size_of::<Self>())?).ok()?;
+        (header.magic == MAGIC && header.version == VERSION).then_some(header)
+    }
+}
+
+impl AccountType {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            1 => Some(Self::Mapping),
+            2 => Some(Self::Product),
+            3 => Some(Self::Price),
+            _ => None,
+        }
+    }
+}
+
+/// The fixed-size part of a mapping account, which lists product accounts.
+///
+/// The account data continues with `num_products` product account addresses,
+/// read with [`MappingAccount::products`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Pod, Zeroable)]
+pub struct MappingAccount {
+    pub header: AccountHeader,
+    pub num_products: u32,
+    pub unused: u32,
+    pub next_mapping_account: Pubkey,
+}
+
+impl MappingAccount {
+    /// Reads a mapping account, returning `None` if `data` holds any other
+    /// kind of account.
+    pub fn from_account_data(data: &[u8]) -> Option<Self> {
+        read_account(data, AccountType::Mapping)
+    }
+
+    /// Reads the product account addresses that follow the fixed-size part of
+    /// the account, stopping early if `data` is too short to hold all of them.
+    pub fn products(&self, data: &[u8]) -> Vec<Pubkey> {
+        data.get(size_of::<Self>()..)
+            .unwrap_or_default()
+            .chunks_exact(size_of::<Pubkey>())
+            .take(self.num_products as usize)
+            .filter_map(|chunk| Pubkey::try_from(chunk).ok())
+            .collect()
+    }
+}
+
+/// The fixed-size part of a product account, which describes the asset
+/// quoted by a list of price accounts.
+///
+/// The account data continues with the product's attributes, read with
+/// [`ProductAccount::attributes`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Pod, Zeroable)]
+pub struct ProductAccount {
+    pub header: AccountHeader,
+    /// The first price account of the product.
+    pub price_account: Pubkey,
+}
+
+impl ProductAccount {
+    /// Reads a product account, returning `None` if `data` holds any other
+    /// kind of account.
+    pub fn from_account_data(data: &[u8]) -> Option<Self> {
+        read_account(data, AccountType::Product)
+    }
+
+    /// Reads the attributes of the product, such as its symbol, as key and
+    /// value pairs.
+    ///
+    /// Attributes are stored as consecutive length-prefixed strings up to the
+    /// size recorded in the header. Reading stops at the first string that is
+    /// truncated or not valid UTF-8.
+    pub fn attributes(&self, data: &[u8]) -> Vec<(String, String)> {
+        let end = (self.header.size as usize).min(data.len());
+        let mut rest = data.get(size_of::<Self>()..end).unwrap_or_default();
+        let mut read_string = || {
+            let (len, tail) = rest.split_first()?;
+            let (string, tail) =
+                (tail.len() >= *len as usize).then(|| tail.split_at(*len as usize))?;
+            rest = tail;
+            std::str::from_utf8(string).ok().map(str::to_string)
+        };
+        let mut attributes = vec![];
+        while let (Some(key), Some(value)) = (read_string(), read_string()) {
+            attributes.push((key, value));
+        }
+        attributes
+    }
+}
+
+/// Reads the fixed-size part of an oracle account of the given type.
+fn read_account<T: Pod>(data: &[u8], account_type: AccountType) -> Option<T> {
+    let header = AccountHeader::from_account_data(data)?;
+    if header.account_type != account_type as u32 {
+        return None;
+    }
+    bytemuck::try_pod_read_unaligned(data.get(..size_of::<T>())?).ok()
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Pod, Zeroable)]
+pub struct PriceInfo {
+    pub price: i64,
+    pub conf: u64,
+    pub status: u32,
+    pub corp_act: u32,
+    pub pub_slot: u64,
+}
+
+/// An exponentially-weighted moving average, stored as `numer / denom` with
+/// `val` holding the rounded result.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Pod, Zeroable)]
+pub struct PriceEma {
+    pub val: i64,
+    pub numer: i64,
+    pub denom: i64,
+}
+
+/// A single publisher's contribution to a price account.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Pod, Zeroable)]
+pub struct PriceComponent {
+    pub publisher: Pubkey,
+    /// The price used in the current aggregate.
+    pub agg: PriceInfo,
+    /// The most recent price submitted by the publisher.
+    pub latest: PriceInfo,
+}
+
+/// The fixed-size part of a price account.
+///
+/// The account data continues with `num_components` [`PriceComponent`]s; how
+/// many slots are reserved for them differs between oracle deployments, so
+/// they are read separately with [`PriceAccount::components`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Pod, Zeroable)]
+pub struct PriceAccount {
+    pub header: AccountHeader,
+    pub price_type: u32,
+    pub exponent: i32,
+    pub num_components: u32,
+    /// Number of publishers that contributed to the last aggregate.
+    pub num_quoters: u32,
+    /// Slot of the last valid aggregate price.
+    pub last_slot: u64,
+    pub valid_slot: u64,
+    pub ema_price: PriceEma,
+    pub ema_conf: PriceEma,
+    /// Unix timestamp of the last aggregate price.
+    pub timestamp: i64,
+    pub min_publishers: u8,
+    pub message_sent: u8,
+    pub unused_1: u16,
+    pub unused_2: u32,
+    pub product_account: Pubkey,
+    pub next_price_account: Pubkey,
+    pub prev_slot: u64,
+    pub prev_price: i64,
+    pub prev_conf: u64,
+    pub prev_timestamp: i64,
+    pub agg: PriceInfo,
+}
+
+impl PriceAccount {
+    /// Reads a price account, returning `None` if `data` holds any other kind
+    /// of account.
+    pub fn from_account_data(data: &[u8]) -> Option<Self> {
+        read_account(data, AccountType::Price)
+    }
+
+    /// Reads the publisher components that follow the fixed-size part of the
+    /// account, stopping early if `data` is too short to hold all of them.
+    pub fn components(&self, data: &[u8]) -> Vec<PriceComponent> {
+        data.get(size_of::<Self>()..)
+            .unwrap_or_default()
+            .chunks_exact(size_of::<PriceComponent>())
+            .take(self.num_components as usize)
+            .filter_map(|chunk| bytemuck::try_pod_read_unaligned(chunk).ok())
+            .collect()
+    }
+
+    pub fn status(&self) -> PriceStatus {
+        PriceStatus::from_u32(self.agg.status)
+    }
+
+    /// Writes the fixed-size part of the account and `components` back to
+    /// `data`, leaving any component that does not fit untouched.
+    pub fn write_account_data(&self, components: &[PriceComponent], data: &mut [u8]) {
+        let (fixed, rest) = data.split_at_mut(size_of::<Self>().min(data.len()));
+        if fixed.len() < size_of::<Self>() {
+            return;
+        }
+        fixed.copy_from_slice(bytemuck::bytes_of(self));
+        for (chunk, component) in rest
+            .chunks_exact_mut(size_of::<PriceComponent>())
+            .zip(components)
+        {
+            chunk.copy_from_slice(bytemuck::bytes_of(component));
+        }
+    }
+
+    /// Recomputes the aggregate price from the latest price of every
+    /// publisher, as the oracle program does when a price is updated.
+    ///
+    /// Only publishers that are trading and published within
+    /// [`MAX_SEND_LATENCY`] slots of `slot` contribute. Each contributes its
+    /// price and the two ends of its confidence interval; the aggregate is the
+    /// median of those values, the mean of the two middle ones for an even
+    /// count, and its confidence the larger distance from the median to the
+    /// 25th or 75th percentile. If fewer than `min_publishers` (and at least
+    /// one) publishers contribute, the aggregate status becomes
+    /// [`PriceStatus::Unknown`].
+    ///
+    /// Unlike the oracle program, this does not update `ema_price` and
+    /// `ema_conf`, which keep the values of the last update made by the
+    /// program.
+    pub fn aggregate(
+        &mut self,
+        components: &mut [PriceComponent],
+        slot: Slot,
+        timestamp: UnixTimestamp,
+    ) {
+        let mut values = Vec::with_capacity(components.len() * 3);
+        for component in components.iter_mut() {
+            let latest = component.latest;
+            component.agg = latest;
+            if PriceStatus::from_u32(latest.status) == PriceStatus::Trading
+                && latest.pub_slot <= slot
+                && slot - latest.pub_slot <= MAX_SEND_LATENCY
+            {
+                let conf = i64::try_from(latest.conf).unwrap_or(i64::MAX);
+                values.push(latest.price.saturating_sub(conf));
+                values.push(latest.price);
+                values.push(latest.price.saturating_add(conf));
+            }
+        }
+
+        if self.status() == PriceStatus::Trading {
+            self.prev_slot = self.valid_slot;
+            self.prev_price = self.agg.price;
+            self.prev_conf = self.agg.conf;
+            self.prev_timestamp = self.timestamp;
+        }
+        self.valid_slot = self.agg.pub_slot;
+        self.agg.pub_slot = slot;
+        self.num_quoters = (values.len() / 3) as u32;
+        if self.num_quoters < u32::from(self.min_publishers).max(1) {
+            self.agg.status = PriceStatus::Unknown as u32;
+            return;
+        }
+
+        values.sort_unstable();
+        let percentile = |percent: usize| values[(values.len() - 1) * percent / 100];
+        let median = if values.len() % 2 == 0 {
+            let (lower, upper) = (values[values.len() / 2 - 1], values[values.len() / 2]);
+            ((i128::from(lower) + i128::from(upper)) / 2) as i64
+        } else {
+            values[values.len() / 2]
+        };
+        let (low, high) = (percentile(25), percentile(75));
+        self.agg.price = median;
+        self.agg.conf = median.abs_diff(low).max(high.abs_diff(median));
+        self.agg.status = PriceStatus::Trading as u32;
+        self.last_slot = slot;
+        self.timestamp = timestamp;
+    }
+
+    /// Builds the accumulator message committing to this account's aggregate
+    /// price. `feed_id` is the address of the price account.
+    pub fn to_price_feed_message(&self, feed_id: &Pubkey) -> PriceFeedMessage {
+        PriceFeedMessage {
+            feed_id: *feed_id,
+            price: self.agg.price,
+            conf: self.agg.conf,
+            exponent: self.exponent,
+            publish_time: self.timestamp,
+            prev_publish_time: self.prev_timestamp,
+            ema_price: self.ema_price.val,
+            ema_conf: self.ema_conf.val as u64,
+        }
+    }
+}
+
+/// Identifies the instruction carried by an oracle instruction's data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(i32)]
+pub enum Command {
+    InitMapping = 0,
+    AddMapping = 1,
+    AddProduct = 2,
+    UpdProduct = 3,
+    AddPrice = 4,
+    AddPublisher = 5,
+    DelPublisher = 6,
+    UpdPrice = 7,
+    AggPrice = 8,
+    InitPrice = 9,
+    SetMinPub = 12,
+    UpdPriceNoFailOnError = 13,
+    ResizePriceAccount = 14,
+    DelPrice = 15,
+    DelProduct = 16,
+    UpdPermissions = 17,
+}
+
+impl Command {
+    pub fn from_i32(value: i32) -> Option<Self> {
+        match value {
+            0 => Some(Self::InitMapping),
+            1 => Some(Self::AddMapping),
+            2 => Some(Self::AddProduct),
+            3 => Some(Self::UpdProduct),
+            4 => Some(Self::AddPrice),
+            5 => Some(Self::AddPublisher),
+            6 => Some(Self::DelPublisher),
+            7 => Some(Self::UpdPrice),
+            8 => Some(Self::AggPrice),
+            9 => Some(Self::InitPrice),
+            12 => Some(Self::SetMinPub),
+            13 => Some(Self::UpdPriceNoFailOnError),
+            14 => Some(Self::ResizePriceAccount),
+            15 => Some(Self::DelPrice),
+            16 => Some(Self::DelProduct),
+            17 => Some(Self::UpdPermissions),
+            _ => None,
+        }
+    }
+}
+
+/// The header starting the data of every oracle instruction.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Pod, Zeroable)]
+pub struct CommandHeader {
+    pub version: u32,
+    pub command: i32,
+}
+
+impl CommandHeader {
+    /// Reads the command of an oracle instruction, returning `None` if `data`
+    /// is not an instruction of a supported version.
+    pub fn command(data: &[u8]) -> Option<Command> {
+        let header: Self = read_instruction(data)?;
+        Command::from_i32(header.command)
+    }
+}
+
+/// The data of the `upd_price` and `upd_price_no_fail_on_error` instructions,
+/// through which a publisher submits its price.
+///
+/// Accounts expected: `[signer, writable]` publisher, `[writable]` price
+/// account, and optionally the clock sysvar.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Pod, Zeroable)]
+pub struct UpdPriceArgs {
+    pub header: CommandHeader,
+    pub status: u32,
+    pub unused: u32,
+    pub price: i64,
+    pub conf: u64,
+    /// The slot the publisher observed the price in.
+    pub pub_slot: u64,
+}
+
+impl UpdPriceArgs {
+    pub fn from_instruction_data(data: &[u8]) -> Option<Self> {
+        read_instruction(data)
+    }
+}
+
+/// The data of the `add_publisher` and `del_publisher` instructions.
+///
+/// Accounts expected: `[signer, writable]` funding account, `[signer,
+/// writable]` price account.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Pod, Zeroable)]
+pub struct PublisherArgs {
+    pub header: CommandHeader,
+    pub publisher: Pubkey,
+}
+
+impl PublisherArgs {
+    pub fn from_instruction_data(data: &[u8]) -> Option<Self> {
+        read_instruction(data)
+    }
+}
+
+/// Reads the data of an oracle instruction, ignoring any trailing bytes.
+fn read_instruction<T: Pod>(data: &[u8]) -> Option<T> {
+    let header: CommandHeader =
+        bytemuck::try_pod_read_unaligned(data.get(..size_of::<CommandHeader>())?).ok()?;
+    if header.version != VERSION {
+        return None;
+    }
+    bytemuck::try_pod_read_unaligned(data.get(..size_of::<T>())?).ok()
+}
+
+/// Creates an `upd_price` instruction, or an `upd_price_no_fail_on_error`
+/// one if `fail_on_error` is false.
+pub fn upd_price(
+    publisher: &Pubkey,
+    price_account: &Pubkey,
+    status: PriceStatus,
+    price: i64,
+    conf: u64,
+    pub_slot: u64,
+    fail_on_error: bool,
+) -> Instruction {
+    let command = if fail_on_error {
+        Command::UpdPrice
+    } else {
+        Command::UpdPriceNoFailOnError
+    };
+    let args = UpdPriceArgs {
+        header: CommandHeader {
+            version: VERSION,
+            command: command as i32,
+        },
+        status: status as u32,
+        unused: 0,
+        price,
+        conf,
+        pub_slot,
+    };
+    Instruction::new_with_bytes(
+        id(),
+        bytemuck::bytes_of(&args),
+        vec![
+            AccountMeta::new(*publisher, true),
+            AccountMeta::new(*price_account, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+    )
+}
+
+/// Creates an `add_publisher` instruction permitting `publisher` to update
+/// `price_account`.
+pub fn add_publisher(funding: &Pubkey, price_account: &Pubkey, publisher: &Pubkey) -> Instruction {
+    let args = PublisherArgs {
+        header: CommandHeader {
+            version: VERSION,
+            command: Command::AddPublisher as i32,
+        },
+        publisher: *publisher,
+    };
+    Instruction::new_with_bytes(
+        id(),
+        bytemuck::bytes_of(&args),
+        vec![
+            AccountMeta::new(*funding, true),
+            AccountMeta::new(*price_account, true),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_sizes() {
+        assert_eq!(size_of::<AccountHeader>(), 16);
+        assert_eq!(size_of::<PriceInfo>(), 32);
+        assert_eq!(size_of::<PriceComponent>(), 96);
+        assert_eq!(size_of::<PriceAccount>(), 240);
+    }
+
+    #[test]
+    fn test_price_account_from_account_data() {
+        let publisher = Pubkey::new_unique();
+        let price = PriceAccount {
+            header: AccountHeader {
+                magic: MAGIC,
+                version: VERSION,
+                account_type: AccountType::Price as u32,
+                size: (size_of::<PriceAccount>() + size_of::<PriceComponent>()) as u32,
+            },
+            exponent: -8,
+            num_components: 1,
+            agg: PriceInfo {
+                price: 100,
+                conf: 2,
+                status: PriceStatus::Trading as u32,
+                ..PriceInfo::default()
+            },
+            ..PriceAccount::default()
+        };
+        let component = PriceComponent {
+            publisher,
+            ..PriceComponent::default()
+        };
+        let mut data = bytemuck::bytes_of(&price).to_vec();
+        data.extend_from_slice(bytemuck::bytes_of(&component));
+        // Reserved but unused component slots.
+        data.resize(data.len() + 2 * size_of::<PriceComponent>(), 0);
+
+        let parsed = PriceAccount::from_account_data(&data).unwrap();
+        assert_eq!(parsed, price);
+        assert_eq!(parsed.status(), PriceStatus::Trading);
+        assert_eq!(parsed.components(&data), vec![component]);
+        assert_eq!(
+            parsed.components(&data[..size_of::<PriceAccount>()]),
+            vec![]
+        );
+
+        let mut product = price;
+        product.header.account_type = AccountType::Product as u32;
+        assert_eq!(
+            PriceAccount::from_account_data(bytemuck::bytes_of(&product)),
+            None
+        );
+        assert_eq!(PriceAccount::from_account_data(&data[..100]), None);
+        assert_eq!(PriceAccount::from_account_data(&[0; 240]), None);
+    }
+
+    fn component(price: i64, conf: u64, status: PriceStatus, pub_slot: Slot) -> PriceComponent {
+        PriceComponent {
+            publisher: Pubkey::new_unique(),
+            latest: PriceInfo {
+                price,
+                conf,
+                status: status as u32,
+                corp_act: 0,
+                pub_slot,
+            },
+            ..PriceComponent::default()
+        }
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let mut price = PriceAccount {
+            min_publishers: 2,
+            agg: PriceInfo {
+                price: 90,
+                conf: 1,
+                status: PriceStatus::Trading as u32,
+                corp_act: 0,
+                pub_slot: 98,
+            },
+            valid_slot: 97,
+            timestamp: 1_000,
+            ..PriceAccount::default()
+        };
+        let mut components = vec![
+            component(100, 10, PriceStatus::Trading, 100),
+            component(110, 2, PriceStatus::Trading, 99),
+            component(104, 4, PriceStatus::Trading, 100),
+            // Halted and stale publishers are ignored.
+            component(1, 1, PriceStatus::Halted, 100),
+            component(500, 1, PriceStatus::Trading, 100 - MAX_SEND_LATENCY - 1),
+        ];
+
+        price.aggregate(&mut components, 100, 1_040);
+        assert_eq!(price.num_quoters, 3);
+        // Values are 90 100 100 104 108 108 110 110 112.
+        assert_eq!(
+            price.agg,
+            PriceInfo {
+                price: 108,
+                conf: 8,
+                status: PriceStatus::Trading as u32,
+                corp_act: 0,
+                pub_slot: 100,
+            }
+        );
+        assert_eq!(
+            (price.prev_slot, price.prev_price, price.prev_conf),
+            (97, 90, 1)
+        );
+        assert_eq!(price.prev_timestamp, 1_000);
+        assert_eq!(price.valid_slot, 98);
+        assert_eq!((price.last_slot, price.timestamp), (100, 1_040));
+        assert!(components
+            .iter()
+            .all(|component| component.agg == component.latest));
+
+        // With an even number of values, the median is the mean of the middle
+        // two. Values are 90 100 108 110 110 112.
+        price.aggregate(&mut components[..2], 100, 1_040);
+        assert_eq!(price.num_quoters, 2);
+        assert_eq!((price.agg.price, price.agg.conf), (109, 9));
+
+        // Without enough publishers the previous aggregate price is kept, but
+        // is no longer trading.
+        price.aggregate(&mut components[..1], 101, 1_080);
+        assert_eq!(price.num_quoters, 1);
+        assert_eq!(price.status(), PriceStatus::Unknown);
+        assert_eq!((price.agg.price, price.agg.pub_slot), (109, 101));
+        assert_eq!((price.last_slot, price.timestamp), (100, 1_040));
+    }
+
+    #[test]
+    fn test_write_account_data() {
+        let price = PriceAccount {
+            header: AccountHeader {
+                magic: MAGIC,
+                version: VERSION,
+                account_type: AccountType::Price as u32,
+                size: (size_of::<PriceAccount>() + size_of::<PriceComponent>()) as u32,
+            },
+            num_components: 2,
+            ..PriceAccount::default()
+        };
+        let components = vec![
+            component(1, 1, PriceStatus::Trading, 1),
+            component(2, 2, PriceStatus::Trading, 2),
+        ];
+        // Only room for one component.
+        let mut data = vec![0; size_of::<PriceAccount>() + size_of::<PriceComponent>()];
+        price.write_account_data(&components, &mut data);
+        assert_eq!(PriceAccount::from_account_data(&data), Some(price));
+        assert_eq!(price.components(&data), components[..1].to_vec());
+
+        let mut short = vec![0; 100];
+        price.write_account_data(&components, &mut short);
+        assert_eq!(short, vec![0; 100]);
+    }
+
+    #[test]
+    fn test_mapping_account_from_account_data() {
+        let products = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let mapping = MappingAccount {
+            header: AccountHeader {
+                magic: MAGIC,
+                version: VERSION,
+                account_type: AccountType::Mapping as u32,
+                size: (size_of::<MappingAccount>() + 2 * size_of::<Pubkey>()) as u32,
+            },
+            num_products: 2,
+            next_mapping_account: Pubkey::new_unique(),
+            ..MappingAccount::default()
+        };
+        let mut data = bytemuck::bytes_of(&mapping).to_vec();
+        for product in &products {
+            data.extend_from_slice(product.as_ref());
+        }
+        data.resize(data.len() + size_of::<Pubkey>(), 0);
+
+        let parsed = MappingAccount::from_account_data(&data).unwrap();
+        assert_eq!(parsed, mapping);
+        assert_eq!(parsed.products(&data), products.to_vec());
+        assert_eq!(PriceAccount::from_account_data(&data), None);
+    }
+
+    #[test]
+    fn test_product_account_attributes() {
+        let mut attributes = vec![];
+        for string in ["symbol", "Crypto.BTC/USD", "base", "BTC"] {
+            attributes.push(string.len() as u8);
+            attributes.extend_from_slice(string.as_bytes());
+        }
+        let product = ProductAccount {
+            header: AccountHeader {
+                magic: MAGIC,
+                version: VERSION,
+                account_type: AccountType::Product as u32,
+                size: (size_of::<ProductAccount>() + attributes.len()) as u32,
+            },
+            price_account: Pubkey::new_unique(),
+        };
+        let mut data = bytemuck::bytes_of(&product).to_vec();
+        data.extend_from_slice(&attributes);
+        // Bytes past the size in the header are not attributes.
+        data.extend_from_slice(&[3, b'a', b'b', b'c', 0]);
+
+        let parsed = ProductAccount::from_account_data(&data).unwrap();
+        assert_eq!(parsed, product);
+        assert_eq!(
+            parsed.attributes(&data),
+            vec![
+                ("symbol".to_string(), "Crypto.BTC/USD".to_string()),
+                ("base".to_string(), "BTC".to_string()),
+            ]
+        );
+
+        // A truncated value ends the attributes.
+        let truncated = &data[..data.len() - 7];
+        assert_eq!(
+            parsed.attributes(truncated),
+            vec![("symbol".to_string(), "Crypto.BTC/USD".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_instruction_data() {
+        let (publisher, price_account) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let instruction = upd_price(
+            &publisher,
+            &price_account,
+            PriceStatus::Trading,
+            100,
+            2,
+            7,
+            false,
+        );
+        assert_eq!(instruction.data.len(), 40);
+        assert_eq!(
+            CommandHeader::command(&instruction.data),
+            Some(Command::UpdPriceNoFailOnError)
+        );
+        let args = UpdPriceArgs::from_instruction_data(&instruction.data).unwrap();
+        assert_eq!((args.price, args.conf, args.pub_slot), (100, 2, 7));
+        assert_eq!(args.status, PriceStatus::Trading as u32);
+
+        let instruction = add_publisher(&Pubkey::new_unique(), &price_account, &publisher);
+        assert_eq!(
+            CommandHeader::command(&instruction.data),
+            Some(Command::AddPublisher)
+        );
+        assert_eq!(
+            PublisherArgs::from_instruction_data(&instruction.data)
+                .unwrap()
+                .publisher,
+            publisher
+        );
+        assert_eq!(
+            PublisherArgs::from_instruction_data(&instruction.data[..20]),
+            None
+        );
+
+        let mut data = instruction.data;
+        data[0] = 1;
+        assert_eq!(CommandHeader::command(&data), None);
+    }
+}
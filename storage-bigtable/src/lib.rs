@@ -2,10 +2,12 @@
 
 use {
     crate::bigtable::RowKey,
+    async_trait::async_trait,
     log::*,
     serde::{Deserialize, Serialize},
     solana_metrics::{datapoint_info, inc_new_counter_debug},
     solana_sdk::{
+        accumulator::Accumulator,
         clock::{Slot, UnixTimestamp},
         deserialize_utils::default_on_eof,
         message::v0::LoadedAddresses,
@@ -39,13 +41,19 @@ extern crate serde_derive;
 mod access_token;
 mod bigtable;
 mod compression;
+mod local_storage;
 mod root_ca_certificate;
 
+pub use local_storage::LocalLedgerStorage;
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("BigTable: {0}")]
     BigTableError(bigtable::Error),
 
+    #[error("Local storage: {0}")]
+    LocalStorageError(local_storage::Error),
+
     #[error("I/O Error: {0}")]
     IoError(std::io::Error),
 
@@ -68,6 +76,12 @@ impl std::convert::From<bigtable::Error> for Error {
     }
 }
 
+impl std::convert::From<local_storage::Error> for Error {
+    fn from(err: local_storage::Error) -> Self {
+        Self::LocalStorageError(err)
+    }
+}
+
 impl std::convert::From<std::io::Error> for Error {
     fn from(err: std::io::Error) -> Self {
         Self::IoError(err)
@@ -399,6 +413,163 @@ impl Default for LedgerStorageConfig {
     }
 }
 
+/// Long-term storage of confirmed blocks and transaction history.
+///
+/// Implemented by `LedgerStorage` on top of BigTable and by `LocalLedgerStorage` on top of a
+/// database on the local disk, so that RPC and the upload service can serve and keep the full
+/// ledger history from either.
+#[async_trait]
+pub trait LedgerStorageAdapter: Send + Sync {
+    /// Return the available slot that contains a block
+    async fn get_first_available_block(&self) -> Result<Option<Slot>>;
+
+    /// Fetch the next slots after the provided slot that contains a block
+    ///
+    /// start_slot: slot to start the search from (inclusive)
+    /// limit: stop after this many slots have been found
+    async fn get_confirmed_blocks(&self, start_slot: Slot, limit: usize) -> Result<Vec<Slot>>;
+
+    /// Fetch the confirmed block from the desired slot
+    async fn get_confirmed_block(&self, slot: Slot) -> Result<ConfirmedBlock>;
+
+    /// Fetch the accumulator committed in the desired slot, if one was uploaded
+    async fn get_accumulator(&self, slot: Slot) -> Result<Option<Accumulator>>;
+
+    async fn get_signature_status(&self, signature: &Signature) -> Result<TransactionStatus>;
+
+    /// Fetch a confirmed transaction
+    async fn get_confirmed_transaction(
+        &self,
+        signature: &Signature,
+    ) -> Result<Option<ConfirmedTransactionWithStatusMeta>>;
+
+    /// Get confirmed signatures for the provided address, in descending ledger order
+    ///
+    /// address: address to search for
+    /// before_signature: start with the first signature older than this one
+    /// until_signature: end with the last signature more recent than this one
+    /// limit: stop after this many signatures
+    async fn get_confirmed_signatures_for_address(
+        &self,
+        address: &Pubkey,
+        before_signature: Option<&Signature>,
+        until_signature: Option<&Signature>,
+        limit: usize,
+    ) -> Result<
+        Vec<(
+            ConfirmedTransactionStatusWithSignature,
+            u32, /*slot index*/
+        )>,
+    >;
+
+    // Upload the accumulator committed in a slot
+    async fn upload_accumulator(&self, slot: Slot, accumulator: Accumulator) -> Result<()>;
+
+    // Upload a new confirmed block and associated meta data.
+    async fn upload_confirmed_block(
+        &self,
+        slot: Slot,
+        confirmed_block: VersionedConfirmedBlock,
+    ) -> Result<()>;
+
+    // Delete a confirmed block and associated meta data.
+    async fn delete_confirmed_block(&self, slot: Slot, dry_run: bool) -> Result<()>;
+}
+
+// The `tx` and `tx-by-addr` cells of a confirmed block
+#[allow(clippy::type_complexity)]
+fn transaction_cells(
+    slot: Slot,
+    confirmed_block: &VersionedConfirmedBlock,
+) -> (
+    Vec<(RowKey, TransactionInfo)>,
+    Vec<(RowKey, tx_by_addr::TransactionByAddr)>,
+) {
+    let mut by_addr: HashMap<&Pubkey, Vec<TransactionByAddrInfo>> = HashMap::new();
+
+    let mut tx_cells = vec![];
+    for (index, transaction_with_meta) in confirmed_block.transactions.iter().enumerate() {
+        let VersionedTransactionWithStatusMeta { meta, transaction } = transaction_with_meta;
+        let err = meta.status.clone().err();
+        let index = index as u32;
+        let signature = transaction.signatures[0];
+        let memo = extract_and_fmt_memos(transaction_with_meta);
+
+        for address in transaction_with_meta.account_keys().iter() {
+            if !is_sysvar_id(address) {
+                by_addr
+                    .entry(address)
+                    .or_default()
+                    .push(TransactionByAddrInfo {
+                        signature,
+                        err: err.clone(),
+                        index,
+                        memo: memo.clone(),
+                        block_time: confirmed_block.block_time,
+                    });
+            }
+        }
+
+        tx_cells.push((
+            signature.to_string(),
+            TransactionInfo {
+                slot,
+                index,
+                err,
+                memo,
+            },
+        ));
+    }
+
+    let tx_by_addr_cells = by_addr
+        .into_iter()
+        .map(|(address, transaction_info_by_addr)| {
+            (
+                format!("{}/{}", address, slot_to_tx_by_addr_key(slot)),
+                tx_by_addr::TransactionByAddr {
+                    tx_by_addrs: transaction_info_by_addr
+                        .into_iter()
+                        .map(|by_addr| by_addr.into())
+                        .collect(),
+                },
+            )
+        })
+        .collect();
+
+    (tx_cells, tx_by_addr_cells)
+}
+
+// The transaction at `index` in the block of `slot`, if it is the one with `signature`
+fn confirmed_transaction_in_block(
+    signature: &Signature,
+    slot: Slot,
+    index: u32,
+    block: ConfirmedBlock,
+) -> Option<ConfirmedTransactionWithStatusMeta> {
+    match block.transactions.into_iter().nth(index as usize) {
+        None => {
+            // report this somewhere actionable?
+            warn!("Transaction info for {} is corrupt", signature);
+            None
+        }
+        Some(tx_with_meta) => {
+            if tx_with_meta.transaction_signature() != signature {
+                warn!(
+                    "Transaction info or confirmed block for {} is corrupt",
+                    signature
+                );
+                None
+            } else {
+                Some(ConfirmedTransactionWithStatusMeta {
+                    slot,
+                    tx_with_meta,
+                    block_time: block.block_time,
+                })
+            }
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct LedgerStorage {
     connection: bigtable::BigTableConnection,
@@ -552,6 +723,24 @@ impl LedgerStorage {
         })
     }
 
+    /// Fetch the accumulator committed in the desired slot, if one was uploaded
+    pub async fn get_accumulator(&self, slot: Slot) -> Result<Option<Accumulator>> {
+        debug!(
+            "LedgerStorage::get_accumulator request received: {:?}",
+            slot
+        );
+        inc_new_counter_debug!("storage-bigtable-query", 1);
+        let mut bigtable = self.connection.client();
+        match bigtable
+            .get_bincode_cell::<Accumulator>("accumulator", slot_to_blocks_key(slot))
+            .await
+        {
+            Ok(accumulator) => Ok(Some(accumulator)),
+            Err(bigtable::Error::RowNotFound) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
     /// Does the confirmed block exist in the Bigtable
     pub async fn confirmed_block_exists(&self, slot: Slot) -> Result<bool> {
         debug!(
@@ -670,28 +859,9 @@ impl LedgerStorage {
 
         // Load the block and return the transaction
         let block = self.get_confirmed_block(slot).await?;
-        match block.transactions.into_iter().nth(index as usize) {
-            None => {
-                // report this somewhere actionable?
-                warn!("Transaction info for {} is corrupt", signature);
-                Ok(None)
-            }
-            Some(tx_with_meta) => {
-                if tx_with_meta.transaction_signature() != signature {
-                    warn!(
-                        "Transaction info or confirmed block for {} is corrupt",
-                        signature
-                    );
-                    Ok(None)
-                } else {
-                    Ok(Some(ConfirmedTransactionWithStatusMeta {
-                        slot,
-                        tx_with_meta,
-                        block_time: block.block_time,
-                    }))
-                }
-            }
-        }
+        Ok(confirmed_transaction_in_block(
+            signature, slot, index, block,
+        ))
     }
 
     /// Get confirmed signatures for the provided address, in descending ledger order
@@ -835,62 +1005,29 @@ impl LedgerStorage {
         Ok(infos)
     }
 
+    // Upload the accumulator committed in a slot, so that proofs can be regenerated once the
+    // slot has been purged from the blockstore.
+    pub async fn upload_accumulator(&self, slot: Slot, accumulator: Accumulator) -> Result<()> {
+        let cells = [(slot_to_blocks_key(slot), accumulator)];
+        let bytes_written = self
+            .connection
+            .put_bincode_cells_with_retry::<Accumulator>("accumulator", &cells)
+            .await?;
+        datapoint_info!(
+            "storage-bigtable-upload-accumulator",
+            ("slot", slot, i64),
+            ("bytes", bytes_written, i64),
+        );
+        Ok(())
+    }
+
     // Upload a new confirmed block and associated meta data.
     pub async fn upload_confirmed_block(
         &self,
         slot: Slot,
         confirmed_block: VersionedConfirmedBlock,
     ) -> Result<()> {
-        let mut by_addr: HashMap<&Pubkey, Vec<TransactionByAddrInfo>> = HashMap::new();
-
-        let mut tx_cells = vec![];
-        for (index, transaction_with_meta) in confirmed_block.transactions.iter().enumerate() {
-            let VersionedTransactionWithStatusMeta { meta, transaction } = transaction_with_meta;
-            let err = meta.status.clone().err();
-            let index = index as u32;
-            let signature = transaction.signatures[0];
-            let memo = extract_and_fmt_memos(transaction_with_meta);
-
-            for address in transaction_with_meta.account_keys().iter() {
-                if !is_sysvar_id(address) {
-                    by_addr
-                        .entry(address)
-                        .or_default()
-                        .push(TransactionByAddrInfo {
-                            signature,
-                            err: err.clone(),
-                            index,
-                            memo: memo.clone(),
-                            block_time: confirmed_block.block_time,
-                        });
-                }
-            }
-
-            tx_cells.push((
-                signature.to_string(),
-                TransactionInfo {
-                    slot,
-                    index,
-                    err,
-                    memo,
-                },
-            ));
-        }
-
-        let tx_by_addr_cells: Vec<_> = by_addr
-            .into_iter()
-            .map(|(address, transaction_info_by_addr)| {
-                (
-                    format!("{}/{}", address, slot_to_tx_by_addr_key(slot)),
-                    tx_by_addr::TransactionByAddr {
-                        tx_by_addrs: transaction_info_by_addr
-                            .into_iter()
-                            .map(|by_addr| by_addr.into())
-                            .collect(),
-                    },
-                )
-            })
-            .collect();
+        let (tx_cells, tx_by_addr_cells) = transaction_cells(slot, &confirmed_block);
 
         let mut tasks = vec![];
 
@@ -1062,6 +1199,10 @@ impl LedgerStorage {
             self.connection
                 .delete_rows_with_retry("blocks", &[slot_to_blocks_key(slot)])
                 .await?;
+
+            self.connection
+                .delete_rows_with_retry("accumulator", &[slot_to_blocks_key(slot)])
+                .await?;
         }
 
         info!(
@@ -1076,6 +1217,63 @@ impl LedgerStorage {
     }
 }
 
+#[async_trait]
+impl LedgerStorageAdapter for LedgerStorage {
+    async fn get_first_available_block(&self) -> Result<Option<Slot>> {
+        self.get_first_available_block().await
+    }
+
+    async fn get_confirmed_blocks(&self, start_slot: Slot, limit: usize) -> Result<Vec<Slot>> {
+        self.get_confirmed_blocks(start_slot, limit).await
+    }
+
+    async fn get_confirmed_block(&self, slot: Slot) -> Result<ConfirmedBlock> {
+        self.get_confirmed_block(slot).await
+    }
+
+    async fn get_accumulator(&self, slot: Slot) -> Result<Option<Accumulator>> {
+        self.get_accumulator(slot).await
+    }
+
+    async fn get_signature_status(&self, signature: &Signature) -> Result<TransactionStatus> {
+        self.get_signature_status(signature).await
+    }
+
+    async fn get_confirmed_transaction(
+        &self,
+        signature: &Signature,
+    ) -> Result<Option<ConfirmedTransactionWithStatusMeta>> {
+        self.get_confirmed_transaction(signature).await
+    }
+
+    async fn get_confirmed_signatures_for_address(
+        &self,
+        address: &Pubkey,
+        before_signature: Option<&Signature>,
+        until_signature: Option<&Signature>,
+        limit: usize,
+    ) -> Result<Vec<(ConfirmedTransactionStatusWithSignature, u32)>> {
+        self.get_confirmed_signatures_for_address(address, before_signature, until_signature, limit)
+            .await
+    }
+
+    async fn upload_accumulator(&self, slot: Slot, accumulator: Accumulator) -> Result<()> {
+        self.upload_accumulator(slot, accumulator).await
+    }
+
+    async fn upload_confirmed_block(
+        &self,
+        slot: Slot,
+        confirmed_block: VersionedConfirmedBlock,
+    ) -> Result<()> {
+        self.upload_confirmed_block(slot, confirmed_block).await
+    }
+
+    async fn delete_confirmed_block(&self, slot: Slot, dry_run: bool) -> Result<()> {
+        self.delete_confirmed_block(slot, dry_run).await
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -0,0 +1,770 @@
+//! Long-term ledger storage in a RocksDB database on the local disk.
+//!
+//! The database holds the tables of the BigTable instance behind `LedgerStorage`, one column
+//! family per table, under the same row keys, so that blocks and transaction history are looked
+//! up the same way without a Google Cloud account. Rows hold the protobuf or bincode encoding of
+//! the BigTable cells, and are compressed by RocksDB rather than cell by cell.
+
+use {
+    crate::{
+        confirmed_transaction_in_block, key_to_slot, slot_to_blocks_key, slot_to_tx_by_addr_key,
+        transaction_cells, LedgerStorageAdapter, TransactionInfo,
+    },
+    async_trait::async_trait,
+    log::*,
+    prost::Message,
+    rocksdb::{ColumnFamily, ColumnFamilyDescriptor, DBCompressionType, Options, WriteBatch, DB},
+    serde::{de::DeserializeOwned, Serialize},
+    solana_metrics::datapoint_info,
+    solana_sdk::{
+        accumulator::Accumulator, clock::Slot, pubkey::Pubkey, signature::Signature,
+        sysvar::is_sysvar_id,
+    },
+    solana_storage_proto::convert::{generated, tx_by_addr},
+    solana_transaction_status::{
+        ConfirmedBlock, ConfirmedTransactionStatusWithSignature,
+        ConfirmedTransactionWithStatusMeta, TransactionByAddrInfo, TransactionStatus,
+        VersionedConfirmedBlock,
+    },
+    std::{collections::HashSet, convert::TryInto, path::Path, sync::Arc},
+    thiserror::Error,
+};
+
+const BLOCKS: &str = "blocks";
+const TX: &str = "tx";
+const TX_BY_ADDR: &str = "tx-by-addr";
+const ACCUMULATOR: &str = "accumulator";
+const TABLES: [&str; 4] = [BLOCKS, TX, TX_BY_ADDR, ACCUMULATOR];
+/// Directory, within the storage, holding the state of the secondary instances
+const SECONDARY_DIR: &str = "secondary";
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("RocksDB: {0}")]
+    RocksDb(rocksdb::Error),
+
+    #[error("Object is corrupt: {0}")]
+    ObjectCorrupt(String),
+}
+
+impl std::convert::From<rocksdb::Error> for Error {
+    fn from(err: rocksdb::Error) -> Self {
+        Self::RocksDb(err)
+    }
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+/// Long-term ledger storage kept in a RocksDB database in a local directory
+#[derive(Clone)]
+pub struct LocalLedgerStorage {
+    db: Arc<DB>,
+    /// Whether `db` is a secondary instance, which follows the writes of the primary one
+    secondary: bool,
+}
+
+impl LocalLedgerStorage {
+    /// Open the storage at `path`, creating it if it does not exist yet.
+    ///
+    /// Only one process at a time can open the storage for writing. A `read_only` storage is
+    /// opened as a RocksDB secondary instance alongside it, and catches up with the blocks
+    /// uploaded since before every read.
+    pub fn open(path: &Path, read_only: bool) -> crate::Result<Self> {
+        let mut options = Options::default();
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+        // Required by secondary instances
+        options.set_max_open_files(-1);
+        let tables = TABLES.iter().map(|table| {
+            let mut table_options = Options::default();
+            table_options.set_compression_type(DBCompressionType::Lz4);
+            ColumnFamilyDescriptor::new(*table, table_options)
+        });
+        let db = if read_only {
+            let secondary_path = path.join(SECONDARY_DIR);
+            DB::open_cf_descriptors_as_secondary(&options, path, &secondary_path, tables)
+        } else {
+            DB::open_cf_descriptors(&options, path, tables)
+        }
+        .map_err(Error::from)?;
+        info!(
+            "Opened local ledger storage at {}{}",
+            path.display(),
+            if read_only { " (read only)" } else { "" }
+        );
+        Ok(Self {
+            db: Arc::new(db),
+            secondary: read_only,
+        })
+    }
+
+    fn table(&self, table: &str) -> &ColumnFamily {
+        self.db.cf_handle(table).unwrap()
+    }
+
+    /// Get up to `limit` `table` row keys in lexical order, starting with `start_at`
+    fn get_row_keys(&self, table: &str, start_at: &str, limit: usize) -> Result<Vec<String>> {
+        let mut iterator = self.db.raw_iterator_cf(self.table(table));
+        iterator.seek(start_at);
+        let mut row_keys = vec![];
+        while row_keys.len() < limit && iterator.valid() {
+            let row_key = iterator.key().unwrap();
+            row_keys.push(String::from_utf8(row_key.to_vec()).map_err(|_| {
+                Error::ObjectCorrupt(format!("{table}/{}", String::from_utf8_lossy(row_key)))
+            })?);
+            iterator.next();
+        }
+        iterator.status()?;
+        Ok(row_keys)
+    }
+
+    fn get_bincode_row<T: DeserializeOwned>(&self, table: &str, key: &str) -> Result<Option<T>> {
+        self.db
+            .get_pinned_cf(self.table(table), key)?
+            .map(|data| {
+                bincode::deserialize(&data).map_err(|err| {
+                    warn!("Failed to deserialize {}/{}: {}", table, key, err);
+                    Error::ObjectCorrupt(format!("{table}/{key}"))
+                })
+            })
+            .transpose()
+    }
+
+    fn get_protobuf_row<T: Message + Default>(&self, table: &str, key: &str) -> Result<Option<T>> {
+        self.db
+            .get_pinned_cf(self.table(table), key)?
+            .map(|data| decode_protobuf_row(table, key, &data))
+            .transpose()
+    }
+
+    fn put_bincode_row<T: Serialize>(
+        &self,
+        batch: &mut WriteBatch,
+        table: &str,
+        key: &str,
+        data: &T,
+    ) -> usize {
+        let data = bincode::serialize(data).unwrap();
+        batch.put_cf(self.table(table), key, &data);
+        data.len()
+    }
+
+    fn put_protobuf_row<T: Message>(
+        &self,
+        batch: &mut WriteBatch,
+        table: &str,
+        key: &str,
+        data: &T,
+    ) -> usize {
+        let data = data.encode_to_vec();
+        batch.put_cf(self.table(table), key, &data);
+        data.len()
+    }
+
+    fn get_transaction_info(&self, signature: &Signature) -> crate::Result<TransactionInfo> {
+        self.get_bincode_row(TX, &signature.to_string())?
+            .ok_or(crate::Error::SignatureNotFound)
+    }
+
+    /// Run `f` on the blocking thread pool of the runtime, as every RocksDB call blocks. A
+    /// secondary instance first catches up with the writes of the primary one.
+    async fn run_blocking<T, F>(&self, f: F) -> crate::Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Self) -> crate::Result<T> + Send + 'static,
+    {
+        let storage = self.clone();
+        tokio::task::spawn_blocking(move || {
+            if storage.secondary {
+                storage
+                    .db
+                    .try_catch_up_with_primary()
+                    .map_err(Error::from)?;
+            }
+            f(&storage)
+        })
+        .await
+        .map_err(crate::Error::TokioJoinError)?
+    }
+
+    fn first_available_block(&self) -> crate::Result<Option<Slot>> {
+        Ok(self
+            .get_row_keys(BLOCKS, &slot_to_blocks_key(0), 1)?
+            .first()
+            .and_then(|key| key_to_slot(key)))
+    }
+
+    fn confirmed_blocks(&self, start_slot: Slot, limit: usize) -> crate::Result<Vec<Slot>> {
+        Ok(self
+            .get_row_keys(BLOCKS, &slot_to_blocks_key(start_slot), limit)?
+            .into_iter()
+            .filter_map(|key| key_to_slot(&key))
+            .collect())
+    }
+
+    fn confirmed_block(&self, slot: Slot) -> crate::Result<ConfirmedBlock> {
+        let key = slot_to_blocks_key(slot);
+        let block = self
+            .get_protobuf_row::<generated::ConfirmedBlock>(BLOCKS, &key)?
+            .ok_or(crate::Error::BlockNotFound(slot))?;
+        Ok(block
+            .try_into()
+            .map_err(|_err| Error::ObjectCorrupt(format!("{BLOCKS}/{key}")))?)
+    }
+
+    fn confirmed_transaction(
+        &self,
+        signature: &Signature,
+    ) -> crate::Result<Option<ConfirmedTransactionWithStatusMeta>> {
+        let TransactionInfo { slot, index, .. } = self.get_transaction_info(signature)?;
+        let block = self.confirmed_block(slot)?;
+        Ok(confirmed_transaction_in_block(
+            signature, slot, index, block,
+        ))
+    }
+
+    fn confirmed_signatures_for_address(
+        &self,
+        address: &Pubkey,
+        before_signature: Option<&Signature>,
+        until_signature: Option<&Signature>,
+        limit: usize,
+    ) -> crate::Result<Vec<(ConfirmedTransactionStatusWithSignature, u32)>> {
+        let address_prefix = format!("{address}/");
+
+        // Figure out where to start listing from based on `before_signature`
+        let (first_slot, before_transaction_index) = match before_signature {
+            None => (Slot::MAX, 0),
+            Some(before_signature) => {
+                let TransactionInfo { slot, index, .. } =
+                    self.get_transaction_info(before_signature)?;
+                (slot, index)
+            }
+        };
+
+        // Figure out where to end listing from based on `until_signature`
+        let (last_slot, until_transaction_index) = match until_signature {
+            None => (0, u32::MAX),
+            Some(until_signature) => {
+                let TransactionInfo { slot, index, .. } =
+                    self.get_transaction_info(until_signature)?;
+                (slot, index)
+            }
+        };
+
+        let end_key = format!("{}{}", address_prefix, slot_to_tx_by_addr_key(last_slot));
+        let mut iterator = self.db.raw_iterator_cf(self.table(TX_BY_ADDR));
+        iterator.seek(format!(
+            "{}{}",
+            address_prefix,
+            slot_to_tx_by_addr_key(first_slot)
+        ));
+
+        let mut infos = vec![];
+        'outer: while iterator.valid() {
+            let row_key = String::from_utf8_lossy(iterator.key().unwrap()).into_owned();
+            if row_key > end_key {
+                break;
+            }
+            let slot = !key_to_slot(&row_key[address_prefix.len()..]).ok_or_else(|| {
+                Error::ObjectCorrupt(format!(
+                    "Failed to convert key to slot: {TX_BY_ADDR}/{row_key}"
+                ))
+            })?;
+            let mut row_data: Vec<TransactionByAddrInfo> =
+                decode_protobuf_row::<tx_by_addr::TransactionByAddr>(
+                    TX_BY_ADDR,
+                    &row_key,
+                    iterator.value().unwrap(),
+                )?
+                .try_into()
+                .map_err(|error| {
+                    Error::ObjectCorrupt(format!(
+                        "Failed to deserialize: {error}: {TX_BY_ADDR}/{row_key}"
+                    ))
+                })?;
+
+            row_data.reverse();
+            for tx_by_addr_info in row_data.into_iter() {
+                // Filter out records before `before_transaction_index`
+                if slot == first_slot && tx_by_addr_info.index >= before_transaction_index {
+                    continue;
+                }
+                // Filter out records after `until_transaction_index`
+                if slot == last_slot && tx_by_addr_info.index <= until_transaction_index {
+                    continue;
+                }
+                infos.push((
+                    ConfirmedTransactionStatusWithSignature {
+                        signature: tx_by_addr_info.signature,
+                        slot,
+                        err: tx_by_addr_info.err,
+                        memo: tx_by_addr_info.memo,
+                        block_time: tx_by_addr_info.block_time,
+                    },
+                    tx_by_addr_info.index,
+                ));
+                // Respect limit
+                if infos.len() >= limit {
+                    break 'outer;
+                }
+            }
+            iterator.next();
+        }
+        iterator.status().map_err(Error::from)?;
+        Ok(infos)
+    }
+
+    fn write_accumulator(&self, slot: Slot, accumulator: &Accumulator) -> crate::Result<()> {
+        let mut batch = WriteBatch::default();
+        let bytes_written = self.put_bincode_row(
+            &mut batch,
+            ACCUMULATOR,
+            &slot_to_blocks_key(slot),
+            accumulator,
+        );
+        self.db.write(batch).map_err(Error::from)?;
+        datapoint_info!(
+            "storage-local-upload-accumulator",
+            ("slot", slot, i64),
+            ("bytes", bytes_written, i64),
+        );
+        Ok(())
+    }
+
+    fn write_confirmed_block(
+        &self,
+        slot: Slot,
+        confirmed_block: VersionedConfirmedBlock,
+    ) -> crate::Result<()> {
+        let (tx_cells, tx_by_addr_cells) = transaction_cells(slot, &confirmed_block);
+        let num_transactions = confirmed_block.transactions.len();
+
+        // The block and all its meta data are written in a single batch, so that a partially
+        // uploaded block never becomes visible to `get_confirmed_block()`
+        let mut batch = WriteBatch::default();
+        let mut bytes_written = 0;
+        for (key, transaction_info) in &tx_cells {
+            bytes_written += self.put_bincode_row(&mut batch, TX, key, transaction_info);
+        }
+        for (key, tx_by_addr) in &tx_by_addr_cells {
+            bytes_written += self.put_protobuf_row(&mut batch, TX_BY_ADDR, key, tx_by_addr);
+        }
+        bytes_written += self.put_protobuf_row(
+            &mut batch,
+            BLOCKS,
+            &slot_to_blocks_key(slot),
+            &generated::ConfirmedBlock::from(confirmed_block),
+        );
+        self.db.write(batch).map_err(Error::from)?;
+        datapoint_info!(
+            "storage-local-upload-block",
+            ("slot", slot, i64),
+            ("transactions", num_transactions, i64),
+            ("bytes", bytes_written, i64),
+        );
+        Ok(())
+    }
+
+    fn delete_block(&self, slot: Slot, dry_run: bool) -> crate::Result<()> {
+        let confirmed_block = self.confirmed_block(slot)?;
+
+        let mut batch = WriteBatch::default();
+        let mut addresses: HashSet<Pubkey> = HashSet::new();
+        let mut num_tx_rows = 0;
+        for (index, transaction_with_meta) in confirmed_block.transactions.iter().enumerate() {
+            let signature = transaction_with_meta.transaction_signature();
+            for address in transaction_with_meta.account_keys().iter() {
+                if !is_sysvar_id(address) {
+                    addresses.insert(*address);
+                }
+            }
+
+            // Leave the row alone if the transaction was uploaded again as part of another block
+            match self.get_bincode_row::<TransactionInfo>(TX, &signature.to_string()) {
+                Ok(Some(transaction_info))
+                    if transaction_info.slot == slot && transaction_info.index == index as u32 =>
+                {
+                    batch.delete_cf(self.table(TX), signature.to_string());
+                    num_tx_rows += 1;
+                }
+                Ok(transaction_info) => {
+                    warn!(
+                        "skipped tx row {} because the entry ({:?}) is not from slot {}",
+                        signature, transaction_info, slot
+                    );
+                }
+                Err(err) => {
+                    warn!(
+                        "skipped tx row {} because the entry was corrupted: {:?}",
+                        signature, err
+                    );
+                }
+            }
+        }
+
+        for address in &addresses {
+            batch.delete_cf(
+                self.table(TX_BY_ADDR),
+                format!("{}/{}", address, slot_to_tx_by_addr_key(slot)),
+            );
+        }
+        batch.delete_cf(self.table(BLOCKS), slot_to_blocks_key(slot));
+        batch.delete_cf(self.table(ACCUMULATOR), slot_to_blocks_key(slot));
+
+        if !dry_run {
+            self.db.write(batch).map_err(Error::from)?;
+        }
+
+        info!(
+            "{}deleted ledger data for slot {}: {} transaction rows, {} address slot rows",
+            if dry_run { "[dry run] " } else { "" },
+            slot,
+            num_tx_rows,
+            addresses.len()
+        );
+
+        Ok(())
+    }
+}
+
+fn decode_protobuf_row<T: Message + Default>(table: &str, key: &str, data: &[u8]) -> Result<T> {
+    T::decode(data).map_err(|err| {
+        warn!("Failed to deserialize {}/{}: {}", table, key, err);
+        Error::ObjectCorrupt(format!("{table}/{key}"))
+    })
+}
+
+#[async_trait]
+impl LedgerStorageAdapter for LocalLedgerStorage {
+    async fn get_first_available_block(&self) -> crate::Result<Option<Slot>> {
+        debug!("LocalLedgerStorage::get_first_available_block request received");
+        self.run_blocking(|storage| storage.first_available_block())
+            .await
+    }
+
+    async fn get_confirmed_blocks(
+        &self,
+        start_slot: Slot,
+        limit: usize,
+    ) -> crate::Result<Vec<Slot>> {
+        debug!(
+            "LocalLedgerStorage::get_confirmed_blocks request received: {:?} {:?}",
+            start_slot, limit
+        );
+        self.run_blocking(move |storage| storage.confirmed_blocks(start_slot, limit))
+            .await
+    }
+
+    async fn get_confirmed_block(&self, slot: Slot) -> crate::Result<ConfirmedBlock> {
+        debug!(
+            "LocalLedgerStorage::get_confirmed_block request received: {:?}",
+            slot
+        );
+        self.run_blocking(move |storage| storage.confirmed_block(slot))
+            .await
+    }
+
+    async fn get_accumulator(&self, slot: Slot) -> crate::Result<Option<Accumulator>> {
+        debug!(
+            "LocalLedgerStorage::get_accumulator request received: {:?}",
+            slot
+        );
+        self.run_blocking(move |storage| {
+            Ok(storage.get_bincode_row(ACCUMULATOR, &slot_to_blocks_key(slot))?)
+        })
+        .await
+    }
+
+    async fn get_signature_status(
+        &self,
+        signature: &Signature,
+    ) -> crate::Result<TransactionStatus> {
+        debug!(
+            "LocalLedgerStorage::get_signature_status request received: {:?}",
+            signature
+        );
+        let signature = *signature;
+        self.run_blocking(move |storage| Ok(storage.get_transaction_info(&signature)?.into()))
+            .await
+    }
+
+    async fn get_confirmed_transaction(
+        &self,
+        signature: &Signature,
+    ) -> crate::Result<Option<ConfirmedTransactionWithStatusMeta>> {
+        debug!(
+            "LocalLedgerStorage::get_confirmed_transaction request received: {:?}",
+            signature
+        );
+        let signature = *signature;
+        self.run_blocking(move |storage| storage.confirmed_transaction(&signature))
+            .await
+    }
+
+    async fn get_confirmed_signatures_for_address(
+        &self,
+        address: &Pubkey,
+        before_signature: Option<&Signature>,
+        until_signature: Option<&Signature>,
+        limit: usize,
+    ) -> crate::Result<Vec<(ConfirmedTransactionStatusWithSignature, u32)>> {
+        debug!(
+            "LocalLedgerStorage::get_confirmed_signatures_for_address request received: {:?}",
+            address
+        );
+        let address = *address;
+        let before_signature = before_signature.copied();
+        let until_signature = until_signature.copied();
+        self.run_blocking(move |storage| {
+            storage.confirmed_signatures_for_address(
+                &address,
+                before_signature.as_ref(),
+                until_signature.as_ref(),
+                limit,
+            )
+        })
+        .await
+    }
+
+    async fn upload_accumulator(&self, slot: Slot, accumulator: Accumulator) -> crate::Result<()> {
+        self.run_blocking(move |storage| storage.write_accumulator(slot, &accumulator))
+            .await
+    }
+
+    async fn upload_confirmed_block(
+        &self,
+        slot: Slot,
+        confirmed_block: VersionedConfirmedBlock,
+    ) -> crate::Result<()> {
+        self.run_blocking(move |storage| storage.write_confirmed_block(slot, confirmed_block))
+            .await
+    }
+
+    async fn delete_confirmed_block(&self, slot: Slot, dry_run: bool) -> crate::Result<()> {
+        self.run_blocking(move |storage| storage.delete_block(slot, dry_run))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_sdk::{
+            hash::Hash,
+            message::v0::LoadedAddresses,
+            signature::{Keypair, Signer},
+            system_transaction,
+            transaction::VersionedTransaction,
+        },
+        solana_transaction_status::{TransactionStatusMeta, VersionedTransactionWithStatusMeta},
+    };
+
+    /// Run `future` to completion on a runtime, which the storage needs to run its RocksDB calls
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    fn transfer(from: &Keypair, to: &Pubkey, lamports: u64) -> VersionedTransactionWithStatusMeta {
+        let transaction = system_transaction::transfer(from, to, lamports, Hash::new_unique());
+        VersionedTransactionWithStatusMeta {
+            transaction: VersionedTransaction::from(transaction),
+            meta: TransactionStatusMeta {
+                status: Ok(()),
+                fee: 5_000,
+                pre_balances: vec![lamports + 5_000, 0, 1],
+                post_balances: vec![0, lamports, 1],
+                inner_instructions: Some(vec![]),
+                log_messages: Some(vec![]),
+                pre_token_balances: Some(vec![]),
+                post_token_balances: Some(vec![]),
+                rewards: Some(vec![]),
+                loaded_addresses: LoadedAddresses::default(),
+                return_data: None,
+                compute_units_consumed: Some(150),
+            },
+        }
+    }
+
+    fn block(
+        slot: Slot,
+        transactions: Vec<VersionedTransactionWithStatusMeta>,
+    ) -> VersionedConfirmedBlock {
+        VersionedConfirmedBlock {
+            previous_blockhash: Hash::new_unique().to_string(),
+            blockhash: Hash::new_unique().to_string(),
+            parent_slot: slot.saturating_sub(1),
+            transactions,
+            rewards: vec![],
+            block_time: Some(1_700_000_000 + slot as i64),
+            block_height: Some(slot),
+        }
+    }
+
+    fn signatures(
+        results: crate::Result<Vec<(ConfirmedTransactionStatusWithSignature, u32)>>,
+    ) -> Vec<(Signature, Slot)> {
+        results
+            .unwrap()
+            .into_iter()
+            .map(|(status, _)| (status.signature, status.slot))
+            .collect()
+    }
+
+    #[test]
+    fn test_local_ledger_storage() {
+        let ledger_path = tempfile::tempdir().unwrap();
+        let storage = LocalLedgerStorage::open(ledger_path.path(), false).unwrap();
+        let payer = Keypair::new();
+        let recipient = Pubkey::new_unique();
+
+        assert_eq!(block_on(storage.get_first_available_block()).unwrap(), None);
+        assert!(matches!(
+            block_on(storage.get_confirmed_block(1)),
+            Err(crate::Error::BlockNotFound(1))
+        ));
+
+        let tx_1 = transfer(&payer, &recipient, 1);
+        let tx_2 = transfer(&payer, &recipient, 2);
+        let tx_3 = transfer(&payer, &recipient, 3);
+        let [sig_1, sig_2, sig_3] = [&tx_1, &tx_2, &tx_3].map(|tx| tx.transaction.signatures[0]);
+        let block_5 = block(5, vec![tx_1]);
+        let block_8 = block(8, vec![tx_2, tx_3]);
+        block_on(storage.upload_confirmed_block(5, block_5.clone())).unwrap();
+        block_on(storage.upload_confirmed_block(8, block_8)).unwrap();
+        let accumulator = Accumulator {
+            slot: 8,
+            root: Hash::new_unique(),
+            messages: vec![vec![1, 2, 3]],
+        };
+        block_on(storage.upload_accumulator(8, accumulator.clone())).unwrap();
+
+        assert_eq!(
+            block_on(storage.get_first_available_block()).unwrap(),
+            Some(5)
+        );
+        assert_eq!(
+            block_on(storage.get_confirmed_blocks(0, 10)).unwrap(),
+            vec![5, 8]
+        );
+        assert_eq!(
+            block_on(storage.get_confirmed_blocks(6, 10)).unwrap(),
+            vec![8]
+        );
+        assert_eq!(
+            block_on(storage.get_confirmed_blocks(0, 1)).unwrap(),
+            vec![5]
+        );
+        assert_eq!(
+            block_on(storage.get_confirmed_block(5)).unwrap(),
+            ConfirmedBlock::from(block_5)
+        );
+        assert_eq!(
+            block_on(storage.get_accumulator(8)).unwrap(),
+            Some(accumulator)
+        );
+        assert_eq!(block_on(storage.get_accumulator(5)).unwrap(), None);
+
+        let status = block_on(storage.get_signature_status(&sig_3)).unwrap();
+        assert_eq!((status.slot, status.err), (8, None));
+        let transaction = block_on(storage.get_confirmed_transaction(&sig_3))
+            .unwrap()
+            .unwrap();
+        assert_eq!(transaction.slot, 8);
+        assert_eq!(transaction.tx_with_meta.transaction_signature(), &sig_3);
+        assert!(matches!(
+            block_on(storage.get_signature_status(&Signature::default())),
+            Err(crate::Error::SignatureNotFound)
+        ));
+
+        // Signatures are listed in descending ledger order
+        for address in [payer.pubkey(), recipient] {
+            assert_eq!(
+                signatures(block_on(
+                    storage.get_confirmed_signatures_for_address(&address, None, None, 10)
+                )),
+                vec![(sig_3, 8), (sig_2, 8), (sig_1, 5)]
+            );
+        }
+        assert_eq!(
+            signatures(block_on(
+                storage.get_confirmed_signatures_for_address(&recipient, None, None, 2)
+            )),
+            vec![(sig_3, 8), (sig_2, 8)]
+        );
+        assert_eq!(
+            signatures(block_on(storage.get_confirmed_signatures_for_address(
+                &recipient,
+                Some(&sig_3),
+                None,
+                10
+            ))),
+            vec![(sig_2, 8), (sig_1, 5)]
+        );
+        assert_eq!(
+            signatures(block_on(storage.get_confirmed_signatures_for_address(
+                &recipient,
+                None,
+                Some(&sig_1),
+                10
+            ))),
+            vec![(sig_3, 8), (sig_2, 8)]
+        );
+        assert_eq!(
+            signatures(block_on(storage.get_confirmed_signatures_for_address(
+                &recipient,
+                Some(&sig_3),
+                Some(&sig_1),
+                10
+            ))),
+            vec![(sig_2, 8)]
+        );
+        assert!(
+            signatures(block_on(storage.get_confirmed_signatures_for_address(
+                &Pubkey::new_unique(),
+                None,
+                None,
+                10
+            )))
+            .is_empty()
+        );
+
+        // A dry run leaves the block in place
+        block_on(storage.delete_confirmed_block(8, true)).unwrap();
+        assert!(block_on(storage.get_confirmed_block(8)).is_ok());
+        block_on(storage.delete_confirmed_block(8, false)).unwrap();
+        assert!(matches!(
+            block_on(storage.get_confirmed_block(8)),
+            Err(crate::Error::BlockNotFound(8))
+        ));
+        assert_eq!(block_on(storage.get_accumulator(8)).unwrap(), None);
+        assert!(matches!(
+            block_on(storage.get_signature_status(&sig_2)),
+            Err(crate::Error::SignatureNotFound)
+        ));
+        assert_eq!(
+            signatures(block_on(
+                storage.get_confirmed_signatures_for_address(&recipient, None, None, 10)
+            )),
+            vec![(sig_1, 5)]
+        );
+
+        // The uploaded blocks can be read by another, read only, instance, which follows the
+        // blocks uploaded after it was opened
+        let read_only_storage = LocalLedgerStorage::open(ledger_path.path(), true).unwrap();
+        assert_eq!(
+            block_on(read_only_storage.get_confirmed_blocks(0, 10)).unwrap(),
+            vec![5]
+        );
+        block_on(storage.upload_confirmed_block(9, block(9, vec![]))).unwrap();
+        assert_eq!(
+            block_on(read_only_storage.get_confirmed_blocks(0, 10)).unwrap(),
+            vec![5, 9]
+        );
+        assert!(block_on(read_only_storage.upload_confirmed_block(10, block(10, vec![]))).is_err());
+    }
+}
@@ -0,0 +1,309 @@
+//! Replay of recorded account states on a running test validator.
+//!
+//! A recording holds one account state per line, as written by
+//! `solana account --output json` with the slot the state was observed in:
+//!
+//! ```text
+//! {"slot":1000,"pubkey":"...","account":{"lamports":1,"data":["","base64"],...}}
+//! ```
+//!
+//! Such a stream can be produced from the account updates of a geyser plugin
+//! or from the accounts written in a range of a ledger. The states of the
+//! first recorded slot are added to genesis, and the states of every later
+//! slot are written to the first leader bank the validator creates once it has
+//! advanced as many slots past its start as the recording did, so consumers
+//! see the accounts change at the recorded pace. The states are written before
+//! the bank is handed to banking stage, and update the capitalization of the
+//! bank by the lamports they add or remove.
+
+use {
+    log::*,
+    serde_derive::Deserialize,
+    solana_client::rpc_response::RpcKeyedAccount,
+    solana_core::replay_stage::LeaderBankCallback,
+    solana_runtime::bank::Bank,
+    solana_sdk::{
+        account::{AccountSharedData, ReadableAccount, WritableAccount},
+        clock::Slot,
+        pubkey::Pubkey,
+        pyth_oracle::{self, PriceAccount},
+    },
+    std::{
+        collections::{BTreeMap, HashMap},
+        fs::File,
+        io::{BufRead, BufReader},
+        path::Path,
+        str::FromStr,
+        sync::{Arc, Mutex},
+    },
+};
+
+#[derive(Deserialize)]
+struct RecordedAccount {
+    slot: Slot,
+    #[serde(flatten)]
+    keyed_account: RpcKeyedAccount,
+}
+
+/// Recorded account states, by the slot they were observed in.
+#[derive(Clone, Debug, Default)]
+pub struct AccountReplay {
+    states: BTreeMap<Slot, Vec<(Pubkey, AccountSharedData)>>,
+}
+
+impl AccountReplay {
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    /// Record the state of `address` in `slot`.
+    pub fn add_account_state(
+        &mut self,
+        slot: Slot,
+        address: Pubkey,
+        account: AccountSharedData,
+    ) -> &mut Self {
+        self.states
+            .entry(slot)
+            .or_default()
+            .push((address, account));
+        self
+    }
+
+    /// Add the account states of a recording file.
+    pub fn add_recording_file<P: AsRef<Path>>(&mut self, path: P) -> Result<&mut Self, String> {
+        let path = path.as_ref();
+        let file = File::open(path)
+            .map_err(|err| format!("Unable to open {}: {}", path.display(), err))?;
+        self.add_recording(BufReader::new(file))
+            .map_err(|err| format!("Unable to read {}: {}", path.display(), err))
+    }
+
+    /// Add the account states of a recording, one JSON object per line.
+    pub fn add_recording<R: BufRead>(&mut self, reader: R) -> Result<&mut Self, String> {
+        for (index, line) in reader.lines().enumerate() {
+            let line = line.map_err(|err| err.to_string())?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let invalid_line = |err: String| format!("line {}: {}", index + 1, err);
+            let recorded: RecordedAccount =
+                serde_json::from_str(&line).map_err(|err| invalid_line(err.to_string()))?;
+            let address = Pubkey::from_str(&recorded.keyed_account.pubkey)
+                .map_err(|err| invalid_line(err.to_string()))?;
+            let account = recorded
+                .keyed_account
+                .account
+                .decode::<AccountSharedData>()
+                .ok_or_else(|| invalid_line(format!("unable to decode account {address}")))?;
+            self.add_account_state(recorded.slot, address, account);
+        }
+        Ok(self)
+    }
+
+    /// The states of the first recorded slot, which make up the accounts of
+    /// genesis.
+    pub fn initial_accounts(&self) -> HashMap<Pubkey, AccountSharedData> {
+        self.states
+            .iter()
+            .next()
+            .map(|(recorded_slot, states)| {
+                states
+                    .iter()
+                    .map(|(address, account)| {
+                        let mut account = account.clone();
+                        rebase_price_account(&mut account, *recorded_slot, 0);
+                        (*address, account)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// A callback that writes the states recorded after the first slot to
+    /// the leader banks of the validator as it advances.
+    pub(crate) fn leader_bank_callback(self) -> LeaderBankCallback {
+        let mut states = self.states;
+        let first_slot = match states.keys().next() {
+            Some(first_slot) => *first_slot,
+            None => return Arc::new(|_| ()),
+        };
+        // the states of the first slot are in genesis
+        states.remove(&first_slot);
+        let progress = Mutex::new(ReplayProgress {
+            states,
+            first_slot,
+            start_slot: None,
+        });
+        Arc::new(move |bank| progress.lock().unwrap().replay(bank))
+    }
+}
+
+struct ReplayProgress {
+    /// Recorded states not replayed yet
+    states: BTreeMap<Slot, Vec<(Pubkey, AccountSharedData)>>,
+    first_slot: Slot,
+    /// Slot of the first leader bank, in which the first slot is replayed
+    start_slot: Option<Slot>,
+}
+
+impl ReplayProgress {
+    /// Write to `bank` the states recorded up to as many slots past the first
+    /// recorded slot as `bank` is past the first leader bank.
+    fn replay(&mut self, bank: &Bank) {
+        if self.states.is_empty() {
+            return;
+        }
+        let start_slot = *self.start_slot.get_or_insert(bank.slot());
+        let replay_until = self
+            .first_slot
+            .saturating_add(bank.slot().saturating_sub(start_slot));
+        let later_states = self.states.split_off(&replay_until.saturating_add(1));
+        let due_states = std::mem::replace(&mut self.states, later_states);
+        for (recorded_slot, slot_states) in due_states {
+            debug!(
+                "Replaying {} accounts of slot {} in slot {}",
+                slot_states.len(),
+                recorded_slot,
+                bank.slot()
+            );
+            for (address, mut account) in slot_states {
+                rebase_price_account(&mut account, recorded_slot, bank.slot());
+                bank.store_account_and_update_capitalization(&address, &account);
+            }
+        }
+        if self.states.is_empty() {
+            info!("Account replay complete");
+        }
+    }
+}
+
+/// Shift the slots of a Pyth price account recorded in `recorded_slot` to
+/// `slot`, so that its prices are as recent as when they were recorded rather
+/// than stale. Other accounts are left untouched.
+fn rebase_price_account(account: &mut AccountSharedData, recorded_slot: Slot, slot: Slot) {
+    if account.owner() != &pyth_oracle::id() {
+        return;
+    }
+    let mut price = match PriceAccount::from_account_data(account.data()) {
+        Some(price) => price,
+        None => return,
+    };
+    let rebase = |recorded: &mut Slot| {
+        if *recorded != 0 {
+            *recorded = recorded.saturating_add(slot).saturating_sub(recorded_slot);
+        }
+    };
+    rebase(&mut price.agg.pub_slot);
+    rebase(&mut price.valid_slot);
+    rebase(&mut price.last_slot);
+    rebase(&mut price.prev_slot);
+    let mut components = price.components(account.data());
+    for component in components.iter_mut() {
+        rebase(&mut component.agg.pub_slot);
+        rebase(&mut component.latest.pub_slot);
+    }
+    price.write_account_data(&components, account.data_as_mut_slice());
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_program_test::pyth::PythOracle,
+        solana_runtime::genesis_utils::create_genesis_config,
+        solana_sdk::{account::Account, system_program},
+    };
+
+    #[test]
+    fn test_add_recording() {
+        let address = Pubkey::new_unique();
+        let recording = format!(
+            "{{\"slot\":12,\"pubkey\":\"{address}\",\"account\":{{\"lamports\":2,\"data\":[\"AQI=\",\"base64\"],\"owner\":\"{}\",\"executable\":false,\"rentEpoch\":0}}}}\n\
+             \n\
+             {{\"slot\":10,\"pubkey\":\"{address}\",\"account\":{{\"lamports\":1,\"data\":[\"\",\"base64\"],\"owner\":\"{}\",\"executable\":false,\"rentEpoch\":0}}}}\n",
+            system_program::id(),
+            system_program::id(),
+        );
+        let mut replay = AccountReplay::default();
+        replay.add_recording(recording.as_bytes()).unwrap();
+
+        assert_eq!(
+            replay.states.keys().copied().collect::<Vec<_>>(),
+            vec![10, 12]
+        );
+        let initial_accounts = replay.initial_accounts();
+        assert_eq!(initial_accounts.len(), 1);
+        assert_eq!(initial_accounts[&address].lamports(), 1);
+        let (_, later) = &replay.states[&12][0];
+        assert_eq!(later.data(), &[1, 2]);
+
+        assert!(AccountReplay::default()
+            .add_recording("{\"slot\":1}".as_bytes())
+            .unwrap_err()
+            .starts_with("line 1:"));
+    }
+
+    #[test]
+    fn test_leader_bank_callback() {
+        let address = Pubkey::new_unique();
+        let mut replay = AccountReplay::default();
+        for (slot, lamports) in [(10, 1), (12, 5), (13, 3)] {
+            replay.add_account_state(
+                slot,
+                address,
+                AccountSharedData::new(lamports, 0, &system_program::id()),
+            );
+        }
+        let leader_bank_callback = replay.leader_bank_callback();
+
+        let genesis_config = create_genesis_config(1_000_000).genesis_config;
+        let bank0 = Arc::new(Bank::new_for_tests(&genesis_config));
+        let bank1 = Arc::new(Bank::new_from_parent(&bank0, &Pubkey::default(), 1));
+        // the first recorded slot belongs to genesis
+        leader_bank_callback(&bank1);
+        assert_eq!(bank1.get_balance(&address), 0);
+
+        // slots skipped by the validator are caught up on in the next leader bank
+        let bank3 = Bank::new_from_parent(&bank1, &Pubkey::default(), 3);
+        let capitalization = bank3.capitalization();
+        leader_bank_callback(&bank3);
+        assert_eq!(bank3.get_balance(&address), 5);
+        assert_eq!(bank3.capitalization(), capitalization + 5);
+        assert!(bank3.calculate_and_verify_capitalization(true));
+    }
+
+    #[test]
+    fn test_rebase_price_account() {
+        let mut oracle = PythOracle::new();
+        let feed = oracle.add_price_feed("Crypto.BTC/USD", -8, 2);
+        let (_, account) = oracle
+            .genesis_accounts()
+            .into_iter()
+            .find(|(address, _)| *address == feed.price)
+            .unwrap();
+        let mut account = AccountSharedData::from(account);
+
+        let mut price = PriceAccount::from_account_data(account.data()).unwrap();
+        let mut components = price.components(account.data());
+        price.agg.pub_slot = 1_000;
+        components[0].latest.pub_slot = 998;
+        price.write_account_data(&components, account.data_as_mut_slice());
+
+        rebase_price_account(&mut account, 1_000, 50);
+        let price = PriceAccount::from_account_data(account.data()).unwrap();
+        let components = price.components(account.data());
+        assert_eq!(price.agg.pub_slot, 50);
+        assert_eq!(components[0].latest.pub_slot, 48);
+        // slots that were never set stay unset
+        assert_eq!(components[1].latest.pub_slot, 0);
+
+        // other accounts are not price accounts even if their data looks alike
+        let mut other = AccountSharedData::from(Account {
+            owner: system_program::id(),
+            ..Account::from(account.clone())
+        });
+        rebase_price_account(&mut other, 50, 1_000);
+        assert_eq!(other.data(), account.data());
+    }
+}
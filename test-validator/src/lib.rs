@@ -1,10 +1,12 @@
 #![allow(clippy::integer_arithmetic)]
 
 use {
+    crate::account_replay::AccountReplay,
     log::*,
     solana_cli_output::CliAccount,
     solana_client::rpc_request::MAX_MULTIPLE_ACCOUNTS,
     solana_core::{
+        publisher_lane::PublisherLane,
         tower_storage::TowerStorage,
         validator::{Validator, ValidatorConfig, ValidatorStartProgress},
     },
@@ -62,6 +64,8 @@ use {
     tokio::time::sleep,
 };
 
+pub mod account_replay;
+
 #[derive(Clone)]
 pub struct AccountInfo<'a> {
     pub address: Option<Pubkey>,
@@ -109,6 +113,7 @@ pub struct TestValidatorGenesis {
     warp_slot: Option<Slot>,
     no_bpf_jit: bool,
     accounts: HashMap<Pubkey, AccountSharedData>,
+    account_replay: AccountReplay,
     programs: Vec<ProgramInfo>,
     ticks_per_slot: Option<u64>,
     epoch_schedule: Option<EpochSchedule>,
@@ -117,6 +122,7 @@ pub struct TestValidatorGenesis {
     pub start_progress: Arc<RwLock<ValidatorStartProgress>>,
     pub authorized_voter_keypairs: Arc<RwLock<Vec<Arc<Keypair>>>>,
     pub staked_nodes_overrides: Arc<RwLock<HashMap<Pubkey, u64>>>,
+    pub publisher_lane: Arc<RwLock<Arc<PublisherLane>>>,
     pub max_ledger_shreds: Option<u64>,
     pub max_genesis_archive_unpacked_size: Option<u64>,
     pub geyser_plugin_config_files: Option<Vec<PathBuf>>,
@@ -140,6 +146,7 @@ impl Default for TestValidatorGenesis {
             warp_slot: Option::<Slot>::default(),
             no_bpf_jit: bool::default(),
             accounts: HashMap::<Pubkey, AccountSharedData>::default(),
+            account_replay: AccountReplay::default(),
             programs: Vec::<ProgramInfo>::default(),
             ticks_per_slot: Option::<u64>::default(),
             epoch_schedule: Option::<EpochSchedule>::default(),
@@ -148,6 +155,7 @@ impl Default for TestValidatorGenesis {
             start_progress: Arc::<RwLock<ValidatorStartProgress>>::default(),
             authorized_voter_keypairs: Arc::<RwLock<Vec<Arc<Keypair>>>>::default(),
             staked_nodes_overrides: Arc::new(RwLock::new(HashMap::new())),
+            publisher_lane: Arc::default(),
             max_ledger_shreds: Option::<u64>::default(),
             max_genesis_archive_unpacked_size: Option::<u64>::default(),
             geyser_plugin_config_files: Option::<Vec<PathBuf>>::default(),
@@ -385,6 +393,26 @@ impl TestValidatorGenesis {
         Ok(self)
     }
 
+    /// Replay recorded account states slot by slot once the validator has
+    /// started. See [`account_replay`] for the format of recordings.
+    pub fn account_replay(&mut self, account_replay: AccountReplay) -> &mut Self {
+        self.account_replay = account_replay;
+        self
+    }
+
+    /// Replay the account states recorded in `files`, in addition to any
+    /// already added
+    pub fn replay_accounts_from_files<T, P>(&mut self, files: T) -> Result<&mut Self, String>
+    where
+        T: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        for file in files {
+            self.account_replay.add_recording_file(file)?;
+        }
+        Ok(self)
+    }
+
     /// Add an account to the test environment with the account data in the provided `filename`
     pub fn add_account_with_file_data(
         &mut self,
@@ -624,6 +652,9 @@ impl TestValidator {
         let mint_lamports = sol_to_lamports(500_000_000.);
 
         let mut accounts = config.accounts.clone();
+        for (address, account) in config.account_replay.initial_accounts() {
+            accounts.entry(address).or_insert(account);
+        }
         for (address, account) in solana_program_test::programs::spl_programs(&config.rent) {
             accounts.entry(address).or_insert(account);
         }
@@ -818,6 +849,7 @@ impl TestValidator {
             max_ledger_shreds: config.max_ledger_shreds,
             no_wait_for_vote_to_start_leader: true,
             staked_nodes_overrides: config.staked_nodes_overrides.clone(),
+            publisher_lane: config.publisher_lane.clone(),
             accounts_db_config,
             runtime_config,
             account_indexes: config.rpc_config.account_indexes.clone(),
@@ -826,6 +858,10 @@ impl TestValidator {
         if let Some(ref tower_storage) = config.tower_storage {
             validator_config.tower_storage = tower_storage.clone();
         }
+        if !config.account_replay.is_empty() {
+            validator_config.leader_bank_callback =
+                Some(config.account_replay.clone().leader_bank_callback());
+        }
 
         let validator = Some(Validator::new(
             node,
@@ -999,7 +1035,7 @@ impl Drop for TestValidator {
 
 #[cfg(test)]
 mod test {
-    use super::*;
+    use {super::*, solana_sdk::system_program, std::thread};
 
     #[test]
     fn get_health() {
@@ -1017,6 +1053,34 @@ mod test {
         rpc_client.get_health().await.expect("health");
     }
 
+    #[test]
+    fn replay_accounts() {
+        let address = Pubkey::new_unique();
+        let mut account_replay = AccountReplay::default();
+        for (slot, lamports) in [(100, 1_000_000), (103, 2_000_000)] {
+            account_replay.add_account_state(
+                slot,
+                address,
+                AccountSharedData::new(lamports, 0, &system_program::id()),
+            );
+        }
+        let (test_validator, _payer) = TestValidatorGenesis::default()
+            .account_replay(account_replay)
+            .start();
+        let rpc_client = test_validator.get_rpc_client();
+
+        let mut balance = rpc_client.get_balance(&address).unwrap();
+        assert!(balance == 1_000_000 || balance == 2_000_000);
+        for _ in 0..100 {
+            if balance == 2_000_000 {
+                break;
+            }
+            thread::sleep(Duration::from_millis(DEFAULT_MS_PER_SLOT));
+            balance = rpc_client.get_balance(&address).unwrap();
+        }
+        assert_eq!(balance, 2_000_000);
+    }
+
     #[tokio::test]
     #[should_panic]
     async fn document_tokio_panic() {
@@ -40,6 +40,7 @@ pub mod parse_address_lookup_table;
 pub mod parse_associated_token;
 pub mod parse_bpf_loader;
 pub mod parse_instruction;
+pub mod parse_pyth_oracle;
 pub mod parse_stake;
 pub mod parse_system;
 pub mod parse_token;
@@ -4,6 +4,7 @@ use {
         parse_address_lookup_table::parse_address_lookup_table,
         parse_associated_token::{parse_associated_token, spl_associated_token_id},
         parse_bpf_loader::{parse_bpf_loader, parse_bpf_upgradeable_loader},
+        parse_pyth_oracle::parse_pyth_oracle,
         parse_stake::parse_stake,
         parse_system::parse_system,
         parse_token::parse_token,
@@ -13,7 +14,7 @@ use {
     serde_json::Value,
     solana_account_decoder::parse_token::spl_token_ids,
     solana_sdk::{
-        instruction::CompiledInstruction, message::AccountKeys, pubkey::Pubkey, stake,
+        instruction::CompiledInstruction, message::AccountKeys, pubkey::Pubkey, pyth_oracle, stake,
         system_program, vote,
     },
     std::{
@@ -30,6 +31,7 @@ lazy_static! {
     static ref BPF_UPGRADEABLE_LOADER_PROGRAM_ID: Pubkey = solana_sdk::bpf_loader_upgradeable::id();
     static ref MEMO_V1_PROGRAM_ID: Pubkey = spl_memo_id_v1();
     static ref MEMO_V3_PROGRAM_ID: Pubkey = spl_memo_id_v3();
+    static ref PYTH_ORACLE_PROGRAM_ID: Pubkey = pyth_oracle::id();
     static ref STAKE_PROGRAM_ID: Pubkey = stake::program::id();
     static ref SYSTEM_PROGRAM_ID: Pubkey = system_program::id();
     static ref VOTE_PROGRAM_ID: Pubkey = vote::program::id();
@@ -53,6 +55,7 @@ lazy_static! {
             *BPF_UPGRADEABLE_LOADER_PROGRAM_ID,
             ParsableProgram::BpfUpgradeableLoader,
         );
+        m.insert(*PYTH_ORACLE_PROGRAM_ID, ParsableProgram::PythOracle);
         m.insert(*STAKE_PROGRAM_ID, ParsableProgram::Stake);
         m.insert(*SYSTEM_PROGRAM_ID, ParsableProgram::System);
         m.insert(*VOTE_PROGRAM_ID, ParsableProgram::Vote);
@@ -102,6 +105,7 @@ pub enum ParsableProgram {
     SplToken,
     BpfLoader,
     BpfUpgradeableLoader,
+    PythOracle,
     Stake,
     System,
     Vote,
@@ -131,6 +135,9 @@ pub fn parse(
         ParsableProgram::BpfUpgradeableLoader => {
             serde_json::to_value(parse_bpf_upgradeable_loader(instruction, account_keys)?)?
         }
+        ParsableProgram::PythOracle => {
+            serde_json::to_value(parse_pyth_oracle(instruction, account_keys)?)?
+        }
         ParsableProgram::Stake => serde_json::to_value(parse_stake(instruction, account_keys)?)?,
         ParsableProgram::System => serde_json::to_value(parse_system(instruction, account_keys)?)?,
         ParsableProgram::Vote => serde_json::to_value(parse_vote(instruction, account_keys)?)?,
@@ -0,0 +1,168 @@
+use {
+    crate::parse_instruction::{
+        check_num_accounts, ParsableProgram, ParseInstructionError, ParsedInstructionEnum,
+    },
+    serde_json::json,
+    solana_account_decoder::parse_pyth_oracle::UiPriceStatus,
+    solana_sdk::{
+        instruction::CompiledInstruction,
+        message::AccountKeys,
+        pyth_oracle::{Command, CommandHeader, PriceStatus, PublisherArgs, UpdPriceArgs},
+    },
+};
+
+pub fn parse_pyth_oracle(
+    instruction: &CompiledInstruction,
+    account_keys: &AccountKeys,
+) -> Result<ParsedInstructionEnum, ParseInstructionError> {
+    let not_parsable =
+        || ParseInstructionError::InstructionNotParsable(ParsableProgram::PythOracle);
+    let command = CommandHeader::command(&instruction.data).ok_or_else(not_parsable)?;
+    match instruction.accounts.iter().max() {
+        Some(index) if (*index as usize) < account_keys.len() => {}
+        _ => {
+            // Runtime should prevent this from ever happening
+            return Err(ParseInstructionError::InstructionKeyMismatch(
+                ParsableProgram::PythOracle,
+            ));
+        }
+    }
+    match command {
+        Command::UpdPrice | Command::UpdPriceNoFailOnError => {
+            let args =
+                UpdPriceArgs::from_instruction_data(&instruction.data).ok_or_else(not_parsable)?;
+            check_num_pyth_oracle_accounts(&instruction.accounts, 2)?;
+            let instruction_type = if command == Command::UpdPrice {
+                "updPrice"
+            } else {
+                "updPriceNoFailOnError"
+            };
+            Ok(ParsedInstructionEnum {
+                instruction_type: instruction_type.to_string(),
+                info: json!({
+                    "publisher": account_keys[instruction.accounts[0] as usize].to_string(),
+                    "priceAccount": account_keys[instruction.accounts[1] as usize].to_string(),
+                    "status": UiPriceStatus::from(PriceStatus::from_u32(args.status)),
+                    "price": args.price,
+                    "conf": args.conf,
+                    "publishSlot": args.pub_slot,
+                }),
+            })
+        }
+        Command::AddPublisher | Command::DelPublisher => {
+            let args =
+                PublisherArgs::from_instruction_data(&instruction.data).ok_or_else(not_parsable)?;
+            check_num_pyth_oracle_accounts(&instruction.accounts, 2)?;
+            let instruction_type = if command == Command::AddPublisher {
+                "addPublisher"
+            } else {
+                "delPublisher"
+            };
+            Ok(ParsedInstructionEnum {
+                instruction_type: instruction_type.to_string(),
+                info: json!({
+                    "fundingAccount": account_keys[instruction.accounts[0] as usize].to_string(),
+                    "priceAccount": account_keys[instruction.accounts[1] as usize].to_string(),
+                    "publisher": args.publisher.to_string(),
+                }),
+            })
+        }
+        _ => Err(not_parsable()),
+    }
+}
+
+fn check_num_pyth_oracle_accounts(
+    accounts: &[u8],
+    num: usize,
+) -> Result<(), ParseInstructionError> {
+    check_num_accounts(accounts, num, ParsableProgram::PythOracle)
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        solana_sdk::{message::Message, pubkey::Pubkey, pyth_oracle},
+    };
+
+    #[test]
+    fn test_parse_pyth_oracle_upd_price_ix() {
+        let publisher = Pubkey::new_unique();
+        let price_account = Pubkey::new_unique();
+        for (fail_on_error, instruction_type) in
+            [(true, "updPrice"), (false, "updPriceNoFailOnError")]
+        {
+            let instruction = pyth_oracle::upd_price(
+                &publisher,
+                &price_account,
+                PriceStatus::Trading,
+                2_000_000,
+                300,
+                42,
+                fail_on_error,
+            );
+            let mut message = Message::new(&[instruction], None);
+            assert_eq!(
+                parse_pyth_oracle(
+                    &message.instructions[0],
+                    &AccountKeys::new(&message.account_keys, None)
+                )
+                .unwrap(),
+                ParsedInstructionEnum {
+                    instruction_type: instruction_type.to_string(),
+                    info: json!({
+                        "publisher": publisher.to_string(),
+                        "priceAccount": price_account.to_string(),
+                        "status": "trading",
+                        "price": 2_000_000,
+                        "conf": 300,
+                        "publishSlot": 42,
+                    }),
+                }
+            );
+            assert!(parse_pyth_oracle(
+                &message.instructions[0],
+                &AccountKeys::new(&message.account_keys[0..1], None)
+            )
+            .is_err());
+            message.instructions[0].accounts.pop();
+            message.instructions[0].accounts.pop();
+            assert!(parse_pyth_oracle(
+                &message.instructions[0],
+                &AccountKeys::new(&message.account_keys, None)
+            )
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn test_parse_pyth_oracle_add_publisher_ix() {
+        let funding = Pubkey::new_unique();
+        let price_account = Pubkey::new_unique();
+        let publisher = Pubkey::new_unique();
+        let instruction = pyth_oracle::add_publisher(&funding, &price_account, &publisher);
+        let mut message = Message::new(&[instruction], None);
+        assert_eq!(
+            parse_pyth_oracle(
+                &message.instructions[0],
+                &AccountKeys::new(&message.account_keys, None)
+            )
+            .unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "addPublisher".to_string(),
+                info: json!({
+                    "fundingAccount": funding.to_string(),
+                    "priceAccount": price_account.to_string(),
+                    "publisher": publisher.to_string(),
+                }),
+            }
+        );
+
+        message.instructions[0].data.truncate(20);
+        assert!(parse_pyth_oracle(
+            &message.instructions[0],
+            &AccountKeys::new(&message.account_keys, None)
+        )
+        .is_err());
+    }
+}
@@ -7,14 +7,17 @@ use {
     log::*,
     serde::{de::Deserializer, Deserialize, Serialize},
     solana_core::{
-        consensus::Tower, tower_storage::TowerStorage, validator::ValidatorStartProgress,
+        consensus::Tower, publisher_lane::PublisherLane, tower_storage::TowerStorage,
+        validator::ValidatorStartProgress,
     },
     solana_gossip::{
         cluster_info::ClusterInfo, legacy_contact_info::LegacyContactInfo as ContactInfo,
     },
     solana_rpc::rpc::verify_pubkey,
     solana_rpc_client_api::{config::RpcAccountIndex, custom_error::RpcCustomError},
-    solana_runtime::{accounts_index::AccountIndex, bank_forks::BankForks},
+    solana_runtime::{
+        accounts_index::AccountIndex, bank_forks::BankForks, block_cost_limits::MAX_BLOCK_UNITS,
+    },
     solana_sdk::{
         exit::Exit,
         pubkey::Pubkey,
@@ -49,6 +52,7 @@ pub struct AdminRpcRequestMetadata {
     pub authorized_voter_keypairs: Arc<RwLock<Vec<Arc<Keypair>>>>,
     pub tower_storage: Arc<dyn TowerStorage>,
     pub staked_nodes_overrides: Arc<RwLock<HashMap<Pubkey, u64>>>,
+    pub publisher_lane: Arc<RwLock<Arc<PublisherLane>>>,
     pub post_init: Arc<RwLock<Option<AdminRpcRequestMetadataPostInit>>>,
 }
 impl Metadata for AdminRpcRequestMetadata {}
@@ -197,6 +201,9 @@ pub trait AdminRpc {
     #[rpc(meta, name = "setStakedNodesOverrides")]
     fn set_staked_nodes_overrides(&self, meta: Self::Metadata, path: String) -> Result<()>;
 
+    #[rpc(meta, name = "setPublisherLane")]
+    fn set_publisher_lane(&self, meta: Self::Metadata, path: String) -> Result<()>;
+
     #[rpc(meta, name = "contactInfo")]
     fn contact_info(&self, meta: Self::Metadata) -> Result<AdminRpcContactInfo>;
 
@@ -347,6 +354,21 @@ impl AdminRpc for AdminRpcImpl {
         Ok(())
     }
 
+    fn set_publisher_lane(&self, meta: Self::Metadata, path: String) -> Result<()> {
+        let publisher_lane = load_publisher_lane(&path).map_err(|err| {
+            error!("Failed to load publisher lane from {}: {}", &path, err);
+            jsonrpc_core::error::Error::internal_error()
+        })?;
+        info!(
+            "Publisher lane loaded from {}: {} publishers, {} reserved block units",
+            path,
+            publisher_lane.publishers.len(),
+            publisher_lane.reserved_block_units
+        );
+        *meta.publisher_lane.write().unwrap() = Arc::new(publisher_lane);
+        Ok(())
+    }
+
     fn contact_info(&self, meta: Self::Metadata) -> Result<AdminRpcContactInfo> {
         meta.with_post_init(|post_init| Ok(post_init.cluster_info.my_contact_info().into()))
     }
@@ -601,6 +623,51 @@ pub fn load_staked_nodes_overrides(
     }
 }
 
+#[derive(Default, Deserialize, Clone)]
+pub struct PublisherLaneConfig {
+    #[serde(deserialize_with = "deserialize_pubkey_set")]
+    pub publishers: HashSet<Pubkey>,
+    pub reserved_block_units: u64,
+}
+
+pub fn deserialize_pubkey_set<'de, D>(des: D) -> std::result::Result<HashSet<Pubkey>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let container: Vec<String> = serde::Deserialize::deserialize(des)?;
+    container
+        .iter()
+        .map(|key| {
+            Pubkey::try_from(key.as_str()).map_err(|_| {
+                serde::de::Error::invalid_value(serde::de::Unexpected::Str(key), &"PubKey")
+            })
+        })
+        .collect()
+}
+
+pub fn load_publisher_lane(
+    path: &String,
+) -> std::result::Result<PublisherLane, Box<dyn error::Error>> {
+    debug!("Loading publisher lane configuration from {}", path);
+    if Path::new(&path).exists() {
+        let file = std::fs::File::open(path)?;
+        let config: PublisherLaneConfig = serde_yaml::from_reader(file)?;
+        if config.reserved_block_units > MAX_BLOCK_UNITS {
+            return Err(format!(
+                "Publisher lane reserves {} block units, more than the block limit of {}.",
+                config.reserved_block_units, MAX_BLOCK_UNITS
+            )
+            .into());
+        }
+        Ok(PublisherLane::new(
+            config.publishers,
+            config.reserved_block_units,
+        ))
+    } else {
+        Err(format!("Publisher lane provided '{path}' a non-existing file path.").into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use {
@@ -639,7 +706,7 @@ mod tests {
     }
 
     impl RpcHandler {
-        fn _start() -> Self {
+        fn start() -> Self {
             Self::start_with_config(TestConfig::default())
         }
 
@@ -675,6 +742,7 @@ mod tests {
                     repair_whitelist,
                 }))),
                 staked_nodes_overrides: Arc::new(RwLock::new(HashMap::new())),
+                publisher_lane: Arc::default(),
             };
             let mut io = MetaIoHandler::default();
             io.extend_with(AdminRpcImpl.to_delegate());
@@ -1012,4 +1080,44 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_set_publisher_lane() {
+        let RpcHandler { io, meta, .. } = RpcHandler::start();
+        let config_dir = tempfile::tempdir().unwrap();
+        let publisher = Pubkey::new_unique();
+        let set_publisher_lane = |reserved_block_units: u64| {
+            let path = config_dir.path().join("publisher-lane.yml");
+            std::fs::write(
+                &path,
+                format!(
+                    "publishers: [{publisher}]\nreserved_block_units: {reserved_block_units}\n"
+                ),
+            )
+            .unwrap();
+            let req = format!(
+                r#"{{"jsonrpc":"2.0","id":1,"method":"setPublisherLane","params":["{}"]}}"#,
+                path.display(),
+            );
+            let res = io.handle_request_sync(&req, meta.clone());
+            serde_json::from_str::<Value>(&res.expect("actual response"))
+                .expect("actual response deserialization")
+        };
+
+        let result = set_publisher_lane(1_000);
+        assert_eq!(result["result"], Value::Null);
+        assert!(result.get("error").is_none());
+        assert_eq!(
+            **meta.publisher_lane.read().unwrap(),
+            PublisherLane::new(HashSet::from([publisher]), 1_000)
+        );
+
+        // a reservation larger than the block is rejected and the lane is kept
+        let result = set_publisher_lane(MAX_BLOCK_UNITS + 1);
+        assert!(result.get("error").is_some());
+        assert_eq!(
+            meta.publisher_lane.read().unwrap().reserved_block_units,
+            1_000
+        );
+    }
 }
@@ -240,6 +240,11 @@ fn main() {
         .unwrap_or_default()
         .collect();
 
+    let account_recordings: Vec<_> = matches
+        .values_of("replay_accounts")
+        .unwrap_or_default()
+        .collect();
+
     let accounts_to_clone: HashSet<_> = pubkeys_of(&matches, "clone_account")
         .map(|v| v.into_iter().collect())
         .unwrap_or_default();
@@ -358,6 +363,7 @@ fn main() {
             validator_exit: genesis.validator_exit.clone(),
             authorized_voter_keypairs: genesis.authorized_voter_keypairs.clone(),
             staked_nodes_overrides: genesis.staked_nodes_overrides.clone(),
+            publisher_lane: genesis.publisher_lane.clone(),
             post_init: admin_service_post_init.clone(),
             tower_storage: tower_storage.clone(),
         },
@@ -385,6 +391,7 @@ fn main() {
                 String
             ),
             timeout: None,
+            local_ledger_storage_path: None,
         })
     } else {
         None
@@ -414,6 +421,11 @@ fn main() {
             println!("Error: add_accounts_from_directories failed: {e}");
             exit(1);
         })
+        .replay_accounts_from_files(&account_recordings)
+        .unwrap_or_else(|e| {
+            println!("Error: replay_accounts_from_files failed: {e}");
+            exit(1);
+        })
         .deactivate_features(&features_to_deactivate);
 
     genesis.rpc_config(JsonRpcConfig {
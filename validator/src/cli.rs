@@ -13,7 +13,11 @@ use {
     solana_core::banking_trace::{DirByteLimit, BANKING_TRACE_DIR_DEFAULT_BYTE_LIMIT},
     solana_faucet::faucet::{self, FAUCET_PORT},
     solana_net_utils::{MINIMUM_VALIDATOR_PORT_RANGE_WIDTH, VALIDATOR_PORT_RANGE},
-    solana_rpc::{rpc::MAX_REQUEST_BODY_SIZE, rpc_pubsub_service::PubSubConfig},
+    solana_rpc::{
+        rpc::MAX_REQUEST_BODY_SIZE,
+        rpc_pubsub_service::PubSubConfig,
+        rpc_rate_limiter::{MethodQuota, DEFAULT_API_KEY_HEADER, DEFAULT_CLIENT_IP_HEADER},
+    },
     solana_rpc_client_api::request::MAX_MULTIPLE_ACCOUNTS,
     solana_runtime::{
         accounts_db::{
@@ -220,6 +224,18 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                 .takes_value(false)
                 .help("Upload new confirmed blocks into a BigTable instance"),
         )
+        .arg(
+            Arg::with_name("rpc_local_ledger_storage")
+                .long("rpc-local-ledger-storage")
+                .value_name("DIR")
+                .takes_value(true)
+                .requires("enable_rpc_transaction_history")
+                .help("Keep the long-term ledger storage in a database in this directory \
+                       instead of a BigTable instance. Historical transaction info is \
+                       fetched from it as a fallback to local ledger data, and new \
+                       confirmed blocks are uploaded into it with \
+                       --enable-bigtable-ledger-upload"),
+        )
         .arg(
             Arg::with_name("enable_extended_tx_metadata_storage")
                 .long("enable-extended-tx-metadata-storage")
@@ -228,6 +244,19 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                 .help("Include CPI inner instructions, logs, and return data in \
                        the historical transaction info stored"),
         )
+        .arg(
+            Arg::with_name("rpc_account_history_owner")
+                .long("rpc-account-history-owner")
+                .value_name("PROGRAM ID")
+                .validator(is_pubkey)
+                .multiple(true)
+                .takes_value(true)
+                .requires("enable_rpc_transaction_history")
+                .help("Record the state of the accounts owned by this program in every \
+                       slot that writes them, so that the getAccountInfo and \
+                       getMultipleAccounts JSON RPC methods can look them up at a past \
+                       slot with the `atSlot` parameter. May be specified multiple times"),
+        )
         .arg(
             Arg::with_name("rpc_max_multiple_accounts")
                 .long("rpc-max-multiple-accounts")
@@ -785,6 +814,18 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                             number of QUIC streams permitted from the peer and vote packet sender stage.
                             Format of the file: `staked_map_id: {<pubkey>: <SOL stake amount>}"),
         )
+        .arg(
+            Arg::with_name("publisher_lane")
+                .long("publisher-lane")
+                .value_name("PATH")
+                .takes_value(true)
+                .help("Provide path to a yaml file with the publisher lane configuration. Transactions
+                            signed by one of the publishers are scheduled ahead of other transactions
+                            and may use block capacity that is reserved for them. The reservation
+                            may not exceed the block cost limit.
+                            Format of the file: `publishers: [<pubkey>, ...]`,
+                            `reserved_block_units: <compute units>`"),
+        )
         .arg(
             Arg::with_name("bind_address")
                 .long("bind-address")
@@ -1020,6 +1061,80 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                 .default_value(&default_args.rpc_max_request_body_size)
                 .help("The maximum request body size accepted by rpc service"),
         )
+        .arg(
+            Arg::with_name("rpc_ip_rate_limit")
+                .long("rpc-ip-rate-limit")
+                .value_name("UNITS_PER_SECOND")
+                .takes_value(true)
+                .validator(nonzero_u64_validator)
+                .help("Limit the JSON RPC calls of every client IP address to this many \
+                       weight units per second. Most methods weigh 1 unit, expensive \
+                       methods such as getProgramAccounts weigh more, see \
+                       --rpc-method-quota"),
+        )
+        .arg(
+            Arg::with_name("rpc_api_key_rate_limit")
+                .long("rpc-api-key-rate-limit")
+                .value_name("API_KEY:UNITS_PER_SECOND")
+                .takes_value(true)
+                .multiple(true)
+                .validator(|s| parse_rpc_api_key_rate_limit(&s).map(|_| ()))
+                .help("Limit the JSON RPC calls presenting this API key to this many weight \
+                       units per second, instead of limiting them by IP address. \
+                       May be specified multiple times"),
+        )
+        .arg(
+            Arg::with_name("rpc_rate_limit_burst_seconds")
+                .long("rpc-rate-limit-burst-seconds")
+                .value_name("SECONDS")
+                .takes_value(true)
+                .validator(nonzero_u64_validator)
+                .default_value(&default_args.rpc_rate_limit_burst_seconds)
+                .help("Allow rate limited JSON RPC clients to spend this many seconds of \
+                       their rate at once"),
+        )
+        .arg(
+            Arg::with_name("rpc_client_ip_header")
+                .long("rpc-client-ip-header")
+                .value_name("HEADER")
+                .takes_value(true)
+                .default_value(DEFAULT_CLIENT_IP_HEADER)
+                .help("HTTP header set by the reverse proxy in front of the JSON RPC service \
+                       to the IP address of the client. The last address of the header \
+                       that is not a --rpc-trusted-proxy identifies the client for \
+                       --rpc-ip-rate-limit"),
+        )
+        .arg(
+            Arg::with_name("rpc_trusted_proxy")
+                .long("rpc-trusted-proxy")
+                .value_name("IP_ADDRESS")
+                .takes_value(true)
+                .multiple(true)
+                .validator(solana_net_utils::is_host)
+                .help("IP address of a reverse proxy in front of the JSON RPC service. \
+                       Requests coming from it are identified by --rpc-client-ip-header \
+                       rather than by their peer address. May be specified multiple times"),
+        )
+        .arg(
+            Arg::with_name("rpc_api_key_header")
+                .long("rpc-api-key-header")
+                .value_name("HEADER")
+                .takes_value(true)
+                .default_value(DEFAULT_API_KEY_HEADER)
+                .help("HTTP header holding the API key of a JSON RPC client"),
+        )
+        .arg(
+            Arg::with_name("rpc_method_quota")
+                .long("rpc-method-quota")
+                .value_name("METHOD:WEIGHT[:MAX_CONCURRENT]")
+                .takes_value(true)
+                .multiple(true)
+                .validator(|s| parse_rpc_method_quota(&s).map(|_| ()))
+                .help("Charge this many weight units for every call of a JSON RPC method, \
+                       and optionally run at most this many calls of it at once. Enables \
+                       the method quotas, which default to weighing expensive methods \
+                       heavily. May be specified multiple times"),
+        )
         .arg(
             Arg::with_name("enable_accountsdb_repl")
                 .long("enable-accountsdb-repl")
@@ -1502,6 +1617,19 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                 .after_help("Note: the new staked nodes overrides only applies to the \
                          currently running validator instance")
         )
+        .subcommand(
+            SubCommand::with_name("publisher-lane")
+                .about("Replaces the publisher lane of the validator.")
+                .arg(
+                    Arg::with_name("path")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Provide path to a file with the publisher lane configuration."),
+                )
+                .after_help("Note: the new publisher lane only applies to the \
+                         currently running validator instance")
+        )
         .subcommand(
             SubCommand::with_name("wait-for-restart-window")
                 .about("Monitor the validator for a good time to restart")
@@ -1666,6 +1794,7 @@ pub struct DefaultArgs {
     pub rpc_bigtable_instance_name: String,
     pub rpc_bigtable_app_profile_id: String,
     pub rpc_max_request_body_size: String,
+    pub rpc_rate_limit_burst_seconds: String,
     pub rpc_pubsub_worker_threads: String,
 
     pub maximum_local_snapshot_age: String,
@@ -1778,6 +1907,7 @@ impl DefaultArgs {
             accounts_shrink_ratio: DEFAULT_ACCOUNTS_SHRINK_RATIO.to_string(),
             tpu_connection_pool_size: DEFAULT_TPU_CONNECTION_POOL_SIZE.to_string(),
             rpc_max_request_body_size: MAX_REQUEST_BODY_SIZE.to_string(),
+            rpc_rate_limit_burst_seconds: "10".to_string(),
             exit_min_idle_time: "10".to_string(),
             exit_max_delinquent_stake: "5".to_string(),
             wait_for_restart_window_min_idle_time: "10".to_string(),
@@ -1817,6 +1947,58 @@ pub fn port_range_validator(port_range: String) -> Result<(), String> {
     }
 }
 
+/// Parse an `API_KEY:UNITS_PER_SECOND` rate limit
+pub fn parse_rpc_api_key_rate_limit(value: &str) -> Result<(String, u64), String> {
+    let (api_key, units_per_second) = value
+        .rsplit_once(':')
+        .ok_or_else(|| format!("Invalid API key rate limit {value}"))?;
+    let units_per_second = units_per_second
+        .parse::<u64>()
+        .map_err(|err| format!("Invalid API key rate limit {value}: {err}"))?;
+    if api_key.is_empty() || units_per_second == 0 {
+        return Err(format!("Invalid API key rate limit {value}"));
+    }
+    Ok((api_key.to_string(), units_per_second))
+}
+
+/// Parse a `METHOD:WEIGHT[:MAX_CONCURRENT]` method quota
+pub fn parse_rpc_method_quota(value: &str) -> Result<(String, MethodQuota), String> {
+    let invalid_quota = |err: String| format!("Invalid method quota {value}: {err}");
+    let mut parts = value.split(':');
+    let method = parts.next().unwrap_or_default();
+    if method.is_empty() {
+        return Err(invalid_quota("missing method".to_string()));
+    }
+    let weight = parts
+        .next()
+        .ok_or_else(|| invalid_quota("missing weight".to_string()))?
+        .parse::<u64>()
+        .map_err(|err| invalid_quota(err.to_string()))?;
+    let max_concurrent = parts
+        .next()
+        .map(|max_concurrent| max_concurrent.parse::<usize>())
+        .transpose()
+        .map_err(|err| invalid_quota(err.to_string()))?;
+    if parts.next().is_some() {
+        return Err(invalid_quota("too many fields".to_string()));
+    }
+    Ok((
+        method.to_string(),
+        MethodQuota {
+            weight,
+            max_concurrent,
+        },
+    ))
+}
+
+fn nonzero_u64_validator(value: String) -> Result<(), String> {
+    match value.parse::<u64>() {
+        Ok(0) => Err("value must be greater than zero".to_string()),
+        Ok(_) => Ok(()),
+        Err(err) => Err(format!("{err:?}")),
+    }
+}
+
 fn hash_validator(hash: String) -> Result<(), String> {
     Hash::from_str(&hash)
         .map(|_| ())
@@ -2048,6 +2230,20 @@ pub fn test_app<'a>(version: &'a str, default_args: &'a DefaultTestArgs) -> App<
                         If the ledger already exists then this parameter is silently ignored",
                 ),
         )
+        .arg(
+            Arg::with_name("replay_accounts")
+                .long("replay-accounts")
+                .value_name("RECORDING.JSONL")
+                .takes_value(true)
+                .multiple(true)
+                .help(
+                    "Replay the account states recorded in the provided file slot by slot. \
+                        The file holds one JSON object per line, in the format of `solana account \
+                        --output json` with an additional `slot` field. The states of the first \
+                        recorded slot are loaded into genesis, later states are written to the \
+                        leader banks of the validator as it advances",
+                ),
+        )
         .arg(
             Arg::with_name("no_bpf_jit")
                 .long("no-bpf-jit")
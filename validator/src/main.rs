@@ -10,6 +10,7 @@ use {
     solana_core::{
         banking_trace::DISABLED_BAKING_TRACE_DIR,
         ledger_cleanup_service::{DEFAULT_MAX_LEDGER_SHREDS, DEFAULT_MIN_MAX_LEDGER_SHREDS},
+        publisher_lane::PublisherLane,
         system_monitor_service::SystemMonitorService,
         tower_storage,
         tpu::DEFAULT_TPU_COALESCE_MS,
@@ -24,6 +25,7 @@ use {
     solana_rpc::{
         rpc::{JsonRpcConfig, RpcBigtableConfig},
         rpc_pubsub_service::PubSubConfig,
+        rpc_rate_limiter::{RateLimit, RpcRateLimitConfig},
     },
     solana_rpc_client::rpc_client::RpcClient,
     solana_rpc_client_api::config::RpcLeaderScheduleConfig,
@@ -49,9 +51,14 @@ use {
     solana_tpu_client::tpu_connection_cache::DEFAULT_TPU_ENABLE_UDP,
     solana_validator::{
         admin_rpc_service,
-        admin_rpc_service::{load_staked_nodes_overrides, StakedNodesOverrides},
+        admin_rpc_service::{
+            load_publisher_lane, load_staked_nodes_overrides, StakedNodesOverrides,
+        },
         bootstrap,
-        cli::{app, warn_for_deprecated_arguments, DefaultArgs},
+        cli::{
+            app, parse_rpc_api_key_rate_limit, parse_rpc_method_quota,
+            warn_for_deprecated_arguments, DefaultArgs,
+        },
         dashboard::Dashboard,
         ledger_lockfile, lock_ledger, new_spinner_progress_bar, println_name_value,
         redirect_stderr_to_file,
@@ -384,6 +391,48 @@ fn hardforks_of(matches: &ArgMatches<'_>, name: &str) -> Option<Vec<Slot>> {
     }
 }
 
+fn rpc_rate_limit_config_of(matches: &ArgMatches<'_>) -> Option<RpcRateLimitConfig> {
+    if !(matches.is_present("rpc_ip_rate_limit")
+        || matches.is_present("rpc_api_key_rate_limit")
+        || matches.is_present("rpc_method_quota"))
+    {
+        return None;
+    }
+    let burst_seconds = value_t_or_exit!(matches, "rpc_rate_limit_burst_seconds", u64);
+    let rate_limit = |units_per_second: u64| RateLimit {
+        units_per_second,
+        burst: units_per_second.saturating_mul(burst_seconds),
+    };
+    Some(RpcRateLimitConfig {
+        ip_rate_limit: value_t!(matches, "rpc_ip_rate_limit", u64)
+            .ok()
+            .map(rate_limit),
+        api_key_rate_limits: matches
+            .values_of("rpc_api_key_rate_limit")
+            .into_iter()
+            .flatten()
+            .map(|value| {
+                let (api_key, units_per_second) = parse_rpc_api_key_rate_limit(value).unwrap();
+                (api_key, rate_limit(units_per_second))
+            })
+            .collect(),
+        trusted_proxies: matches
+            .values_of("rpc_trusted_proxy")
+            .into_iter()
+            .flatten()
+            .map(|host| solana_net_utils::parse_host(host).unwrap())
+            .collect(),
+        client_ip_header: value_t_or_exit!(matches, "rpc_client_ip_header", String),
+        api_key_header: value_t_or_exit!(matches, "rpc_api_key_header", String),
+        method_quotas: matches
+            .values_of("rpc_method_quota")
+            .into_iter()
+            .flatten()
+            .map(|value| parse_rpc_method_quota(value).unwrap())
+            .collect(),
+    })
+}
+
 fn validators_set(
     identity_pubkey: &Pubkey,
     matches: &ArgMatches<'_>,
@@ -616,6 +665,23 @@ pub fn main() {
                 });
             return;
         }
+        ("publisher-lane", Some(subcommand_matches)) => {
+            let path = subcommand_matches.value_of("path").unwrap();
+
+            let admin_client = admin_rpc_service::connect(&ledger_path);
+            admin_rpc_service::runtime()
+                .block_on(async move {
+                    admin_client
+                        .await?
+                        .set_publisher_lane(path.to_string())
+                        .await
+                })
+                .unwrap_or_else(|err| {
+                    println!("setPublisherLane request failed: {err}");
+                    exit(1);
+                });
+            return;
+        }
         ("set-identity", Some(subcommand_matches)) => {
             let require_tower = subcommand_matches.is_present("require_tower");
 
@@ -817,6 +883,20 @@ pub fn main() {
         .staked_map_id,
     ));
 
+    let publisher_lane = Arc::new(RwLock::new(Arc::new(
+        match matches.value_of("publisher_lane") {
+            None => PublisherLane::default(),
+            Some(p) => load_publisher_lane(&p.to_string()).unwrap_or_else(|err| {
+                error!("Failed to load publisher-lane from {}: {}", p, err);
+                clap::Error::with_description(
+                    "Failed to load configuration of publisher-lane argument",
+                    clap::ErrorKind::InvalidValue,
+                )
+                .exit()
+            }),
+        },
+    )));
+
     let init_complete_file = matches.value_of("init_complete_file");
 
     let rpc_bootstrap_config = bootstrap::RpcBootstrapConfig {
@@ -1062,6 +1142,7 @@ pub fn main() {
 
     let rpc_bigtable_config = if matches.is_present("enable_rpc_bigtable_ledger_storage")
         || matches.is_present("enable_bigtable_ledger_upload")
+        || matches.is_present("rpc_local_ledger_storage")
     {
         Some(RpcBigtableConfig {
             enable_bigtable_ledger_upload: matches.is_present("enable_bigtable_ledger_upload"),
@@ -1074,6 +1155,9 @@ pub fn main() {
             timeout: value_t!(matches, "rpc_bigtable_timeout", u64)
                 .ok()
                 .map(Duration::from_secs),
+            local_ledger_storage_path: matches
+                .value_of("rpc_local_ledger_storage")
+                .map(PathBuf::from),
         })
     } else {
         None
@@ -1140,11 +1224,15 @@ pub fn main() {
             rpc_niceness_adj: value_t_or_exit!(matches, "rpc_niceness_adj", i8),
             account_indexes: account_indexes.clone(),
             rpc_scan_and_fix_roots: matches.is_present("rpc_scan_and_fix_roots"),
+            account_history_owners: values_t!(matches, "rpc_account_history_owner", Pubkey)
+                .map(|owners| owners.into_iter().collect())
+                .unwrap_or_default(),
             max_request_body_size: Some(value_t_or_exit!(
                 matches,
                 "rpc_max_request_body_size",
                 usize
             )),
+            rate_limit_config: rpc_rate_limit_config_of(&matches),
         },
         geyser_plugin_config_files,
         rpc_addrs: value_t!(matches, "rpc_port", u16).ok().map(|rpc_port| {
@@ -1235,6 +1323,7 @@ pub fn main() {
             ..RuntimeConfig::default()
         },
         staked_nodes_overrides: staked_nodes_overrides.clone(),
+        publisher_lane: publisher_lane.clone(),
         replay_slots_concurrently: matches.is_present("replay_slots_concurrently"),
         ..ValidatorConfig::default()
     };
@@ -1502,6 +1591,7 @@ pub fn main() {
             post_init: admin_service_post_init.clone(),
             tower_storage: validator_config.tower_storage.clone(),
             staked_nodes_overrides,
+            publisher_lane,
         },
     );
 
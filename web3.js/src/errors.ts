@@ -27,6 +27,9 @@ export const SolanaJSONRPCErrorCode = {
   JSON_RPC_SERVER_ERROR_BLOCK_STATUS_NOT_AVAILABLE_YET: -32014,
   JSON_RPC_SERVER_ERROR_UNSUPPORTED_TRANSACTION_VERSION: -32015,
   JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED: -32016,
+  JSON_RPC_SERVER_ERROR_ACCOUNT_HISTORY_NOT_AVAILABLE: -32017,
+  JSON_RPC_SERVER_ERROR_RATE_LIMIT_EXCEEDED: -32018,
+  JSON_RPC_SERVER_ERROR_METHOD_CONCURRENCY_LIMIT_EXCEEDED: -32019,
 } as const;
 export type SolanaJSONRPCErrorCodeEnum =
   typeof SolanaJSONRPCErrorCode[keyof typeof SolanaJSONRPCErrorCode];